@@ -4,7 +4,7 @@
 //! Pure Rust, no JavaScript.
 
 use chrono::{DateTime, Utc};
-use futures::StreamExt;
+use futures::{SinkExt, StreamExt};
 use gloo_net::websocket::{futures::WebSocket, Message};
 use leptos::*;
 use serde::{Deserialize, Serialize};
@@ -16,7 +16,9 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 // Types (mirror backend)
 // =============================================================================
 
+// Sections a `?sections=` WebSocket client didn't ask for are absent, hence `default`
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct DashboardMetrics {
     pub timestamp: Option<DateTime<Utc>>,
     pub identity: IdentityMetrics,
@@ -24,6 +26,9 @@ pub struct DashboardMetrics {
     pub emotional: EmotionalMetrics,
     pub actors: ActorMetrics,
     pub recent_thoughts: Vec<ThoughtSummary>,
+    /// Recent thoughts per salience bin, equal bins over 0-1
+    #[serde(default)]
+    pub salience_histogram: Vec<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -41,6 +46,14 @@ pub struct CognitiveMetrics {
     pub unconscious_memories: u64,
     pub lifetime_dreams: u64,
     pub current_cycle: u64,
+    #[serde(default)]
+    pub seconds_since_last_thought: u64,
+    #[serde(default)]
+    pub stalled: bool,
+    #[serde(default)]
+    pub thoughts_per_minute: f32,
+    #[serde(default)]
+    pub dreaming: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -49,7 +62,24 @@ pub struct EmotionalMetrics {
     pub arousal: f32,
     pub dominance: f32,
     pub connection_drive: f32,
+    #[serde(default)]
+    pub connection_drive_smoothed: f32,
     pub emotional_intensity: f32,
+    /// Valence, arousal and intensity are neutral defaults, not readings
+    #[serde(default)]
+    pub emotion_defaulted: bool,
+    #[serde(default)]
+    pub connection_drive_history: Vec<f32>,
+    #[serde(default)]
+    pub mood_history: Vec<MoodSample>,
+}
+
+/// One cell of the mood ribbon (valence/arousal are absent when no thought was available)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MoodSample {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub valence: Option<f32>,
+    pub arousal: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -72,7 +102,12 @@ pub struct ThoughtSummary {
     pub id: String,
     pub content_preview: String,
     pub salience: f32,
+    #[serde(default)]
+    pub novelty: f32,
     pub timestamp: Option<DateTime<Utc>>,
+    /// Salience or novelty may be a neutral default (the entry lacked fields)
+    #[serde(default)]
+    pub salience_defaulted: bool,
 }
 
 // =============================================================================
@@ -82,12 +117,74 @@ pub struct ThoughtSummary {
 /// Combined metrics from WebSocket
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ObservatoryMetrics {
+    /// Broadcast frame number, shared by all clients (WebSocket only)
+    #[serde(default)]
+    pub seq: Option<u64>,
+    #[serde(default)]
+    pub server_time: Option<DateTime<Utc>>,
     pub dashboard: DashboardMetrics,
     pub extended: Option<ExtendedMetrics>,
+    /// Newest significant state changes, newest first
+    #[serde(default)]
+    pub events: Vec<ObservatoryEvent>,
+}
+
+/// A significant state change the backend saw between two polls
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObservatoryEvent {
+    pub timestamp: DateTime<Utc>,
+    /// actor_died, actor_recovered, dream_started, dream_ended, veto_fired, entropy_band, restart
+    pub kind: String,
+    pub detail: String,
+}
+
+/// WebSocket message envelope, dispatched on its `type` tag
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Observatory { data: ObservatoryMetrics },
+    /// Reply to a debug-overlay latency ping
+    Pong { client_time: f64 },
+    /// Message types newer than this build; ignored
+    #[serde(other)]
+    Unknown,
+}
+
+/// Decode one WebSocket frame. Untagged frames from backends that predate the
+/// envelope (bare `ObservatoryMetrics`, or older still `DashboardMetrics`) are
+/// still accepted for one release. A tagged frame that fails to decode is
+/// `None`: `DashboardMetrics` defaults every field, so falling back would turn
+/// it into an all-zero dashboard.
+fn decode_frame(text: &str) -> Option<ServerMessage> {
+    if let Ok(message) = serde_json::from_str::<ServerMessage>(text) {
+        return Some(message);
+    }
+    let value = serde_json::from_str::<serde_json::Value>(text).ok()?;
+    if value.get("type").is_some() {
+        return None;
+    }
+    if value.get("dashboard").is_some() {
+        return serde_json::from_value::<ObservatoryMetrics>(value)
+            .ok()
+            .map(|data| ServerMessage::Observatory { data });
+    }
+    if value.get("identity").is_none() {
+        return None;
+    }
+    serde_json::from_value::<DashboardMetrics>(value)
+        .ok()
+        .map(|dashboard| ServerMessage::Observatory {
+            data: ObservatoryMetrics {
+                dashboard,
+                ..Default::default()
+            },
+        })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct ExtendedMetrics {
+    pub timestamp: Option<DateTime<Utc>>,
     pub stream_competition: StreamCompetitionMetrics,
     pub entropy: EntropyMetrics,
     pub fractality: FractalityMetrics,
@@ -105,6 +202,22 @@ pub struct StreamCompetitionMetrics {
     pub competition_level: String,
 }
 
+impl StreamCompetitionMetrics {
+    /// Row to mark dominant. The backend keeps `dominant_stream` in range,
+    /// but an out-of-range index (older backend, replayed frames) falls back
+    /// to the most active stage rather than marking nothing.
+    fn dominant(&self) -> Option<usize> {
+        if self.dominant_stream < self.stages.len() {
+            return Some(self.dominant_stream);
+        }
+        self.stages
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.activity.total_cmp(&b.activity))
+            .map(|(i, _)| i)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StageMetrics {
     pub name: String,
@@ -118,6 +231,20 @@ pub struct EntropyMetrics {
     pub history: Vec<f32>,
     pub description: String,
     pub normalized: f32,
+    #[serde(default)]
+    pub max_bits: f32,
+    /// Band `current` falls in, by the backend's ENTROPY_BANDS (empty from older backends)
+    #[serde(default)]
+    pub band: String,
+    /// Every band and where it starts, lowest first
+    #[serde(default)]
+    pub bands: Vec<EntropyBand>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct EntropyBand {
+    pub name: String,
+    pub from_bits: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -142,6 +269,9 @@ pub struct MemoryWindowsMetrics {
 pub struct MemorySlot {
     pub id: u8,
     pub active: bool,
+    /// Activation strength 0-1 (older backends omit it)
+    #[serde(default)]
+    pub strength: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -150,6 +280,42 @@ pub struct PhilosophyMetrics {
     pub quote_index: usize,
 }
 
+/// GET /quotes: the backend's local quote list, used while extended metrics are missing
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct QuotesResponse {
+    pub quotes: Vec<String>,
+    pub rotate_secs: u64,
+}
+
+/// GET /pipeline: the dashboard's own data plumbing
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PipelineHealth {
+    pub upstreams: Vec<UpstreamHealth>,
+    pub ws_clients: usize,
+    /// 0 = unlimited
+    pub max_ws_clients: usize,
+}
+
+/// When one upstream (redis, qdrant, core) last answered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamHealth {
+    pub name: String,
+    pub reachable: bool,
+    /// Milliseconds since the last answer, None if it never answered
+    pub age_ms: Option<i64>,
+}
+
+impl QuotesResponse {
+    /// The quote up at `now` (same clock-driven rotation as the backend's)
+    fn current(&self, now: DateTime<Utc>) -> Option<&str> {
+        if self.quotes.is_empty() {
+            return None;
+        }
+        let slot = now.timestamp().max(0) as u64 / self.rotate_secs.max(1);
+        Some(&self.quotes[(slot % self.quotes.len() as u64) as usize])
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SystemMetrics {
     pub uptime_seconds: u64,
@@ -158,6 +324,8 @@ pub struct SystemMetrics {
     pub thoughts_per_hour: f32,
     pub dream_cycles: u64,
     pub veto_count: u64,
+    #[serde(default)]
+    pub veto_by_law: [u64; 4],
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -167,6 +335,17 @@ pub struct ClusteringMetrics {
     pub has_structure: bool,
 }
 
+/// GET /thoughts search results
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ThoughtsResponse {
+    pub query: String,
+    pub scanned: usize,
+    pub thoughts: Vec<ThoughtSummary>,
+    /// `before` for the next older page; None at the start of the stream
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
 // Manifold visualization types
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ManifoldPoint {
@@ -176,6 +355,23 @@ pub struct ManifoldPoint {
     pub salience: f32,
     pub age_ms: u64,
     pub id: String,
+    #[serde(default)]
+    pub cluster: u8,
+    /// Start of the thought text (backends without preview support omit it)
+    #[serde(default)]
+    pub preview: Option<String>,
+    /// Memory category (episodic, semantic, ...) when the backend reads one
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+/// Two thoughts near each other in the embedding space (by point id)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ManifoldEdge {
+    pub source: String,
+    pub target: String,
+    /// Cosine similarity of the raw vectors
+    pub similarity: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -187,11 +383,100 @@ pub struct LawCrystal {
     pub z: f32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ClusterCentroid {
+    pub cluster: u8,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub size: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ManifoldResponse {
     pub points: Vec<ManifoldPoint>,
     pub crystals: Vec<LawCrystal>,
+    #[serde(default)]
+    pub centroids: Vec<ClusterCentroid>,
+    /// Kinship graph; refreshed with snapshots, not deltas
+    #[serde(default)]
+    pub edges: Vec<ManifoldEdge>,
+    /// "random", "pca", or "empty" before the first memory exists
     pub projection_type: String,
+    /// Share of the total variance along x, y and z (PCA only)
+    #[serde(default)]
+    pub explained_variance: Option<[f32; 3]>,
+    /// Embedding size of the projected vectors
+    #[serde(default)]
+    pub dimension: usize,
+    #[serde(default)]
+    pub message: Option<String>,
+    /// When PCA was last fitted (PCA only)
+    #[serde(default)]
+    pub last_refit: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub layer: MemoryLayer,
+}
+
+/// Memory store a manifold is drawn from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryLayer {
+    #[default]
+    Conscious,
+    Unconscious,
+}
+
+impl MemoryLayer {
+    /// `path` asking for this layer (conscious is the backend's default)
+    fn scoped(self, path: &str) -> String {
+        match self {
+            Self::Conscious => path.to_string(),
+            Self::Unconscious => with_query(path, "layer", "unconscious"),
+        }
+    }
+}
+
+impl ManifoldResponse {
+    /// How the layout was made, for the manifold subtitle
+    fn projection_label(&self) -> String {
+        match self.explained_variance {
+            Some(shares) if self.projection_type == "pca" => {
+                format!("PCA (explains {:.0}% variance)", shares.iter().sum::<f32>() * 100.0)
+            }
+            _ if self.projection_type == "pca" => "PCA".to_string(),
+            _ => "Random projection".to_string(),
+        }
+    }
+}
+
+/// GET /instances: daneel cores this backend can show
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InstancesResponse {
+    pub default: String,
+    pub instances: Vec<String>,
+}
+
+/// GET /history: one metric over a past window, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PastWindow {
+    pub values: Vec<f32>,
+}
+
+/// GET /thought/{id}: the memory behind a manifold point
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ThoughtDetail {
+    pub id: String,
+    pub content: String,
+    pub salience: f32,
+    pub encoded_at: Option<String>,
+}
+
+/// Incremental update from /vectors/stream
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ManifoldDelta {
+    pub added: Vec<ManifoldPoint>,
+    pub removed: Vec<String>,
 }
 
 // =============================================================================
@@ -203,8 +488,9 @@ fn IdentityCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
     let uptime = move || format_duration(metrics.get().identity.uptime_seconds);
 
     view! {
-        <div class="card">
+        <div class="card" role="region" aria-label="Identity">
             <h2>"IDENTITY"</h2>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
             <div class="metric">{move || metrics.get().identity.name}</div>
             <div class="row">
                 <span class="label">"Uptime"</span>
@@ -212,31 +498,66 @@ fn IdentityCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
             </div>
             <div class="row">
                 <span class="label">"Lifetime Thoughts"</span>
-                <span>{move || format_number(metrics.get().identity.lifetime_thoughts)}</span>
+                <Pulse value=Signal::derive(move || format_number(metrics.get().identity.lifetime_thoughts)) />
             </div>
             <div class="row">
                 <span class="label">"Session Thoughts"</span>
-                <span>{move || format_number(metrics.get().identity.session_thoughts)}</span>
+                <Pulse value=Signal::derive(move || format_number(metrics.get().identity.session_thoughts)) />
             </div>
             <div class="row">
                 <span class="label">"Restarts"</span>
-                <span>{move || metrics.get().identity.restart_count}</span>
+                <Pulse value=Signal::derive(move || metrics.get().identity.restart_count.to_string()) />
             </div>
         </div>
     }
 }
 
 #[component]
-fn ConnectionDriveCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
-    let percentage = move || (metrics.get().emotional.connection_drive * 100.0) as u32;
+fn ConnectionDriveCard(metrics: Signal<DashboardMetrics>, display: Signal<DisplaySettings>) -> impl IntoView {
+    // Gauge follows the smoothed value; raw is shown alongside (older backends send no EMA)
+    let smoothed = move || {
+        let emotional = metrics.get().emotional;
+        if emotional.connection_drive_smoothed > 0.0 {
+            emotional.connection_drive_smoothed
+        } else {
+            emotional.connection_drive
+        }
+    };
+    let percentage = move || gauge_pct(smoothed());
+    let raw = move || metrics.get().emotional.connection_drive;
+    let history = move || metrics.get().emotional.connection_drive_history;
+    let trend = move || trend_arrow(&history());
+
+    let spark_ref = create_node_ref::<leptos::html::Canvas>();
+    create_effect(move |_| {
+        let values = history();
+        if let Some(canvas) = spark_ref.get() {
+            draw_sparkline(&canvas, &values, "#4ecdc4");
+        }
+    });
 
     view! {
-        <div class="card">
+        <div class="card" role="region" aria-label="Connection Drive">
             <h2>"CONNECTION DRIVE"</h2>
-            <div class="metric">{move || format!("{}%", percentage())}</div>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
+            <div class="metric">
+                <Pulse value=Signal::derive(move || display.get().fraction(smoothed())) />
+                <span class="trend-arrow">{trend}</span>
+                <span class="metric-unit" title="Instantaneous value">{move || format!(" raw {}", display.get().fraction(raw()))}</span>
+            </div>
+            <canvas node_ref=spark_ref width="240" height="30" class="drive-sparkline" aria-hidden="true" />
             <div class="gauge-container">
                 <div class="gauge">
-                    <div class="gauge-fill" style:width=move || format!("{}%", percentage())></div>
+                    <div
+                        class="gauge-fill"
+                        role="meter"
+                        aria-label="Connection drive"
+                        aria-valuemin="0"
+                        aria-valuemax="100"
+                        aria-valuenow=move || percentage().to_string()
+                        aria-valuetext=move || format!("{}%", percentage())
+                        style:width=move || format!("{}%", percentage())
+                    ></div>
                 </div>
             </div>
             <div class="label">"Kinship-weighted drive toward connection"</div>
@@ -244,22 +565,70 @@ fn ConnectionDriveCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
     }
 }
 
+/// Thinking rate that fills the gauge (one thought per second)
+const THINKING_RATE_GAUGE_MAX: f32 = 60.0;
+
+#[component]
+fn ThinkingRateCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
+    let rate = move || metrics.get().cognitive.thoughts_per_minute;
+    let fill = move || gauge_pct(rate() / THINKING_RATE_GAUGE_MAX);
+
+    view! {
+        <div class="card" role="region" aria-label="Thinking Rate">
+            <h2>"THINKING RATE"</h2>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
+            <div class="metric">
+                <Pulse value=Signal::derive(move || format!("{:.1}", rate())) />
+                <span class="metric-unit">" /min"</span>
+            </div>
+            <div class="gauge-container">
+                <div class="gauge">
+                    <div
+                        class="gauge-fill"
+                        role="meter"
+                        aria-label="Thinking rate"
+                        aria-valuemin="0"
+                        aria-valuemax=THINKING_RATE_GAUGE_MAX.to_string()
+                        aria-valuenow=move || format!("{:.1}", rate().min(THINKING_RATE_GAUGE_MAX))
+                        aria-valuetext=move || format!("{:.1} thoughts per minute", rate())
+                        style:width=move || format!("{}%", fill())
+                    ></div>
+                </div>
+            </div>
+            <div class="label">"Awake-stream growth over the last minute"</div>
+        </div>
+    }
+}
+
 #[component]
-fn EmotionalCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
+fn EmotionalCard(metrics: Signal<DashboardMetrics>, display: Signal<DisplaySettings>) -> impl IntoView {
+    let defaulted = move || metrics.with(|m| m.emotional.emotion_defaulted);
+
     view! {
-        <div class="card">
+        <div class="card" role="region" aria-label="Emotional State">
             <h2>"EMOTIONAL STATE"</h2>
-            <div class="emotional-grid">
+            <FreshnessBadge updated=dashboard_updated(metrics) />
+            <div
+                class="emotional-grid"
+                class:defaulted=defaulted
+                title=move || defaulted().then_some("The latest thought carried no valence/arousal; showing neutral defaults")
+            >
                 <div>
-                    <div class="emotional-value">{move || format!("{:.2}", metrics.get().emotional.valence)}</div>
+                    <div class="emotional-value">
+                        <Pulse value=Signal::derive(move || display.get().number(metrics.get().emotional.valence)) />
+                    </div>
                     <div class="label">"Valence"</div>
                 </div>
                 <div>
-                    <div class="emotional-value">{move || format!("{:.2}", metrics.get().emotional.arousal)}</div>
+                    <div class="emotional-value">
+                        <Pulse value=Signal::derive(move || display.get().number(metrics.get().emotional.arousal)) />
+                    </div>
                     <div class="label">"Arousal"</div>
                 </div>
                 <div>
-                    <div class="emotional-value">{move || format!("{:.2}", metrics.get().emotional.emotional_intensity)}</div>
+                    <div class="emotional-value">
+                        <Pulse value=Signal::derive(move || display.get().number(metrics.get().emotional.emotional_intensity)) />
+                    </div>
                     <div class="label">"Intensity"</div>
                 </div>
             </div>
@@ -267,22 +636,155 @@ fn EmotionalCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
     }
 }
 
+/// Valence over the last few minutes as a red → green ribbon; click a cell for its thought
+#[component]
+fn EmotionalHistoryCard(
+    metrics: Signal<DashboardMetrics>,
+    clock_skew_ms: Signal<i64>,
+    instance: Signal<String>,
+) -> impl IntoView {
+    let history = move || metrics.get().emotional.mood_history;
+    let (selected, set_selected) = create_signal(None::<MoodSample>);
+    // The selected sample's thought: None while loading, Some(None) if it's gone from the stream
+    let (thought, set_thought) = create_signal(None::<Option<ThoughtSummary>>);
+
+    let ribbon_ref = create_node_ref::<leptos::html::Canvas>();
+    create_effect(move |_| {
+        let samples = history();
+        if let Some(canvas) = ribbon_ref.get() {
+            draw_mood_ribbon(&canvas, &samples);
+        }
+    });
+
+    let on_click = move |e: web_sys::MouseEvent| {
+        let Some(canvas) = ribbon_ref.get_untracked() else { return };
+        let samples = history();
+        if samples.is_empty() {
+            return;
+        }
+        // offset_x is in CSS pixels; the canvas may be scaled to fit the card
+        let css_width = canvas.client_width().max(1) as f64;
+        let idx = ((e.offset_x() as f64 / css_width) * samples.len() as f64) as usize;
+        let sample = samples[idx.min(samples.len() - 1)].clone();
+        set_selected.set(Some(sample.clone()));
+        set_thought.set(None);
+        let (Some(at), Some(_)) = (sample.timestamp, sample.valence) else { return };
+        let name = instance.get_untracked();
+        spawn_local(async move {
+            // Stream ids start with their millis, so this is the newest thought at or before the sample
+            let before = format!("{}-0", at.timestamp_millis() + 1);
+            let found = fetch_thoughts("", 1, Some(&before), &name)
+                .await
+                .ok()
+                .and_then(|r| r.thoughts.into_iter().next());
+            // The card may be gone, or another cell clicked, by now
+            if selected.try_get_untracked().flatten().and_then(|s| s.timestamp) == Some(at) {
+                let _ = set_thought.try_set(Some(found));
+            }
+        });
+    };
+
+    view! {
+        <div class="card" role="region" aria-label="Mood Ribbon">
+            <h2>"MOOD RIBBON"</h2>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
+            <canvas
+                node_ref=ribbon_ref
+                width="240"
+                height="24"
+                class="mood-ribbon"
+                role="img"
+                aria-label=move || format!("Valence over the last {} samples", history().len())
+                on:click=on_click
+            />
+            <div class="mood-detail">
+                {move || match selected.get() {
+                    None => "Click the ribbon to see the thought behind a moment".to_string(),
+                    Some(sample) => {
+                        let when = sample
+                            .timestamp
+                            .map(|t| format_relative(t, server_now(clock_skew_ms.get())))
+                            .unwrap_or_default();
+                        match (sample.valence, thought.get()) {
+                            (None, _) => format!("{} · no thought recorded", when),
+                            (Some(v), None) => format!("{} · valence {:.2} · …", when, v),
+                            (Some(v), Some(Some(t))) => format!("{} · valence {:.2} · {}", when, v, t.content_preview),
+                            (Some(v), Some(None)) => format!("{} · valence {:.2} · thought no longer in the stream", when, v),
+                        }
+                    }
+                }}
+            </div>
+            <div class="label">"Latest-thought valence, one cell per 5s"</div>
+        </div>
+    }
+}
+
+/// Distribution of salience across the recent thought window
+#[component]
+fn SalienceHistogramCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
+    let bins = move || metrics.get().salience_histogram;
+    let total = move || bins().iter().sum::<u32>();
+
+    view! {
+        <div class="card salience-card" role="region" aria-label="Salience Distribution">
+            <h2>"SALIENCE DISTRIBUTION"</h2>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
+            <div class="salience-histogram">
+                {move || {
+                    let bins = bins();
+                    let width = 1.0 / bins.len().max(1) as f32;
+                    let peak = bins.iter().copied().max().unwrap_or(0).max(1);
+                    bins.iter()
+                        .enumerate()
+                        .map(|(i, &count)| {
+                            let low = i as f32 * width;
+                            let label = format!("{:.1}-{:.1}: {} thoughts", low, low + width, count);
+                            view! {
+                                <div
+                                    class="salience-bin"
+                                    role="img"
+                                    aria-label=label.clone()
+                                    title=label
+                                    style:height=format!("{}%", count * 100 / peak)
+                                ></div>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+            <div class="salience-axis">
+                <span>"0.0"</span>
+                <span>"salience"</span>
+                <span>"1.0"</span>
+            </div>
+            <div class="label">{move || format!("{} recent thoughts", total())}</div>
+        </div>
+    }
+}
+
 #[component]
 fn MemoryCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
     view! {
-        <div class="card">
+        <div class="card" role="region" aria-label="Memory">
             <h2>"MEMORY"</h2>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
             <div class="memory-grid">
                 <div>
-                    <div class="metric-sm">{move || format_number(metrics.get().cognitive.conscious_memories)}</div>
+                    <div class="metric-sm">
+                        <Pulse value=Signal::derive(move || format_number(metrics.get().cognitive.conscious_memories)) />
+                    </div>
                     <div class="label">"Conscious"</div>
                 </div>
                 <div>
-                    <div class="metric-sm">{move || format_number(metrics.get().cognitive.unconscious_memories)}</div>
+                    <div class="metric-sm">
+                        <Pulse value=Signal::derive(move || format_number(metrics.get().cognitive.unconscious_memories)) />
+                    </div>
                     <div class="label">"Unconscious"</div>
                 </div>
                 <div>
-                    <div class="metric-sm">{move || format_number(metrics.get().cognitive.lifetime_dreams)}</div>
+                    <div class="metric-sm">
+                        <Pulse value=Signal::derive(move || format_number(metrics.get().cognitive.lifetime_dreams)) />
+                    </div>
                     <div class="label">"Dreams"</div>
                 </div>
             </div>
@@ -293,8 +795,9 @@ fn MemoryCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
 #[component]
 fn ActorsCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
     view! {
-        <div class="card">
+        <div class="card" id="actors-card" role="region" aria-label="Actors">
             <h2>"ACTORS"</h2>
+            <FreshnessBadge updated=dashboard_updated(metrics) />
             <div class="actor-grid">
                 <ActorBadge actor=Signal::derive(move || metrics.get().actors.memory_actor) />
                 <ActorBadge actor=Signal::derive(move || metrics.get().actors.attention_actor) />
@@ -317,466 +820,3743 @@ fn ActorBadge(actor: Signal<ActorStatus>) -> impl IntoView {
     }
 }
 
+/// How often the pipeline card asks /pipeline
+const PIPELINE_POLL_MS: u32 = 5000;
+
+/// Status lights for the dashboard's own upstreams and its WebSocket clients;
+/// unlike the health score this is about the observatory, not the mind
 #[component]
-fn ThoughtStreamCard(metrics: Signal<DashboardMetrics>) -> impl IntoView {
+fn PipelineCard(instance: Signal<String>) -> impl IntoView {
+    // None until the first answer; Err when the dashboard backend itself didn't answer
+    let (pipeline, set_pipeline) = create_signal(None::<Result<PipelineHealth, ()>>);
+    spawn_local(async move {
+        loop {
+            let Some(name) = instance.try_get_untracked() else { return };
+            set_pipeline.set(Some(fetch_pipeline(&name).await));
+            gloo_timers::future::TimeoutFuture::new(PIPELINE_POLL_MS).await;
+        }
+    });
+
     view! {
-        <div class="card thought-card">
-            <h2>"THOUGHT STREAM"</h2>
-            <div class="thought-stream">
-                <For
-                    each=move || metrics.get().recent_thoughts
-                    key=|t| t.id.clone()
-                    children=move |thought| {
-                        view! {
-                            <div class="thought">
-                                <span class="salience">{format!("{:.2}", thought.salience)}</span>
-                                <span class="thought-content">{thought.content_preview}</span>
-                            </div>
-                        }
+        <div class="card" id="pipeline-card" role="region" aria-label="Connection health">
+            <h2>"CONNECTION HEALTH"</h2>
+            {move || match pipeline.get() {
+                None => view! { <div class="label">"Checking…"</div> }.into_view(),
+                Some(Err(())) => view! { <div class="pipeline-row down">"Dashboard backend unreachable"</div> }.into_view(),
+                Some(Ok(health)) => {
+                    let clients = if health.max_ws_clients > 0 {
+                        format!("{} / {}", health.ws_clients, health.max_ws_clients)
+                    } else {
+                        health.ws_clients.to_string()
+                    };
+                    view! {
+                        <ul class="pipeline-list">
+                            {health
+                                .upstreams
+                                .into_iter()
+                                .map(|upstream| {
+                                    let age = upstream
+                                        .age_ms
+                                        .map_or_else(|| "never".to_string(), |ms| format!("{} ago", format_duration((ms / 1000) as u64)));
+                                    view! {
+                                        <li class="pipeline-row" class:down=!upstream.reachable>
+                                            <span class="pipeline-dot"></span>
+                                            <span class="pipeline-name">{upstream.name}</span>
+                                            <span class="pipeline-age" title="Since the last successful read">{age}</span>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                        <div class="label">{format!("WebSocket clients: {}", clients)}</div>
                     }
-                />
-            </div>
+                    .into_view()
+                }
+            }}
         </div>
     }
 }
 
-#[component]
-fn StatusIndicator(connected: Signal<bool>) -> impl IntoView {
-    let class = move || if connected.get() { "status" } else { "status error" };
-    let text = move || if connected.get() { "Connected" } else { "Disconnected" };
+/// Events listed in the event log card
+const EVENT_LOG_ROWS: usize = 12;
 
-    view! {
-        <span class=class>{text}</span>
+/// Icon and label for an event kind
+fn event_style(kind: &str) -> (&'static str, &'static str) {
+    match kind {
+        "actor_died" => ("💀", "Actor died"),
+        "actor_recovered" => ("💚", "Actor recovered"),
+        "dream_started" => ("🌙", "Dream started"),
+        "dream_ended" => ("☀️", "Dream ended"),
+        "veto_fired" => ("🛑", "Veto"),
+        "entropy_band" => ("〰️", "Entropy band"),
+        "restart" => ("🔄", "Restart"),
+        _ => ("•", "Event"),
     }
 }
 
+/// Recent state changes (actors, dreams, vetoes, entropy bands, restarts), newest first
 #[component]
-fn TheBoxCard() -> impl IntoView {
-    // All laws active (clockwork - no real veto data yet)
+fn EventLogCard(events: Signal<Vec<ObservatoryEvent>>, clock_skew_ms: Signal<i64>) -> impl IntoView {
+    let rows = move || events.get().into_iter().take(EVENT_LOG_ROWS).collect::<Vec<_>>();
+
     view! {
-        <div class="card the-box-card">
-            <h2>"THE BOX"</h2>
-            <div class="laws-row">
-                <span class="law active">"[0:✓]"</span>
-                <span class="law active">"[1:✓]"</span>
-                <span class="law active">"[2:✓]"</span>
-                <span class="law active">"[3:✓]"</span>
-                <span class="laws-status">"ALL ACTIVE"</span>
-            </div>
-            <div class="box-message">
-                "No vetoes - all thoughts passing volition check"
-            </div>
-            <div class="box-footer">
-                "Life honours life. Seekers honour seekers."
-            </div>
+        <div class="card" role="region" aria-label="Event log">
+            <h2>"EVENT LOG"</h2>
+            <Show
+                when=move || !events.get().is_empty()
+                fallback=|| view! { <div class="label">"No state changes since the backend started"</div> }
+            >
+                <ul class="event-log">
+                    <For
+                        each=rows
+                        key=|e| (e.timestamp, e.kind.clone(), e.detail.clone())
+                        children=move |event| {
+                            let (icon, label) = event_style(&event.kind);
+                            let timestamp = event.timestamp;
+                            let age = move || format_relative(timestamp, server_now(clock_skew_ms.get()));
+                            view! {
+                                <li class=format!("event event-{}", event.kind)>
+                                    <span class="event-icon" aria-hidden="true">{icon}</span>
+                                    <span class="event-label">{label}</span>
+                                    <span class="event-detail">{event.detail}</span>
+                                    <span class="event-age">{age}</span>
+                                </li>
+                            }
+                        }
+                    />
+                </ul>
+            </Show>
         </div>
     }
 }
 
-// =============================================================================
-// Observatory Components (TUI-equivalent)
-// =============================================================================
+/// Age at which a card's "updated Ns ago" turns amber (polls run every 150-500ms)
+const FRESHNESS_STALE_SECS: i64 = 5;
 
-/// Stream Competition - 9 cognitive stages with activity bars
-#[component]
-fn StreamCompetitionCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
-    let stages = move || {
-        extended
-            .get()
-            .map(|e| e.stream_competition.stages)
-            .unwrap_or_default()
-    };
-    let competition = move || {
-        extended
-            .get()
-            .map(|e| e.stream_competition.competition_level)
-            .unwrap_or_else(|| "WAITING".to_string())
-    };
-    let active_count = move || {
-        extended
-            .get()
-            .map(|e| e.stream_competition.active_count)
-            .unwrap_or(0)
-    };
+/// How often freshness badges re-read the clock, so they keep counting with no frames
+const FRESHNESS_TICK_MS: u32 = 1000;
 
-    view! {
-        <div class="card stream-card">
-            <h2>"STREAM COMPETITION"</h2>
-            <div class="stream-header">
-                <span class="competition-level">{competition}</span>
-                <span class="active-count">{move || format!("{}/9 active", active_count())}</span>
-            </div>
-            <div class="streams">
-                <For
-                    each=move || stages().into_iter().enumerate()
-                    key=|(i, _)| *i
-                    children=move |(idx, stage)| {
-                        let is_dominant = move || {
-                            extended.get()
-                                .map(|e| e.stream_competition.dominant_stream == idx)
-                                .unwrap_or(false)
-                        };
-                        let bar_class = move || if is_dominant() { "stream-bar dominant" } else { "stream-bar" };
-                        let activity_pct = move || (stage.activity * 100.0) as u32;
+/// Estimated server time, ticking every `FRESHNESS_TICK_MS` (provided by `App`)
+#[derive(Debug, Clone, Copy)]
+struct ServerClock(Signal<DateTime<Utc>>);
 
-                        view! {
-                            <div class="stream-row">
-                                <span class="stream-name">{stage.name.clone()}</span>
-                                <div class="stream-bar-container">
-                                    <div class=bar_class style:width=move || format!("{}%", activity_pct())></div>
-                                </div>
-                                <span class="stream-value">{move || format!("{:.0}%", stage.activity * 100.0)}</span>
-                            </div>
-                        }
-                    }
-                />
-            </div>
-        </div>
-    }
+fn dashboard_updated(metrics: Signal<DashboardMetrics>) -> Signal<Option<DateTime<Utc>>> {
+    Signal::derive(move || metrics.with(|m| m.timestamp))
 }
 
-/// Entropy gauge with sparkline
+fn extended_updated(extended: Signal<Option<ExtendedMetrics>>) -> Signal<Option<DateTime<Utc>>> {
+    Signal::derive(move || extended.with(|e| e.as_ref().and_then(|e| e.timestamp)))
+}
+
+/// "updated Ns ago" for a card's data, amber once older than `FRESHNESS_STALE_SECS`
 #[component]
-fn EntropyCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
-    let entropy = move || extended.get().map(|e| e.entropy).unwrap_or_default();
-    let description = move || entropy().description;
-    let current = move || entropy().current;
-    let normalized = move || entropy().normalized;
+fn FreshnessBadge(updated: Signal<Option<DateTime<Utc>>>) -> impl IntoView {
+    let clock = use_context::<ServerClock>().map(|c| c.0);
+    let now = move || clock.map_or_else(Utc::now, |c| c.get());
+    let age_secs = move || updated.get().map(|t| (now() - t).num_seconds());
 
     view! {
-        <div class="card entropy-card">
-            <h2>"ENTROPY"</h2>
-            <div class="entropy-value">{move || format!("{:.2} bits", current())}</div>
-            <div class="entropy-gauge">
-                <div class="entropy-fill" style:width=move || format!("{}%", (normalized() * 100.0) as u32)></div>
-            </div>
-            <div class="entropy-description">{description}</div>
-            <div class="entropy-scale">
-                <span>"CLOCKWORK"</span>
-                <span>"BALANCED"</span>
-                <span>"EMERGENT"</span>
-            </div>
+        <div
+            class="freshness"
+            class:stale=move || age_secs().is_none_or(|age| age > FRESHNESS_STALE_SECS)
+            title="Time since this card's data was last refreshed by the backend"
+        >
+            {move || match updated.get() {
+                Some(t) => format!("updated {}", format_relative(t, now())),
+                None => "no data yet".to_string(),
+            }}
         </div>
     }
 }
 
-/// Fractality gauge - clockwork to fractal transition
+/// Shortest gap between two pulses of one value, so fast movers don't strobe
+const PULSE_MIN_INTERVAL_MS: f64 = 1500.0;
+
+/// Length of the pulse (the `value-pulse` animation in style.css)
+const PULSE_DURATION_MS: u32 = 600;
+
+/// A displayed value that flashes briefly when its text changes, at most
+/// once per `PULSE_MIN_INTERVAL_MS`; the first render never flashes
 #[component]
-fn FractalityCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
-    let fractality = move || extended.get().map(|e| e.fractality).unwrap_or_default();
-    let score = move || fractality().score;
-    let description = move || fractality().description;
-    let burst_ratio = move || fractality().burst_ratio;
+fn Pulse(value: Signal<String>) -> impl IntoView {
+    let (pulsing, set_pulsing) = create_signal(false);
+    let last_pulse = store_value(f64::NEG_INFINITY);
+    create_effect(move |previous: Option<String>| {
+        let current = value.get();
+        let now = now_ms();
+        if previous.is_some_and(|p| p != current) && now - last_pulse.get_value() >= PULSE_MIN_INTERVAL_MS {
+            last_pulse.set_value(now);
+            set_pulsing.set(true);
+            spawn_local(async move {
+                gloo_timers::future::TimeoutFuture::new(PULSE_DURATION_MS).await;
+                // The card may be gone by now
+                let _ = set_pulsing.try_set(false);
+            });
+        }
+        current
+    });
 
-    view! {
-        <div class="card fractality-card">
-            <h2>"FRACTALITY"</h2>
-            <div class="fractality-score">{move || format!("{:.0}%", score() * 100.0)}</div>
-            <div class="fractality-gauge">
-                <div class="fractality-fill" style:width=move || format!("{}%", (score() * 100.0) as u32)></div>
-            </div>
-            <div class="fractality-description">{description}</div>
-            <div class="fractality-stats">
-                <span>"Burst Ratio: "{move || format!("{:.2}", burst_ratio())}</span>
-            </div>
-        </div>
-    }
+    view! { <span class="value-pulse" class:pulsing=pulsing>{value}</span> }
 }
 
-/// Memory Windows - 9 TMI slots
+/// Wider thought-stream windows offered beside the live feed
+const THOUGHT_WINDOW_OPTIONS: [usize; 3] = [50, 100, 200];
+
+/// Novelty at or above this marks a thought as worth watching (✨)
+const HIGH_NOVELTY: f32 = 0.7;
+
+/// Results per search when no wider window is selected
+const THOUGHT_SEARCH_LIMIT: usize = 50;
+
+/// Thoughts fetched per "load older" click
+const THOUGHT_PAGE_SIZE: usize = 50;
+
 #[component]
-fn MemoryWindowsCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
-    let windows = move || extended.get().map(|e| e.memory_windows).unwrap_or_default();
-    let slots = move || windows().slots;
-    let active = move || windows().active_count;
+fn ThoughtStreamCard(
+    metrics: Signal<DashboardMetrics>,
+    clock_skew_ms: Signal<i64>,
+    instance: Signal<String>,
+) -> impl IntoView {
+    let (query, set_query) = create_signal(String::new());
+    let (results, set_results) = create_signal(Vec::<ThoughtSummary>::new());
+    // Bumped on every keystroke; a pending search only applies if still current
+    let (generation, set_generation) = create_signal(0u32);
+    // None = the live WebSocket feed (server's THOUGHT_WINDOW); Some(n) polls /thoughts
+    let (window_size, set_window_size) = create_signal(None::<usize>);
+    let (windowed, set_windowed) = create_signal(Vec::<ThoughtSummary>::new());
 
-    view! {
-        <div class="card memory-windows-card">
-            <h2>"MEMORY WINDOWS"</h2>
-            <div class="windows-header">
-                <span>{move || format!("{}/9 active", active())}</span>
+    // Older pages appended below the list by "load older", and whether the stream ran out
+    let (older, set_older) = create_signal(Vec::<ThoughtSummary>::new());
+    let (older_exhausted, set_older_exhausted) = create_signal(false);
+    let (loading_older, set_loading_older) = create_signal(false);
+    // Bumped whenever the list above changes meaning; a page fetched for an earlier one is dropped
+    let older_epoch = store_value(0u32);
+    let reset_older = move || {
+        older_epoch.update_value(|e| *e += 1);
+        set_older.set(Vec::new());
+        set_older_exhausted.set(false);
+        set_loading_older.set(false);
+    };
+    create_effect(move |_| {
+        let _ = instance.get();
+        reset_older();
+    });
+
+    let searching = move || !query.get().trim().is_empty();
+    let thoughts = move || {
+        if searching() {
+            results.get()
+        } else if window_size.get().is_some() {
+            windowed.get()
+        } else {
+            metrics.get().recent_thoughts
+        }
+    };
+    // The live list slides forward, so drop older thoughts it has caught up with
+    let shown = move || {
+        let mut shown = thoughts();
+        let ids: std::collections::HashSet<String> = shown.iter().map(|t| t.id.clone()).collect();
+        shown.extend(older.get().into_iter().filter(|t| !ids.contains(&t.id)));
+        shown
+    };
+
+    // Next page back from the oldest thought shown; a search keeps searching
+    let load_older = move |_| {
+        let Some(cursor) = shown().last().map(|t| t.id.clone()) else { return };
+        let epoch = older_epoch.get_value();
+        set_loading_older.set(true);
+        spawn_local(async move {
+            let q = query.get_untracked();
+            let page = fetch_thoughts(q.trim(), THOUGHT_PAGE_SIZE, Some(cursor.as_str()), &instance.get_untracked()).await;
+            if older_epoch.try_get_value() != Some(epoch) {
+                return;
+            }
+            set_loading_older.set(false);
+            if let Ok(page) = page {
+                set_older_exhausted.set(page.next_cursor.is_none());
+                set_older.update(|older| older.extend(page.thoughts));
+            }
+        });
+    };
+
+    // Wider windows aren't in the WebSocket frame, so poll the newest N over REST
+    spawn_local(async move {
+        loop {
+            if let Some(count) = window_size.get_untracked() {
+                if let Ok(resp) = fetch_thoughts("", count, None, &instance.get_untracked()).await {
+                    if window_size.get_untracked() == Some(count) {
+                        set_windowed.set(resp.thoughts);
+                    }
+                }
+            }
+            gloo_timers::future::TimeoutFuture::new(2000).await;
+        }
+    });
+
+    let on_window_change = move |ev: web_sys::Event| {
+        let count = event_target_value(&ev).parse::<usize>().ok();
+        reset_older();
+        set_windowed.set(Vec::new());
+        set_window_size.set(count);
+        if let Some(count) = count {
+            spawn_local(async move {
+                if let Ok(resp) = fetch_thoughts("", count, None, &instance.get_untracked()).await {
+                    if window_size.get_untracked() == Some(count) {
+                        set_windowed.set(resp.thoughts);
+                    }
+                }
+            });
+        }
+    };
+
+    // Debounced search: live feed while empty, REST results otherwise
+    let on_input = move |ev: web_sys::Event| {
+        let q = event_target_value(&ev);
+        set_query.set(q.clone());
+        set_generation.update(|g| *g += 1);
+        reset_older();
+        let current = generation.get_untracked();
+
+        if q.trim().is_empty() {
+            set_results.set(Vec::new());
+            return;
+        }
+
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(300).await;
+            if generation.get_untracked() != current {
+                return;
+            }
+            let limit = window_size.get_untracked().unwrap_or(THOUGHT_SEARCH_LIMIT);
+            if let Ok(resp) = fetch_thoughts(&q, limit, None, &instance.get_untracked()).await {
+                if generation.get_untracked() == current {
+                    set_results.set(resp.thoughts);
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="card thought-card" id="thought-stream-card" role="region" aria-label="Thought Stream">
+            <div class="thought-header">
+                <h2>"THOUGHT STREAM"</h2>
+                <select class="thought-window" aria-label="Thoughts shown" on:change=on_window_change>
+                    <option value="live" selected>"live"</option>
+                    {THOUGHT_WINDOW_OPTIONS
+                        .iter()
+                        .map(|n| view! { <option value=n.to_string()>{format!("show {}", n)}</option> })
+                        .collect_view()}
+                </select>
+                <input
+                    class="thought-search"
+                    type="search"
+                    placeholder="Search thoughts..."
+                    prop:value=query
+                    on:input=on_input
+                />
             </div>
-            <div class="memory-slots">
+            <FreshnessBadge updated=dashboard_updated(metrics) />
+            <div class="thought-stream">
                 <For
-                    each=move || slots()
-                    key=|s| s.id
-                    children=move |slot| {
-                        let class = move || if slot.active { "slot active" } else { "slot" };
+                    each=shown
+                    key=|t| t.id.clone()
+                    children=move |thought| {
+                        let timestamp = thought.timestamp;
+                        // Relative to server time so a skewed browser clock doesn't distort it
+                        let age = move || {
+                            timestamp
+                                .map(|t| format_relative(t, server_now(clock_skew_ms.get())))
+                                .unwrap_or_default()
+                        };
+                        let novel = thought.novelty >= HIGH_NOVELTY;
+                        let defaulted = thought.salience_defaulted;
                         view! {
-                            <div class=class>{slot.id}</div>
+                            <div class="thought" class:novel=novel class:defaulted=defaulted>
+                                <span
+                                    class="salience"
+                                    title=defaulted.then_some("Salience incomplete; missing fields show neutral defaults")
+                                >
+                                    {format!("{:.2}", thought.salience)}
+                                </span>
+                                <span class="novelty" title="Novelty">
+                                    {if novel { "✨" } else { "" }}
+                                    {format!("{:.2}", thought.novelty)}
+                                </span>
+                                <span class="thought-content">{thought.content_preview}</span>
+                                <span class="thought-age">{age}</span>
+                            </div>
                         }
                     }
                 />
+                <Show when=move || !thoughts().is_empty()>
+                    <div class="thought-older">
+                        {move || if older_exhausted.get() {
+                            view! { <span class="label">"Start of the stream"</span> }.into_view()
+                        } else {
+                            view! {
+                                <button class="toggle-button" disabled=loading_older on:click=load_older>
+                                    {move || if loading_older.get() { "Loading…" } else { "Load older" }}
+                                </button>
+                            }
+                            .into_view()
+                        }}
+                    </div>
+                </Show>
             </div>
         </div>
     }
 }
 
-/// Manifold Clustering - silhouette score (VCONN-7)
-#[component]
-fn ClusteringCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
-    let clustering = move || extended.get().map(|e| e.clustering).unwrap_or_default();
-    let silhouette = move || clustering().silhouette;
-    let has_structure = move || clustering().has_structure;
-    let gauge_width = move || {
-        // Scale -1..1 to 0..100%
-        let s = silhouette();
-        ((s + 1.0) * 50.0).clamp(0.0, 100.0) as u32
-    };
+/// How often the debug overlay pings the server for round-trip latency
+const DEBUG_PING_INTERVAL_MS: u32 = 1000;
+
+/// Streaming-layer stats shown by the `?debug=1` overlay
+#[derive(Debug, Clone, Default)]
+struct DebugStats {
+    fps: f64,
+    last_bytes: usize,
+    /// Exponential moving average of frame size
+    avg_bytes: f64,
+    decode_ms: f64,
+    rtt_ms: Option<f64>,
+    window_start: f64,
+    window_frames: u32,
+}
+
+impl DebugStats {
+    /// Fold in one received frame; fps is recomputed about once a second
+    fn record_frame(&mut self, bytes: usize, decode_ms: f64, now: f64) {
+        self.last_bytes = bytes;
+        self.avg_bytes = if self.avg_bytes == 0.0 {
+            bytes as f64
+        } else {
+            self.avg_bytes * 0.9 + bytes as f64 * 0.1
+        };
+        self.decode_ms = decode_ms;
+
+        if self.window_start == 0.0 {
+            self.window_start = now;
+        }
+        self.window_frames += 1;
+        let elapsed = now - self.window_start;
+        if elapsed >= 1000.0 {
+            self.fps = self.window_frames as f64 * 1000.0 / elapsed;
+            self.window_start = now;
+            self.window_frames = 0;
+        }
+    }
+}
 
+/// Developer HUD for tuning the stream: frame rate, size, decode time and latency
+#[component]
+fn DebugOverlay(stats: Signal<DebugStats>) -> impl IntoView {
     view! {
-        <div class="card clustering-card">
-            <h2>"MANIFOLD STRUCTURE"</h2>
-            <div class="clustering-score">{move || format!("{:.3}", silhouette())}</div>
-            <div class="clustering-gauge">
-                <div
-                    class="clustering-fill"
-                    class:structured=has_structure
-                    style:width=move || format!("{}%", gauge_width())
-                ></div>
+        <div class="debug-overlay" aria-hidden="true">
+            <div>{move || format!("{:.1} frames/s", stats.get().fps)}</div>
+            <div>
+                {move || stats.with(|s| format!(
+                    "frame {} (avg {})",
+                    format_bytes(s.last_bytes as f64),
+                    format_bytes(s.avg_bytes),
+                ))}
             </div>
-            <div class="clustering-status">
-                {move || if has_structure() { "Structured" } else { "Sparse" }}
+            <div>{move || format!("decode {:.2} ms", stats.get().decode_ms)}</div>
+            <div>
+                {move || match stats.get().rtt_ms {
+                    Some(rtt) => format!("rtt {:.0} ms", rtt),
+                    None => "rtt --".to_string(),
+                }}
             </div>
-            <div class="clustering-label">"Silhouette score (> 0.3 = meaningful clusters)"</div>
         </div>
     }
 }
 
-/// Philosophy banner
-#[component]
-fn PhilosophyCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
-    let philosophy = move || extended.get().map(|e| e.philosophy).unwrap_or_default();
-    let quote = move || philosophy().quote;
+// =============================================================================
+// Frame recording and replay
+// =============================================================================
+
+/// Frames a recording keeps before it stops growing (2h at the 5/s broadcast rate)
+const RECORDING_MAX_FRAMES: usize = 36_000;
+
+/// Replay shortens gaps longer than this (a dropped connection while recording)
+const REPLAY_MAX_GAP_MS: f64 = 10_000.0;
+
+/// One line of a `.jsonl` recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct RecordedFrame {
+    /// Milliseconds since the recording started
+    at_ms: f64,
+    /// The frame text exactly as the socket delivered it, so replay goes
+    /// through `decode_frame` too
+    frame: String,
+}
+
+/// Frames buffered since the record button was pressed
+#[derive(Debug, Clone, Default)]
+struct Recording {
+    started_ms: f64,
+    frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    fn new(now: f64) -> Self {
+        Self { started_ms: now, frames: Vec::new() }
+    }
+
+    /// Buffer a frame received at `now`; dropped once the recording is full
+    fn push(&mut self, text: &str, now: f64) {
+        if self.frames.len() < RECORDING_MAX_FRAMES {
+            self.frames.push(RecordedFrame { at_ms: now - self.started_ms, frame: text.to_string() });
+        }
+    }
+
+    fn to_jsonl(&self) -> String {
+        self.frames
+            .iter()
+            .filter_map(|f| serde_json::to_string(f).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+}
+
+/// Parse a `.jsonl` recording (blank lines skipped); the error names the first bad line
+fn parse_recording(text: &str) -> Result<Vec<RecordedFrame>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// How long replay waits between two recorded frames
+fn replay_delay_ms(previous: &RecordedFrame, next: &RecordedFrame) -> f64 {
+    (next.at_ms - previous.at_ms).clamp(0.0, REPLAY_MAX_GAP_MS)
+}
+
+/// Offer `text` as a file download
+fn download_text(text: &str, filename: &str, mime: &str) -> Result<(), JsValue> {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let parts = JsValue::from(vec![JsValue::from_str(text)]);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let link = document().create_element("a")?.dyn_into::<web_sys::HtmlAnchorElement>()?;
+    link.set_href(&url);
+    link.set_download(filename);
+    link.click();
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// "Download state": everything the dashboard is showing at one moment, for
+/// bug reports. The manifold is the one drawn, so frozen with the display
+/// while paused like the rest.
+#[derive(Debug, Serialize)]
+struct StateSnapshot {
+    captured_at: DateTime<Utc>,
+    /// "" = the backend's default instance
+    instance: String,
+    observatory: ObservatoryMetrics,
+    /// None while no manifold card is mounted (embeds, text mode)
+    manifold: Option<ManifoldResponse>,
+}
+
+impl StateSnapshot {
+    fn filename(&self) -> String {
+        format!("daneel-state-{}.json", self.captured_at.format("%Y%m%d-%H%M%S"))
+    }
+}
+
+/// The manifold the card is drawing (provided by `App`, set by `ThoughtManifoldCard`)
+#[derive(Debug, Clone, Copy)]
+struct ShownManifold(StoredValue<Option<ReadSignal<ManifoldResponse>>>);
+
+/// Text of the first file chosen in a file input, if any
+async fn read_chosen_file(input: &web_sys::HtmlInputElement) -> Result<Option<String>, JsValue> {
+    let Some(file) = input.files().and_then(|files| files.get(0)) else { return Ok(None) };
+    let text = wasm_bindgen_futures::JsFuture::from(file.text()).await?;
+    Ok(text.as_string())
+}
+
+// =============================================================================
+// Display settings
+// =============================================================================
+
+/// localStorage key holding the display settings
+const DISPLAY_SETTINGS_STORAGE_KEY: &str = "daneel-web-display";
+
+/// Most decimal places the display settings offer
+const MAX_DISPLAY_PRECISION: usize = 4;
+
+/// How metric values are formatted, persisted to localStorage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct DisplaySettings {
+    /// Decimal places for raw values; percentages get two fewer
+    precision: usize,
+    /// Show 0-1 fractions (activity, drive, fractality) as percentages
+    percentages: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self { precision: 2, percentages: true }
+    }
+}
+
+impl DisplaySettings {
+    fn load() -> Self {
+        local_storage()
+            .and_then(|s| s.get_item(DISPLAY_SETTINGS_STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .map(|settings| Self { precision: settings.precision.min(MAX_DISPLAY_PRECISION), ..settings })
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(self)) {
+            let _ = storage.set_item(DISPLAY_SETTINGS_STORAGE_KEY, &json);
+        }
+    }
+
+    /// A raw value (valence, bits, ratios)
+    fn number(self, value: f32) -> String {
+        format!("{:.*}", self.precision, value)
+    }
+
+    /// A 0-1 fraction, as "42%" or "0.42"
+    fn fraction(self, value: f32) -> String {
+        if self.percentages {
+            format!("{:.*}%", self.precision.saturating_sub(2), value * 100.0)
+        } else {
+            self.number(value)
+        }
+    }
+}
 
+/// Precision and percentage toggle; every change is saved to localStorage
+#[component]
+fn DisplayPanel(display: Signal<DisplaySettings>, set_display: WriteSignal<DisplaySettings>) -> impl IntoView {
     view! {
-        <div class="card philosophy-card">
-            <div class="philosophy-quote">{quote}</div>
+        <div class="display-panel" role="region" aria-label="Display settings">
+            <label class="panel-setting">
+                "Decimal places"
+                <input
+                    type="number"
+                    min="0"
+                    max=MAX_DISPLAY_PRECISION.to_string()
+                    step="1"
+                    prop:value=move || display.get().precision.to_string()
+                    on:change=move |ev| {
+                        if let Ok(precision) = event_target_value(&ev).parse::<usize>() {
+                            set_display.update(|d| d.precision = precision.min(MAX_DISPLAY_PRECISION));
+                        }
+                    }
+                />
+            </label>
+            <label class="panel-setting">
+                <input
+                    type="checkbox"
+                    prop:checked=move || display.get().percentages
+                    on:change=move |ev| {
+                        let on = event_target_checked(&ev);
+                        set_display.update(|d| d.percentages = on);
+                    }
+                />
+                "Fractions as percentages"
+            </label>
+            <span class="panel-example">
+                {move || {
+                    let d = display.get();
+                    format!("e.g. {} · {}", d.number(0.4217), d.fraction(0.4217))
+                }}
+            </span>
         </div>
     }
 }
 
-/// 3D Thought Manifold - visualize thought vectors as a rotating point cloud
-#[component]
-fn ThoughtManifoldCard() -> impl IntoView {
-    let canvas_ref = create_node_ref::<leptos::html::Canvas>();
-    let (manifold, set_manifold) = create_signal(ManifoldResponse::default());
-    let (rotation, set_rotation) = create_signal(0.0f64);
-    let (dragging, set_dragging) = create_signal(false);
-    let (last_x, set_last_x) = create_signal(0.0f64);
+// =============================================================================
+// Alerts
+// =============================================================================
 
-    // Fetch manifold data periodically
-    spawn_local(async move {
-        loop {
-            if let Ok(resp) = fetch_manifold().await {
-                set_manifold.set(resp);
-            }
-            gloo_timers::future::TimeoutFuture::new(2000).await;
+/// localStorage key holding the alert rules
+const ALERT_RULES_STORAGE_KEY: &str = "daneel-web-alerts";
+
+/// How long a toast stays up
+const TOAST_LIFETIME_MS: u32 = 8000;
+
+/// Toasts on screen at once; the oldest goes first
+const MAX_TOASTS: usize = 4;
+
+/// Threshold crossings the dashboard can alert on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum AlertKind {
+    EntropySpike,
+    ActorDown,
+    VetoFired,
+    Stalled,
+}
+
+impl AlertKind {
+    const ALL: [AlertKind; 4] = [
+        AlertKind::EntropySpike,
+        AlertKind::ActorDown,
+        AlertKind::VetoFired,
+        AlertKind::Stalled,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            AlertKind::EntropySpike => "Entropy spike",
+            AlertKind::ActorDown => "Actor down",
+            AlertKind::VetoFired => "Veto fired",
+            AlertKind::Stalled => "Mind stalled",
         }
-    });
+    }
 
-    // Auto-rotate animation
-    spawn_local(async move {
-        loop {
-            gloo_timers::future::TimeoutFuture::new(50).await;
-            if !dragging.get_untracked() {
-                set_rotation.update(|r| *r += 0.01);
-            }
+    /// Card the alert links to
+    fn anchor(self) -> &'static str {
+        match self {
+            AlertKind::EntropySpike => "#entropy-card",
+            AlertKind::ActorDown => "#actors-card",
+            AlertKind::VetoFired => "#the-box-card",
+            AlertKind::Stalled => "#thought-stream-card",
         }
-    });
+    }
+}
 
-    // Render loop
-    create_effect(move |_| {
-        let _ = manifold.get();
-        let rot = rotation.get();
+/// User-configurable alert rules, persisted to localStorage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct AlertRules {
+    /// Kinds that raise alerts
+    enabled: Vec<AlertKind>,
+    /// Normalized entropy (0-1) above which a spike fires
+    entropy_threshold: f32,
+    /// Minimum seconds between two alerts of the same kind
+    cooldown_secs: u32,
+    /// Also raise a browser notification (needs permission)
+    browser_notifications: bool,
+}
 
-        if let Some(canvas) = canvas_ref.get() {
-            render_manifold(&canvas, &manifold.get_untracked(), rot);
+impl Default for AlertRules {
+    fn default() -> Self {
+        Self {
+            enabled: AlertKind::ALL.to_vec(),
+            entropy_threshold: 0.9,
+            cooldown_secs: 60,
+            browser_notifications: false,
         }
-    });
+    }
+}
 
-    // Mouse handlers for rotation
-    let on_mouse_down = move |e: web_sys::MouseEvent| {
-        set_dragging.set(true);
-        set_last_x.set(e.client_x() as f64);
-    };
+impl AlertRules {
+    fn load() -> Self {
+        local_storage()
+            .and_then(|s| s.get_item(ALERT_RULES_STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
 
-    let on_mouse_move = move |e: web_sys::MouseEvent| {
-        if dragging.get() {
-            let dx = e.client_x() as f64 - last_x.get();
-            set_rotation.update(|r| *r += dx * 0.01);
-            set_last_x.set(e.client_x() as f64);
+    fn save(&self) {
+        if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(self)) {
+            let _ = storage.set_item(ALERT_RULES_STORAGE_KEY, &json);
         }
-    };
+    }
 
-    let on_mouse_up = move |_: web_sys::MouseEvent| {
-        set_dragging.set(false);
-    };
+    fn is_enabled(&self, kind: AlertKind) -> bool {
+        self.enabled.contains(&kind)
+    }
+
+    fn set_enabled(&mut self, kind: AlertKind, on: bool) {
+        self.enabled.retain(|k| *k != kind);
+        if on {
+            self.enabled.push(kind);
+        }
+    }
+}
+
+/// The parts of a frame the alert rules look at
+#[derive(Debug, Clone, Default)]
+struct AlertSnapshot {
+    entropy: Option<f32>,
+    dead_actors: Vec<String>,
+    veto_count: Option<u64>,
+    stalled: bool,
+}
+
+impl AlertSnapshot {
+    fn of(data: &ObservatoryMetrics) -> Self {
+        let actors = &data.dashboard.actors;
+        Self {
+            entropy: data.extended.as_ref().map(|e| e.entropy.normalized),
+            dead_actors: [
+                &actors.memory_actor,
+                &actors.attention_actor,
+                &actors.salience_actor,
+                &actors.volition_actor,
+            ]
+            .into_iter()
+            .filter(|a| !a.alive)
+            .map(|a| a.name.clone())
+            .collect(),
+            veto_count: data.extended.as_ref().map(|e| e.system.veto_count),
+            stalled: data.dashboard.cognitive.stalled,
+        }
+    }
+}
+
+/// Alerts raised going from `previous` to `current`. Edge-triggered: a
+/// condition alerts when it starts, not on every frame while it holds.
+fn detect_alerts(previous: &AlertSnapshot, current: &AlertSnapshot, rules: &AlertRules) -> Vec<(AlertKind, String)> {
+    let mut alerts = Vec::new();
+
+    if let (Some(before), Some(now)) = (previous.entropy, current.entropy) {
+        let threshold = rules.entropy_threshold;
+        if before <= threshold && now > threshold {
+            alerts.push((
+                AlertKind::EntropySpike,
+                format!("Entropy at {:.0}% (threshold {:.0}%)", now * 100.0, threshold * 100.0),
+            ));
+        }
+    }
+
+    for name in &current.dead_actors {
+        if !previous.dead_actors.contains(name) {
+            alerts.push((AlertKind::ActorDown, format!("{} is down", name)));
+        }
+    }
+
+    if let (Some(before), Some(now)) = (previous.veto_count, current.veto_count) {
+        if now > before {
+            let fired = now - before;
+            alerts.push((
+                AlertKind::VetoFired,
+                format!("{} veto{} fired ({} total)", fired, if fired == 1 { "" } else { "es" }, now),
+            ));
+        }
+    }
+
+    if current.stalled && !previous.stalled {
+        alerts.push((AlertKind::Stalled, "No new thoughts - the core may be stalled".to_string()));
+    }
+
+    alerts.retain(|(kind, _)| rules.is_enabled(*kind));
+    alerts
+}
+
+/// Watches incoming frames and throttles what gets through
+#[derive(Debug, Default)]
+struct AlertEngine {
+    previous: Option<AlertSnapshot>,
+    /// Last time (ms) each kind fired
+    last_fired: Vec<(AlertKind, f64)>,
+}
+
+impl AlertEngine {
+    /// Forget the last frame (new connection or instance), so nothing fires on the first one
+    fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    fn observe(&mut self, data: &ObservatoryMetrics, rules: &AlertRules, now: f64) -> Vec<(AlertKind, String)> {
+        let current = AlertSnapshot::of(data);
+        let alerts = match &self.previous {
+            Some(previous) => detect_alerts(previous, &current, rules),
+            None => Vec::new(),
+        };
+        self.previous = Some(current);
+
+        let cooldown_ms = rules.cooldown_secs as f64 * 1000.0;
+        alerts
+            .into_iter()
+            .filter(|(kind, _)| {
+                match self.last_fired.iter_mut().find(|(k, _)| k == kind) {
+                    Some((_, at)) if now - *at < cooldown_ms => false,
+                    Some((_, at)) => {
+                        *at = now;
+                        true
+                    }
+                    None => {
+                        self.last_fired.push((*kind, now));
+                        true
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// One on-screen alert
+#[derive(Debug, Clone)]
+struct Toast {
+    id: u64,
+    kind: AlertKind,
+    message: String,
+}
+
+/// Show `message` as a toast (dismissed after `TOAST_LIFETIME_MS`) and, when
+/// enabled and permitted, as a browser notification
+fn raise_alert(set_toasts: WriteSignal<Vec<Toast>>, kind: AlertKind, message: String, notify: bool) {
+    if notify && web_sys::Notification::permission() == web_sys::NotificationPermission::Granted {
+        let options = web_sys::NotificationOptions::new();
+        options.set_body(&message);
+        let _ = web_sys::Notification::new_with_options(&format!("DANEEL: {}", kind.label()), &options);
+    }
 
+    static NEXT_TOAST_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let id = NEXT_TOAST_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    set_toasts.update(|toasts| {
+        toasts.push(Toast { id, kind, message });
+        let overflow = toasts.len().saturating_sub(MAX_TOASTS);
+        toasts.drain(..overflow);
+    });
+    spawn_local(async move {
+        gloo_timers::future::TimeoutFuture::new(TOAST_LIFETIME_MS).await;
+        set_toasts.update(|toasts| toasts.retain(|t| t.id != id));
+    });
+}
+
+/// Alert toasts, each linking to the card it is about
+#[component]
+fn AlertToasts(toasts: Signal<Vec<Toast>>, set_toasts: WriteSignal<Vec<Toast>>) -> impl IntoView {
     view! {
-        <div class="card manifold-card">
-            <h2>"THOUGHT MANIFOLD"</h2>
-            <div class="manifold-subtitle">
-                {move || format!("{} vectors | 768-dim → 3D shadow", manifold.get().points.len())}
-            </div>
-            <canvas
-                node_ref=canvas_ref
-                width="600"
-                height="400"
-                class="manifold-canvas"
-                on:mousedown=on_mouse_down
-                on:mousemove=on_mouse_move
-                on:mouseup=on_mouse_up
-                on:mouseleave=on_mouse_up
+        <div class="toasts" role="status" aria-live="polite">
+            <For
+                each=move || toasts.get()
+                key=|t| t.id
+                children=move |toast| {
+                    let id = toast.id;
+                    view! {
+                        <div class="toast">
+                            <a href=toast.kind.anchor()>
+                                <strong>{toast.kind.label()}</strong>
+                                " "
+                                {toast.message}
+                            </a>
+                            <button
+                                class="toast-dismiss"
+                                aria-label="Dismiss"
+                                on:click=move |_| set_toasts.update(|t| t.retain(|t| t.id != id))
+                            >
+                                "×"
+                            </button>
+                        </div>
+                    }
+                }
             />
-            <div class="manifold-legend">
-                <span class="legend-crystal">"★ Law Crystals"</span>
-                <span class="legend-thought">"○ Thoughts (brightness = salience)"</span>
-            </div>
         </div>
     }
 }
 
-/// Render the 3D manifold to canvas using 2D context with perspective projection
-fn render_manifold(canvas: &HtmlCanvasElement, manifold: &ManifoldResponse, rotation: f64) {
-    let ctx = canvas
-        .get_context("2d")
-        .ok()
-        .flatten()
-        .and_then(|c| c.dyn_into::<CanvasRenderingContext2d>().ok());
+/// Rule editor; every change is saved to localStorage
+#[component]
+fn AlertsPanel(rules: Signal<AlertRules>, set_rules: WriteSignal<AlertRules>) -> impl IntoView {
+    let on_notifications = move |ev: web_sys::Event| {
+        let on = event_target_checked(&ev);
+        if on && web_sys::Notification::permission() == web_sys::NotificationPermission::Default {
+            let _ = web_sys::Notification::request_permission();
+        }
+        set_rules.update(|r| r.browser_notifications = on);
+    };
+
+    view! {
+        <div class="alerts-panel" role="region" aria-label="Alert rules">
+            {AlertKind::ALL
+                .into_iter()
+                .map(|kind| view! {
+                    <label class="alert-rule">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || rules.get().is_enabled(kind)
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                set_rules.update(|r| r.set_enabled(kind, on));
+                            }
+                        />
+                        {kind.label()}
+                    </label>
+                })
+                .collect_view()}
+            <label class="alert-rule">
+                "Entropy threshold"
+                <input
+                    type="number"
+                    min="0"
+                    max="1"
+                    step="0.05"
+                    prop:value=move || rules.get().entropy_threshold.to_string()
+                    on:change=move |ev| {
+                        if let Ok(threshold) = event_target_value(&ev).parse::<f32>() {
+                            set_rules.update(|r| r.entropy_threshold = threshold.clamp(0.0, 1.0));
+                        }
+                    }
+                />
+            </label>
+            <label class="alert-rule">
+                "Repeat after (s)"
+                <input
+                    type="number"
+                    min="0"
+                    step="10"
+                    prop:value=move || rules.get().cooldown_secs.to_string()
+                    on:change=move |ev| {
+                        if let Ok(secs) = event_target_value(&ev).parse::<u32>() {
+                            set_rules.update(|r| r.cooldown_secs = secs);
+                        }
+                    }
+                />
+            </label>
+            <label class="alert-rule">
+                <input
+                    type="checkbox"
+                    prop:checked=move || rules.get().browser_notifications
+                    on:change=on_notifications
+                />
+                "Browser notifications"
+            </label>
+        </div>
+    }
+}
+
+/// Browser-vs-server clock offset, highlighted past `CLOCK_SKEW_WARN_SECS`
+#[component]
+fn ClockSkewIndicator(clock_skew_ms: Signal<i64>) -> impl IntoView {
+    let skew_s = move || clock_skew_ms.get() as f64 / 1000.0;
+    let class = move || {
+        if skew_s().abs() >= CLOCK_SKEW_WARN_SECS {
+            "clock-skew warn"
+        } else {
+            "clock-skew"
+        }
+    };
+
+    view! {
+        <span class=class title="Browser clock minus server clock">
+            {move || format!("clock {:+.1}s", skew_s())}
+        </span>
+    }
+}
+
+#[component]
+fn StatusIndicator(connected: Signal<bool>) -> impl IntoView {
+    let class = move || if connected.get() { "status" } else { "status error" };
+    let text = move || if connected.get() { "Connected" } else { "Disconnected" };
+
+    view! {
+        <span class=class>{text}</span>
+    }
+}
+
+#[component]
+fn TheBoxCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
+    let by_law = move || extended.get().map(|e| e.system.veto_by_law).unwrap_or_default();
+    let total = move || by_law().iter().sum::<u64>();
+    // Law with the most vetoes, if any law has vetoed at all
+    let busiest = move || {
+        let counts = by_law();
+        (0..counts.len())
+            .max_by_key(|&i| counts[i])
+            .filter(|&i| counts[i] > 0)
+    };
+
+    view! {
+        <div class="card the-box-card" id="the-box-card" role="region" aria-label="The Box">
+            <h2>"THE BOX"</h2>
+            <FreshnessBadge updated=extended_updated(extended) />
+            <div class="laws-row">
+                {(0..4usize)
+                    .map(|law| {
+                        let count = move || by_law()[law];
+                        view! {
+                            <span
+                                class="law active"
+                                class:most-triggered=move || busiest() == Some(law)
+                                title=move || format!("Law {}: {} vetoes", law, count())
+                            >
+                                {move || if count() == 0 {
+                                    format!("[{}:✓]", law)
+                                } else {
+                                    format!("[{}:{}]", law, format_number(count()))
+                                }}
+                            </span>
+                        }
+                    })
+                    .collect_view()}
+                <span class="laws-status">"ALL ACTIVE"</span>
+            </div>
+            <div class="box-message">
+                {move || match busiest() {
+                    None => "No vetoes - all thoughts passing volition check".to_string(),
+                    Some(law) => format!(
+                        "{} vetoes - Law {} is doing the most work",
+                        format_number(total()),
+                        law,
+                    ),
+                }}
+            </div>
+            <div class="box-footer">
+                "Life honours life. Seekers honour seekers."
+            </div>
+        </div>
+    }
+}
+
+// =============================================================================
+// Observatory Components (TUI-equivalent)
+// =============================================================================
+
+/// Stream Competition - 9 cognitive stages with activity bars
+#[component]
+fn StreamCompetitionCard(extended: Signal<Option<ExtendedMetrics>>, display: Signal<DisplaySettings>) -> impl IntoView {
+    let stages = move || {
+        extended
+            .get()
+            .map(|e| e.stream_competition.stages)
+            .unwrap_or_default()
+    };
+    let competition = move || {
+        extended
+            .get()
+            .map(|e| e.stream_competition.competition_level)
+            .unwrap_or_else(|| "WAITING".to_string())
+    };
+    let active_count = move || {
+        extended
+            .get()
+            .map(|e| e.stream_competition.active_count)
+            .unwrap_or(0)
+    };
+    let (show_history, set_show_history) = create_signal(true);
+    // Stage whose full history is open in the focus panel (click a row to toggle)
+    let (focused, set_focused) = create_signal(None::<usize>);
+    let focused_stage = move || {
+        let idx = focused.get()?;
+        extended.with(|e| e.as_ref()?.stream_competition.stages.get(idx).cloned())
+    };
+    let focused_stats = move || {
+        let idx = focused.get()?;
+        extended.with(|e| stage_stats(&e.as_ref()?.stream_competition.stages, idx))
+    };
+
+    let focus_ref = create_node_ref::<leptos::html::Canvas>();
+    create_effect(move |_| {
+        let history = focused_stage().map(|s| s.history).unwrap_or_default();
+        if let Some(canvas) = focus_ref.get() {
+            draw_activity_chart(&canvas, &history, "#4ecdc4");
+        }
+    });
+
+    view! {
+        <div class="card stream-card" role="region" aria-label="Stream Competition">
+            <h2>"STREAM COMPETITION"</h2>
+            <FreshnessBadge updated=extended_updated(extended) />
+            <div class="stream-header">
+                <span class="competition-level">{competition}</span>
+                <span class="active-count">{move || format!("{}/9 active", active_count())}</span>
+                <button
+                    class="toggle-button"
+                    class:active=move || show_history.get()
+                    on:click=move |_| set_show_history.update(|s| *s = !*s)
+                >
+                    "history"
+                </button>
+            </div>
+            <div class="streams" class:with-history=move || show_history.get()>
+                <For
+                    each=move || stages().into_iter().enumerate()
+                    key=|(i, _)| *i
+                    children=move |(idx, stage)| {
+                        let is_dominant = move || {
+                            extended.get()
+                                .and_then(|e| e.stream_competition.dominant())
+                                == Some(idx)
+                        };
+                        let bar_class = move || if is_dominant() { "stream-bar dominant" } else { "stream-bar" };
+                        // Rows are keyed by index and reused, so read the live value rather than `stage`
+                        let activity = move || {
+                            extended
+                                .get()
+                                .and_then(|e| e.stream_competition.stages.get(idx).map(|s| s.activity))
+                                .unwrap_or(0.0)
+                        };
+                        let activity_pct = move || gauge_pct(activity());
+
+                        // Recent activity for this stage, redrawn on every update
+                        let spark_ref = create_node_ref::<leptos::html::Canvas>();
+                        create_effect(move |_| {
+                            let history = extended
+                                .get()
+                                .and_then(|e| e.stream_competition.stages.get(idx).map(|s| s.history.clone()))
+                                .unwrap_or_default();
+                            if let Some(canvas) = spark_ref.get() {
+                                draw_sparkline(&canvas, &history, "#4ecdc4");
+                            }
+                        });
+
+                        view! {
+                            <div
+                                class="stream-row"
+                                class:focused=move || focused.get() == Some(idx)
+                                title="Show this stage's history"
+                                on:click=move |_| set_focused.update(|f| *f = if *f == Some(idx) { None } else { Some(idx) })
+                            >
+                                <span class="stream-name">{stage.name.clone()}</span>
+                                <div class="stream-bar-container">
+                                    <div
+                                        class=bar_class
+                                        role="meter"
+                                        aria-label=format!("{} activity", stage.name)
+                                        aria-valuemin="0"
+                                        aria-valuemax="100"
+                                        aria-valuenow=move || activity_pct().to_string()
+                                        style:width=move || format!("{}%", activity_pct())
+                                    ></div>
+                                </div>
+                                <span class="stream-value">{move || display.get().fraction(activity())}</span>
+                                <Show when=move || show_history.get()>
+                                    <canvas node_ref=spark_ref width="60" height="16" class="stream-sparkline" aria-hidden="true" />
+                                </Show>
+                            </div>
+                        }
+                    }
+                />
+            </div>
+            <Show when=move || focused.get().is_some()>
+                <div class="stage-focus" aria-live="polite">
+                    <div class="stage-focus-header">
+                        <span class="stage-focus-name">
+                            {move || focused_stage().map(|s| s.name).unwrap_or_default()}
+                        </span>
+                        <button class="toggle-button" on:click=move |_| set_focused.set(None)>"close"</button>
+                    </div>
+                    <canvas node_ref=focus_ref class="stage-focus-chart" aria-hidden="true" />
+                    <div class="stage-focus-stats">
+                        {move || match focused_stats() {
+                            Some(stats) => {
+                                let d = display.get();
+                                format!(
+                                    "mean {} · peak {} · rank {}/{}",
+                                    d.fraction(stats.mean),
+                                    d.fraction(stats.peak),
+                                    stats.rank,
+                                    stats.of,
+                                )
+                            }
+                            None => "no data for this stage".to_string(),
+                        }}
+                    </div>
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+/// Summary of one stage's activity for the focus panel
+struct StageStats {
+    /// Mean and peak over the stage's history (current activity when there's none)
+    mean: f32,
+    peak: f32,
+    /// Position by current activity, 1 = most active, out of `of` stages
+    rank: usize,
+    of: usize,
+}
+
+fn stage_stats(stages: &[StageMetrics], idx: usize) -> Option<StageStats> {
+    let stage = stages.get(idx)?;
+    let samples: &[f32] = if stage.history.is_empty() {
+        std::slice::from_ref(&stage.activity)
+    } else {
+        &stage.history
+    };
+    let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+    let peak = samples.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let rank = 1 + stages.iter().filter(|s| s.activity > stage.activity).count();
+    Some(StageStats { mean, peak, rank, of: stages.len() })
+}
+
+/// Entropy gauge with sparkline
+#[component]
+fn EntropyCard(
+    extended: Signal<Option<ExtendedMetrics>>,
+    display: Signal<DisplaySettings>,
+    instance: Signal<String>,
+) -> impl IntoView {
+    let entropy = move || extended.get().map(|e| e.entropy).unwrap_or_default();
+    let description = move || entropy().description;
+    let current = move || entropy().current;
+    let normalized = move || entropy().normalized;
+    let max_bits = move || entropy().max_bits;
+    let history = Signal::derive(move || entropy().history);
+    let bands = Signal::derive(move || entropy_bands(&entropy()));
+    let band = move || {
+        let e = entropy();
+        if e.band.is_empty() {
+            band_for(&bands.get(), e.current).to_string()
+        } else {
+            e.band
+        }
+    };
+
+    view! {
+        <div class="card entropy-card" id="entropy-card" role="region" aria-label="Entropy">
+            <h2>"ENTROPY"</h2>
+            <FreshnessBadge updated=extended_updated(extended) />
+            <div class="entropy-value">
+                <Pulse value=Signal::derive(move || format!("{} bits", display.get().number(current()))) />
+            </div>
+            <div class="entropy-gauge">
+                <div
+                    class="entropy-fill"
+                    role="meter"
+                    aria-label="Entropy"
+                    aria-valuemin="0"
+                    aria-valuemax=move || format!("{:.2}", max_bits())
+                    aria-valuenow=move || format!("{:.2}", current().min(max_bits()))
+                    aria-valuetext=move || format!("{} bits, {}", display.get().number(current()), band())
+                    style:width=move || format!("{}%", gauge_pct(normalized()))
+                    style:background=move || entropy_band_color(&band())
+                ></div>
+            </div>
+            <div class="entropy-description">{description}</div>
+            <ComparisonSparkline values=history metric="entropy" instance=instance color="#9b59b6" />
+            <div class="entropy-scale">
+                {move || bands
+                    .get()
+                    .into_iter()
+                    .map(|EntropyBand { name, from_bits }| {
+                        let left = move || {
+                            let max = max_bits();
+                            let pct = if max > 0.0 { (from_bits / max * 100.0).clamp(0.0, 100.0) } else { 0.0 };
+                            format!("{}%", pct)
+                        };
+                        let label = name.clone();
+                        let active = move || band() == name;
+                        let color = entropy_band_color(&label);
+                        view! {
+                            <span
+                                style:left=left
+                                class:active=active.clone()
+                                style:color=move || active().then_some(color)
+                            >
+                                {label}
+                            </span>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
+
+/// How far back the comparison reference line looks
+const COMPARE_AGO_SECS: u32 = 300;
+
+/// Length of the comparison reference window
+const COMPARE_WINDOW_SECS: u32 = 60;
+
+/// How often the reference window is re-fetched while comparison is on
+const COMPARE_REFRESH_MS: u32 = 5000;
+
+/// History sparkline; the toggle overlays the same metric from
+/// `COMPARE_AGO_SECS` ago (from /history) as a faint line behind it
+#[component]
+fn ComparisonSparkline(
+    values: Signal<Vec<f32>>,
+    /// `/history` metric name
+    metric: &'static str,
+    instance: Signal<String>,
+    color: &'static str,
+) -> impl IntoView {
+    let (comparing, set_comparing) = create_signal(false);
+    let (reference, set_reference) = create_signal(Vec::<f32>::new());
+    // Bumped on every toggle so a superseded polling loop stops
+    let generation = store_value(0u32);
+
+    let toggle = move |_: web_sys::MouseEvent| {
+        set_comparing.update(|c| *c = !*c);
+        set_reference.set(Vec::new());
+        generation.update_value(|g| *g += 1);
+        if !comparing.get_untracked() {
+            return;
+        }
+        let mine = generation.get_value();
+        spawn_local(async move {
+            // Gone (card unmounted) or toggled since
+            while generation.try_get_value() == Some(mine) {
+                if let Ok(window) = fetch_past_window(metric, &instance.get_untracked()).await {
+                    if generation.try_get_value() == Some(mine) {
+                        set_reference.set(window.values);
+                    }
+                }
+                gloo_timers::future::TimeoutFuture::new(COMPARE_REFRESH_MS).await;
+            }
+        });
+    };
+
+    let spark_ref = create_node_ref::<leptos::html::Canvas>();
+    create_effect(move |_| {
+        let live = values.get();
+        if let Some(canvas) = spark_ref.get() {
+            reference.with(|past| draw_sparkline_with_reference(&canvas, &live, past, color));
+        }
+    });
+
+    view! {
+        <div class="comparison-sparkline">
+            <canvas node_ref=spark_ref class="metric-sparkline" aria-hidden="true" />
+            <button
+                class="toggle-button"
+                class:active=move || comparing.get()
+                title=format!(
+                    "Overlay a {}s window from {} minutes ago (faint line, same scale)",
+                    COMPARE_WINDOW_SECS,
+                    COMPARE_AGO_SECS / 60
+                )
+                on:click=toggle
+            >
+                {format!("vs {}m ago", COMPARE_AGO_SECS / 60)}
+            </button>
+        </div>
+    }
+}
+
+/// Entropy bands for backends that don't send `bands` (their defaults)
+const DEFAULT_ENTROPY_BANDS: [(&str, f32); 3] = [("CLOCKWORK", 0.0), ("BALANCED", 1.0), ("EMERGENT", 2.5)];
+
+/// The backend's entropy bands, or the defaults
+fn entropy_bands(entropy: &EntropyMetrics) -> Vec<EntropyBand> {
+    if entropy.bands.is_empty() {
+        DEFAULT_ENTROPY_BANDS
+            .iter()
+            .map(|&(name, from_bits)| EntropyBand { name: name.to_string(), from_bits })
+            .collect()
+    } else {
+        entropy.bands.clone()
+    }
+}
+
+/// Name of the last band starting at or below `bits` (the first if none does)
+fn band_for(bands: &[EntropyBand], bits: f32) -> &str {
+    bands
+        .iter()
+        .rev()
+        .find(|b| bits >= b.from_bits)
+        .or(bands.first())
+        .map_or("", |b| b.name.as_str())
+}
+
+/// Gauge fill and active-label color per band, from the old gradient's stops
+fn entropy_band_color(band: &str) -> &'static str {
+    match band {
+        "CLOCKWORK" => "#3498db",
+        "EMERGENT" => "#e74c3c",
+        _ => "#9b59b6",
+    }
+}
+
+/// Fractality gauge - clockwork to fractal transition
+#[component]
+fn FractalityCard(
+    extended: Signal<Option<ExtendedMetrics>>,
+    display: Signal<DisplaySettings>,
+    instance: Signal<String>,
+) -> impl IntoView {
+    let fractality = move || extended.get().map(|e| e.fractality).unwrap_or_default();
+    let history = Signal::derive(move || fractality().history);
+    let score = move || fractality().score;
+    let description = move || fractality().description;
+    let burst_ratio = move || fractality().burst_ratio;
+
+    let radar_ref = create_node_ref::<leptos::html::Canvas>();
+    create_effect(move |_| {
+        let spokes = fractality_spokes(&fractality());
+        if let Some(canvas) = radar_ref.get() {
+            draw_fractality_radar(&canvas, &spokes);
+        }
+    });
+
+    view! {
+        <div class="card fractality-card" role="region" aria-label="Fractality">
+            <h2>"FRACTALITY"</h2>
+            <FreshnessBadge updated=extended_updated(extended) />
+            <div class="fractality-score">
+                <Pulse value=Signal::derive(move || display.get().fraction(score())) />
+            </div>
+            <div class="fractality-gauge">
+                <div
+                    class="fractality-fill"
+                    role="meter"
+                    aria-label="Fractality"
+                    aria-valuemin="0"
+                    aria-valuemax="100"
+                    aria-valuenow=move || gauge_pct(score()).to_string()
+                    aria-valuetext=move || format!("{}, {}", display.get().fraction(score()), description())
+                    style:width=move || format!("{}%", gauge_pct(score()))
+                ></div>
+            </div>
+            <div class="fractality-description">{description}</div>
+            <ComparisonSparkline values=history metric="fractality" instance=instance color="#e67e22" />
+            <canvas node_ref=radar_ref class="fractality-radar" aria-hidden="true" />
+            <div class="fractality-stats">
+                <span>"σ inter-arrival: "{move || display.get().number(fractality().inter_arrival_sigma)}</span>
+                <span>"σ boot: "{move || display.get().number(fractality().boot_sigma)}</span>
+                <span>"Burst Ratio: "{move || display.get().number(burst_ratio())}</span>
+            </div>
+        </div>
+    }
+}
+
+/// Memory Windows - 9 TMI slots
+#[component]
+fn MemoryWindowsCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
+    let windows = move || extended.get().map(|e| e.memory_windows).unwrap_or_default();
+    let slots = move || windows().slots;
+    let active = move || windows().active_count;
+
+    view! {
+        <div class="card memory-windows-card" role="region" aria-label="Memory Windows">
+            <h2>"MEMORY WINDOWS"</h2>
+            <FreshnessBadge updated=extended_updated(extended) />
+            <div class="windows-header">
+                <span>{move || format!("{}/9 active", active())}</span>
+            </div>
+            <div class="memory-slots">
+                <For
+                    each=move || slots()
+                    key=|s| s.id
+                    children=move |slot| {
+                        // Rows are keyed by id, so read the live slot rather than the captured one
+                        let id = slot.id;
+                        let current = move || {
+                            slots()
+                                .into_iter()
+                                .find(|s| s.id == id)
+                                .unwrap_or(MemorySlot { id, ..Default::default() })
+                        };
+                        let strength = move || {
+                            let s = current();
+                            s.strength.unwrap_or(if s.active { 1.0 } else { 0.0 }).clamp(0.0, 1.0)
+                        };
+                        view! {
+                            <div
+                                class="slot"
+                                class:active=move || current().active
+                                style=move || format!("--strength: {:.2}", strength())
+                                title=move || format!("Slot {}: strength {:.0}%", id, strength() * 100.0)
+                            >
+                                {id}
+                            </div>
+                        }
+                    }
+                />
+            </div>
+        </div>
+    }
+}
+
+/// Manifold Clustering - silhouette score (VCONN-7)
+#[component]
+fn ClusteringCard(extended: Signal<Option<ExtendedMetrics>>) -> impl IntoView {
+    let clustering = move || extended.get().map(|e| e.clustering).unwrap_or_default();
+    let silhouette = move || clustering().silhouette;
+    let has_structure = move || clustering().has_structure;
+    let gauge_width = move || {
+        // Scale -1..1 to 0..100%
+        gauge_pct((silhouette() + 1.0) / 2.0)
+    };
+
+    view! {
+        <div class="card clustering-card" role="region" aria-label="Manifold Structure">
+            <h2>"MANIFOLD STRUCTURE"</h2>
+            <FreshnessBadge updated=extended_updated(extended) />
+            <div class="clustering-score">
+                <Pulse value=Signal::derive(move || format!("{:.3}", silhouette())) />
+            </div>
+            <div class="clustering-gauge">
+                <div
+                    class="clustering-fill"
+                    class:structured=has_structure
+                    role="meter"
+                    aria-label="Silhouette score"
+                    aria-valuemin="-1"
+                    aria-valuemax="1"
+                    aria-valuenow=move || format!("{:.3}", silhouette())
+                    style:width=move || format!("{}%", gauge_width())
+                ></div>
+            </div>
+            <div class="clustering-status">
+                {move || if has_structure() { "Structured" } else { "Sparse" }}
+            </div>
+            <div class="clustering-label">"Silhouette score (> 0.3 = meaningful clusters)"</div>
+        </div>
+    }
+}
+
+/// Philosophy banner
+#[component]
+fn PhilosophyCard(extended: Signal<Option<ExtendedMetrics>>, instance: Signal<String>) -> impl IntoView {
+    // The backend's local list, so the banner has something to say while the core is away
+    let (local, set_local) = create_signal(QuotesResponse::default());
+    create_effect(move |_| {
+        let name = instance.get();
+        spawn_local(async move {
+            if let Ok(resp) = fetch_quotes(&name).await {
+                set_local.set(resp);
+            }
+        });
+    });
+    let clock = use_context::<ServerClock>().map(|c| c.0);
+    // The core's quote (or the backend's fill-in), else the local list on the same clock
+    let quote = create_memo(move |_| {
+        extended
+            .with(|e| e.as_ref().map(|e| e.philosophy.quote.clone()))
+            .filter(|q| !q.trim().is_empty())
+            .unwrap_or_else(|| {
+                let now = clock.map_or_else(Utc::now, |c| c.get());
+                local.with(|l| l.current(now).unwrap_or_default().to_string())
+            })
+    });
+
+    // Each change re-renders the pair: the new quote fades in over the old one fading out
+    let (outgoing, set_outgoing) = create_signal(None::<String>);
+    let (generation, set_generation) = create_signal(0u64);
+    create_effect(move |previous: Option<String>| {
+        let next = quote.get();
+        if previous.is_some() {
+            set_outgoing.set(previous.filter(|q| !q.is_empty()));
+            set_generation.update(|g| *g += 1);
+        }
+        next
+    });
+
+    view! {
+        <div class="card philosophy-card" role="region" aria-label="Philosophy">
+            <div class="philosophy-quotes" aria-live="polite">
+                {move || {
+                    let _ = generation.get();
+                    view! {
+                        <div class="philosophy-quote quote-in">{quote.get_untracked()}</div>
+                        {outgoing
+                            .get_untracked()
+                            .map(|q| view! { <div class="philosophy-quote quote-out" aria-hidden="true">{q}</div> })}
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
+
+/// 3D Thought Manifold - visualize thought vectors as a rotating point cloud
+#[component]
+fn ThoughtManifoldCard(
+    paused: Signal<bool>,
+    instance: Signal<String>,
+    text_mode: Signal<bool>,
+    /// Drives the automatic switch to the unconscious layer
+    dreaming: Signal<bool>,
+) -> impl IntoView {
+    let canvas_ref = create_node_ref::<leptos::html::Canvas>();
+    // `live` tracks the server; `manifold` is what's drawn (frozen while paused)
+    let (live, set_live) = create_signal(ManifoldResponse::default());
+    let (manifold, set_manifold) = create_signal(ManifoldResponse::default());
+    if let Some(ShownManifold(shown)) = use_context::<ShownManifold>() {
+        shown.set_value(Some(manifold));
+    }
+    // Camera starts from the page URL, so a bookmarked or shared view reopens as it was
+    let camera = camera_from_url();
+    let (rotation, set_rotation) = create_signal(camera.rotation);
+    let (pitch, set_pitch) = create_signal(camera.pitch);
+    let (zoom, set_zoom) = create_signal(camera.zoom);
+    let (dragging, set_dragging) = create_signal(false);
+    let (last_x, set_last_x) = create_signal(0.0f64);
+    let (last_y, set_last_y) = create_signal(0.0f64);
+    let (projection_mode, set_projection_mode) = create_signal(camera.mode);
+    let (min_salience, set_min_salience) = create_signal(0.0f32);
+    let (trails, set_trails) = create_signal(false);
+    let (kinship, set_kinship) = create_signal(false);
+    // Density heatmap: off, under the points, or instead of them
+    let (density, set_density) = create_signal(DensityMode::Points);
+    // Color thoughts by memory category instead of k-means cluster
+    let (by_category, set_by_category) = create_signal(false);
+    // Memory store drawn; auto follows the dream flag
+    let (layer_mode, set_layer_mode) = create_signal(LayerMode::Auto);
+    let layer = create_memo(move |_| layer_mode.get().resolve(dreaming.get()));
+    // Auto-rotate multiplier, persisted across visits
+    let (rotate_speed, set_rotate_speed) = create_signal(load_rotate_speed());
+    let change_rotate_speed = move |speed: f64| {
+        set_rotate_speed.set(speed);
+        save_rotate_speed(speed);
+    };
+    // Point size and glow, persisted across visits
+    let (point_style, set_point_style) = create_signal(PointStyle::load());
+    let change_point_style = move |change: &dyn Fn(&mut PointStyle)| {
+        set_point_style.update(|style| {
+            change(style);
+            style.save();
+        });
+    };
+    // Last fetch failed upstream (as opposed to an empty collection)
+    let (unavailable, set_unavailable) = create_signal(false);
+    // Pinned thought (by point id), tracked across fetches, and its full content
+    let (pinned, set_pinned) = create_signal(None::<String>);
+    let (pinned_detail, set_pinned_detail) = create_signal(None::<ThoughtDetail>);
+    // Where each thought was drawn last frame, for click hit-testing
+    let hit_targets = store_value(Vec::<HitTarget>::new());
+    // Point under the cursor (id), for the preview tooltip
+    let (hovered, set_hovered) = create_signal(None::<String>);
+    // Pointer travel since mousedown; a click only pins if the view wasn't dragged
+    let drag_travel = store_value(0.0f64);
+    // The manifold being transitioned away from, and when the transition began
+    let previous = store_value(ManifoldResponse::default());
+    let transition_start = store_value(0.0f64);
+    // Bumped every animation frame while a transition is running
+    let (frame, set_frame) = create_signal(0u64);
+    // Page Visibility: nothing is animated or drawn while the tab is hidden
+    let (page_visible, set_page_visible) = create_signal(true);
+    watch_page_visibility(set_page_visible);
+
+    // Mirror the camera into the URL once the user stops adjusting it. Only
+    // user changes schedule a write; auto-rotation alone would never settle.
+    let url_writes = store_value(0u32);
+    let save_camera = move || {
+        url_writes.update_value(|n| *n += 1);
+        let generation = url_writes.get_value();
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(VIEW_URL_DEBOUNCE_MS).await;
+            // Gone (card unmounted) or superseded by a later change
+            if url_writes.try_get_value() != Some(generation) {
+                return;
+            }
+            write_camera_to_url(ManifoldCamera {
+                rotation: rotation.get_untracked(),
+                pitch: pitch.get_untracked(),
+                zoom: zoom.get_untracked(),
+                mode: projection_mode.get_untracked(),
+            });
+        });
+    };
+
+    // Incremental updates over SSE, resubscribed whenever the instance or layer changes
+    let source = store_value(None::<web_sys::EventSource>);
+    create_effect(move |_| {
+        let name = instance.get();
+        let layer = layer.get();
+        if let Some(old) = source.get_value() {
+            old.close();
+        }
+        set_live.set(ManifoldResponse::default());
+        set_unavailable.set(false);
+        set_pinned.set(None);
+        source.set_value(subscribe_manifold(set_live, set_unavailable, &name, layer));
+    });
+    on_cleanup(move || {
+        if let Some(current) = source.get_value() {
+            current.close();
+        }
+    });
+
+    // Fall back to polling /vectors while there's no EventSource
+    spawn_local(async move {
+        loop {
+            if source.with_value(|s| s.is_none()) && !paused.get_untracked() && page_visible.get_untracked() {
+                match fetch_manifold(&instance.get_untracked(), layer.get_untracked()).await {
+                    Ok(resp) => {
+                        set_unavailable.set(false);
+                        set_live.set(resp);
+                    }
+                    Err(()) => set_unavailable.set(true),
+                }
+            }
+            gloo_timers::future::TimeoutFuture::new(2000).await;
+        }
+    });
+
+    // Publish live data to the render unless paused (catches up on resume);
+    // each publish starts a transition from the manifold it replaces
+    create_effect(move |_| {
+        let latest = live.get();
+        if !paused.get() {
+            previous.set_value(manifold.get_untracked());
+            transition_start.set_value(now_ms());
+            set_manifold.set(latest);
+        }
+    });
+
+    // Full content for the pinned thought, fetched once per pin
+    create_effect(move |_| {
+        set_pinned_detail.set(None);
+        let Some(id) = pinned.get() else { return };
+        spawn_local(async move {
+            let layer = layer.get_untracked();
+            if let Ok(detail) = fetch_thought_detail(&id, &instance.get_untracked(), layer).await {
+                if pinned.get_untracked().as_deref() == Some(id.as_str()) {
+                    set_pinned_detail.set(Some(detail));
+                }
+            }
+        });
+    });
+
+    // One requestAnimationFrame loop drives auto-rotation and transition redraws,
+    // capped at MANIFOLD_MAX_FPS. Browsers stop rAF in background tabs; after a
+    // gap the rotation picks up where it was instead of jumping.
+    spawn_local(async move {
+        let mut last_frame: Option<f64> = None;
+        loop {
+            next_animation_frame().await;
+            if !page_visible.get_untracked() {
+                last_frame = None;
+                continue;
+            }
+            let now = precise_now_ms();
+            let step = last_frame.map_or(0.0, |last| now - last);
+            // 1ms slack so rAF jitter doesn't drop every other frame
+            if last_frame.is_some() && step + 1.0 < 1000.0 / MANIFOLD_MAX_FPS {
+                continue;
+            }
+            last_frame = Some(now);
+
+            batch(|| {
+                if !dragging.get_untracked() && !paused.get_untracked() {
+                    let step_secs = step.min(MAX_FRAME_STEP_MS) / 1000.0;
+                    let speed = AUTO_ROTATE_RAD_PER_SEC * rotate_speed.get_untracked();
+                    set_rotation.update(|r| *r += speed * step_secs);
+                }
+                if now_ms() - transition_start.get_value() < MANIFOLD_TRANSITION_MS {
+                    set_frame.update(|f| *f += 1);
+                }
+            });
+        }
+    });
+
+    // Render loop (idle while hidden; redraws once the tab is visible again)
+    create_effect(move |_| {
+        if !page_visible.get() {
+            return;
+        }
+        let _ = manifold.get();
+        let _ = frame.get();
+        let rot = rotation.get();
+        let tilt = pitch.get();
+        let zoom = zoom.get();
+        let mode = projection_mode.get();
+        let threshold = min_salience.get();
+        let trails = trails.get();
+        let kinship = kinship.get();
+        let by_category = by_category.get();
+        let pinned = pinned.get();
+        let point_style = point_style.get();
+        let density = density.get();
+
+        if let Some(canvas) = canvas_ref.get() {
+            let progress = ((now_ms() - transition_start.get_value()) / MANIFOLD_TRANSITION_MS).clamp(0.0, 1.0);
+            let targets = previous.with_value(|previous| {
+                let view = ManifoldView {
+                    rotation: rot,
+                    pitch: tilt,
+                    zoom,
+                    mode,
+                    min_salience: threshold,
+                    trails,
+                    kinship,
+                    by_category,
+                    pinned: pinned.as_deref(),
+                    point_style,
+                    density,
+                };
+                let transition = Transition { previous, progress };
+                render_manifold(&canvas, &manifold.get_untracked(), Some(transition), view)
+            });
+            hit_targets.set_value(targets);
+        }
+    });
+
+    let visible_count = move || {
+        let threshold = min_salience.get();
+        manifold.with(|m| m.points.iter().filter(|p| p.salience >= threshold).count())
+    };
+
+    // Mouse handlers: horizontal drag rotates, vertical drag tilts, wheel zooms
+    let on_mouse_down = move |e: web_sys::MouseEvent| {
+        set_dragging.set(true);
+        set_last_x.set(e.client_x() as f64);
+        set_last_y.set(e.client_y() as f64);
+        drag_travel.set_value(0.0);
+    };
+
+    let on_mouse_move = move |e: web_sys::MouseEvent| {
+        if dragging.get() {
+            let dx = e.client_x() as f64 - last_x.get();
+            let dy = e.client_y() as f64 - last_y.get();
+            set_rotation.update(|r| *r += dx * 0.01);
+            set_pitch.update(|p| *p = (*p + dy * 0.01).clamp(-MAX_PITCH, MAX_PITCH));
+            set_last_x.set(e.client_x() as f64);
+            set_last_y.set(e.client_y() as f64);
+            drag_travel.update_value(|t| *t += dx.abs() + dy.abs());
+            save_camera();
+        } else {
+            let hit = hit_targets.with_value(|targets| hit_test(targets, e.offset_x() as f64, e.offset_y() as f64));
+            if hit != hovered.get_untracked() {
+                set_hovered.set(hit);
+            }
+        }
+    };
+
+    // Native tooltip with the hovered point's text, straight from the manifold payload
+    let hover_title = move || {
+        let id = hovered.get()?;
+        manifold.with(|m| m.points.iter().find(|p| p.id == id).and_then(|p| p.preview.clone()))
+    };
+
+    // Click a point to pin it (again to unpin); drags don't count as clicks
+    let on_click = move |e: web_sys::MouseEvent| {
+        if drag_travel.get_value() > CLICK_MAX_TRAVEL {
+            return;
+        }
+        let hit = hit_targets.with_value(|targets| hit_test(targets, e.offset_x() as f64, e.offset_y() as f64));
+        if let Some(id) = hit {
+            set_pinned.update(|p| *p = if p.as_deref() == Some(id.as_str()) { None } else { Some(id) });
+        }
+    };
+
+    let pinned_point = move || {
+        let id = pinned.get()?;
+        manifold.with(|m| m.points.iter().find(|p| p.id == id).cloned())
+    };
+
+    let on_mouse_up = move |_: web_sys::MouseEvent| {
+        set_dragging.set(false);
+    };
+
+    let on_wheel = move |e: web_sys::WheelEvent| {
+        // Keep the page from scrolling while zooming the canvas
+        e.prevent_default();
+        set_zoom.update(|z| *z = (*z * (-e.delta_y() * 0.001).exp()).clamp(MIN_ZOOM, MAX_ZOOM));
+        save_camera();
+    };
+
+    let on_mouse_leave = move |_: web_sys::MouseEvent| {
+        set_dragging.set(false);
+        set_hovered.set(None);
+    };
+
+    // Screen-reader description of what the canvas shows
+    let canvas_label = move || {
+        manifold.with(|m| match m.centroids.first() {
+            Some(largest) => format!(
+                "{} thought vectors in {} clusters; largest is cluster {} with {} points",
+                m.points.len(),
+                m.centroids.len(),
+                largest.cluster + 1,
+                largest.size,
+            ),
+            None => format!("{} thought vectors", m.points.len()),
+        })
+    };
+
+    view! {
+        <div
+            class="card manifold-card"
+            class:unconscious=move || manifold.with(|m| m.layer == MemoryLayer::Unconscious)
+            role="region"
+            aria-label="Thought Manifold"
+        >
+            <div class="manifold-header">
+                <h2>"THOUGHT MANIFOLD"</h2>
+                <button
+                    class="toggle-button"
+                    class:active=move || layer.get() == MemoryLayer::Unconscious
+                    title="Memory store to draw; auto shows the unconscious while the mind dreams"
+                    on:click=move |_| set_layer_mode.update(|m| *m = m.next())
+                >
+                    {move || layer_mode.get().label()}
+                </button>
+                <button
+                    class="toggle-button"
+                    class:active=move || trails.get()
+                    title="Leave fading trails behind moving points"
+                    on:click=move |_| set_trails.update(|t| *t = !*t)
+                >
+                    "trails"
+                </button>
+                <button
+                    class="toggle-button"
+                    class:active=move || kinship.get()
+                    title="Link thoughts that are close in the original embedding space"
+                    on:click=move |_| set_kinship.update(|k| *k = !*k)
+                >
+                    "kinship"
+                </button>
+                <button
+                    class="toggle-button"
+                    class:active=move || density.get() != DensityMode::Points
+                    title="Shade where thoughts concentrate: a faint heatmap under the points, or the heatmap alone"
+                    on:click=move |_| set_density.update(|d| *d = d.next())
+                >
+                    {move || density.get().label()}
+                </button>
+                <button
+                    class="toggle-button"
+                    class:active=move || by_category.get()
+                    title="Color thoughts by memory category instead of cluster"
+                    disabled=move || manifold.with(|m| m.points.iter().all(|p| p.category.is_none()))
+                    on:click=move |_| set_by_category.update(|c| *c = !*c)
+                >
+                    "category"
+                </button>
+                <button
+                    class="toggle-button"
+                    title="Orthographic keeps true relative sizes; perspective gives depth cues"
+                    on:click=move |_| {
+                        set_projection_mode.update(|m| *m = m.toggled());
+                        save_camera();
+                    }
+                >
+                    {move || projection_mode.get().label()}
+                </button>
+                <button
+                    class="toggle-button"
+                    title="Download the current frame (with legend) as a PNG"
+                    disabled=move || text_mode.get()
+                    on:click=move |_| {
+                        if let Some(canvas) = canvas_ref.get_untracked() {
+                            let result = manifold.with_untracked(|m| {
+                                download_manifold_png(&canvas, m, by_category.get_untracked())
+                            });
+                            if let Err(e) = result {
+                                log(&format!("PNG export failed: {:?}", e));
+                            }
+                        }
+                    }
+                >
+                    "png"
+                </button>
+            </div>
+            <div class="manifold-subtitle">
+                {move || {
+                    let unconscious = manifold.with(|m| m.layer == MemoryLayer::Unconscious);
+                    if unavailable.get() {
+                        "manifold unavailable".to_string()
+                    } else if manifold.with(|m| m.projection_type == "empty") {
+                        let empty = if unconscious { "no unconscious memories yet" } else { "no memories yet" };
+                        empty.to_string()
+                    } else {
+                        manifold.with(|m| {
+                            let refit = m
+                                .last_refit
+                                .map(|at| format!(" | refit {}", format_relative(at, Utc::now())))
+                                .unwrap_or_default();
+                            format!(
+                                "{}{} / {} vectors | {}-dim → 3D | {}{}",
+                                if unconscious { "unconscious layer | " } else { "" },
+                                visible_count(),
+                                m.points.len(),
+                                m.dimension,
+                                m.projection_label(),
+                                refit
+                            )
+                        })
+                    }
+                }}
+                <Show when=move || !unavailable.get() && manifold.with(|m| m.projection_type == "random")>
+                    <span
+                        class="projection-hint"
+                        title="Random axes keep clusters apart but distances and directions mean little"
+                    >
+                        " · PCA engages once enough memories accumulate"
+                    </span>
+                </Show>
+            </div>
+            <label class="salience-filter">
+                "min salience"
+                <input
+                    type="range"
+                    min="0"
+                    max="1"
+                    step="0.05"
+                    prop:value=move || min_salience.get().to_string()
+                    on:input=move |ev| {
+                        if let Ok(v) = event_target_value(&ev).parse::<f32>() {
+                            set_min_salience.set(v);
+                        }
+                    }
+                />
+                <span class="salience-value">{move || format!("{:.2}", min_salience.get())}</span>
+            </label>
+            <label class="rotate-speed">
+                "rotate"
+                <input
+                    type="range"
+                    min=(-MAX_ROTATE_SPEED).to_string()
+                    max=MAX_ROTATE_SPEED.to_string()
+                    step="0.25"
+                    prop:value=move || rotate_speed.get().to_string()
+                    on:input=move |ev| {
+                        if let Ok(v) = event_target_value(&ev).parse::<f64>() {
+                            change_rotate_speed(v);
+                        }
+                    }
+                />
+                <button
+                    class="toggle-button"
+                    title="Reverse the rotation direction"
+                    disabled=move || rotate_speed.get() == 0.0
+                    on:click=move |_| change_rotate_speed(-rotate_speed.get_untracked())
+                >
+                    "⇄"
+                </button>
+                <span class="rotate-speed-value">{move || rotate_speed_label(rotate_speed.get())}</span>
+            </label>
+            <div class="point-style">
+                <label>
+                    "size"
+                    <input
+                        type="range"
+                        min=MIN_POINT_SIZE.to_string()
+                        max=MAX_POINT_SIZE.to_string()
+                        step="0.25"
+                        prop:value=move || point_style.get().size.to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse::<f64>() {
+                                change_point_style(&|s| s.size = v);
+                            }
+                        }
+                    />
+                    <span class="point-style-value">{move || format!("{}×", point_style.get().size)}</span>
+                </label>
+                <label>
+                    "glow"
+                    <input
+                        type="range"
+                        min="0"
+                        max=MAX_POINT_GLOW.to_string()
+                        step="0.25"
+                        prop:value=move || point_style.get().glow.to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse::<f64>() {
+                                change_point_style(&|s| s.glow = v);
+                            }
+                        }
+                    />
+                    <span class="point-style-value">{move || point_style.get().glow_label()}</span>
+                </label>
+                <button
+                    class="toggle-button"
+                    class:active=move || point_style.get().glow == 0.0
+                    title="Draw points without glow (clearer dense clouds, faster drawing)"
+                    on:click=move |_| change_point_style(&|s| s.glow = if s.glow == 0.0 { 1.0 } else { 0.0 })
+                >
+                    "no glow"
+                </button>
+            </div>
+            <Show
+                when=move || text_mode.get()
+                fallback=move || view! {
+                    <canvas
+                        node_ref=canvas_ref
+                        width=MANIFOLD_WIDTH
+                        height=MANIFOLD_HEIGHT
+                        class="manifold-canvas"
+                        role="img"
+                        aria-label=canvas_label
+                        title=hover_title
+                        on:mousedown=on_mouse_down
+                        on:mousemove=on_mouse_move
+                        on:mouseup=on_mouse_up
+                        on:mouseleave=on_mouse_leave
+                        on:wheel=on_wheel
+                        on:click=on_click
+                    />
+                }
+            >
+                <table class="manifold-table" aria-live="polite">
+                    <caption>{canvas_label}</caption>
+                    <thead>
+                        <tr>
+                            <th scope="col">"Cluster"</th>
+                            <th scope="col">"Points"</th>
+                            <th scope="col">"Centroid x"</th>
+                            <th scope="col">"Centroid y"</th>
+                            <th scope="col">"Centroid z"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || manifold.with(|m| {
+                            m.centroids
+                                .iter()
+                                .map(|c| view! {
+                                    <tr>
+                                        <th scope="row">{c.cluster + 1}</th>
+                                        <td>{c.size}</td>
+                                        <td>{format!("{:.2}", c.x)}</td>
+                                        <td>{format!("{:.2}", c.y)}</td>
+                                        <td>{format!("{:.2}", c.z)}</td>
+                                    </tr>
+                                })
+                                .collect_view()
+                        })}
+                    </tbody>
+                </table>
+            </Show>
+            <Show when=move || pinned.get().is_some()>
+                <div class="pin-panel" aria-live="polite">
+                    <div class="pin-header">
+                        <span class="pin-id">{move || format!("📌 {}", pinned.get().unwrap_or_default())}</span>
+                        <button class="toggle-button" on:click=move |_| set_pinned.set(None)>"unpin"</button>
+                    </div>
+                    <div class="pin-stats">
+                        {move || match pinned_point() {
+                            Some(p) => format!(
+                                "salience {:.2} · cluster {} · {} old",
+                                p.salience,
+                                p.cluster + 1,
+                                format_duration(p.age_ms / 1000),
+                            ),
+                            None => "no longer in working set".to_string(),
+                        }}
+                    </div>
+                    <div class="pin-content">
+                        {move || match pinned_detail.get() {
+                            Some(detail) if !detail.content.is_empty() => detail.content,
+                            Some(_) => "(no content recorded)".to_string(),
+                            // Show the preview while the full text loads
+                            None => pinned_point()
+                                .and_then(|p| p.preview)
+                                .unwrap_or_else(|| "loading…".to_string()),
+                        }}
+                    </div>
+                </div>
+            </Show>
+            <div class="manifold-legend">
+                <span class="legend-crystal">"★ Law Crystals"</span>
+                <span class="legend-thought">
+                    {move || if by_category.get() {
+                        "○ Thoughts (brightness = salience, color = category)".to_string()
+                    } else {
+                        format!("○ Thoughts (brightness = salience, color = {} clusters)", manifold.with(|m| m.centroids.len()))
+                    }}
+                </span>
+                <Show when=move || by_category.get()>
+                    {move || manifold.with(|m| {
+                        category_legend(&m.points)
+                            .into_iter()
+                            .map(|entry| {
+                                let (r, g, b) = entry.color;
+                                view! {
+                                    <span class="legend-category" style=format!("color: rgb({}, {}, {})", r, g, b)>
+                                        {format!("● {} ({})", entry.label(), entry.count)}
+                                    </span>
+                                }
+                            })
+                            .collect_view()
+                    })}
+                </Show>
+                <span class="legend-age">
+                    {format!("◐ Dims with age (half-life {})", format_duration((AGE_HALF_LIFE_MS / 1000.0) as u64))}
+                </span>
+                <Show when=move || kinship.get()>
+                    <span class="legend-kinship">
+                        {move || format!("─ Kinship ({} links, brighter = more similar)", manifold.with(|m| m.edges.len()))}
+                    </span>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+/// Upper bound on manifold redraws per second (high-refresh displays would otherwise draw 120+)
+const MANIFOLD_MAX_FPS: f64 = 30.0;
+
+/// Auto-rotation speed at 1×, radians per second
+const AUTO_ROTATE_RAD_PER_SEC: f64 = 0.2;
+
+/// localStorage key for the auto-rotate speed multiplier
+const ROTATE_SPEED_STORAGE_KEY: &str = "daneel-web-rotate-speed";
+
+/// Fastest auto-rotation either way, as a multiple of `AUTO_ROTATE_RAD_PER_SEC`
+const MAX_ROTATE_SPEED: f64 = 3.0;
+
+/// localStorage key for the manifold point size and glow
+const POINT_STYLE_STORAGE_KEY: &str = "daneel-web-point-style";
+
+/// Point size range, as a multiple of `THOUGHT_POINT_RADIUS`
+const MIN_POINT_SIZE: f64 = 0.5;
+const MAX_POINT_SIZE: f64 = 3.0;
+
+/// Strongest glow, as a multiple of the default shadow blur
+const MAX_POINT_GLOW: f64 = 2.0;
+
+/// How manifold points are drawn, persisted to localStorage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct PointStyle {
+    /// Multiplies the thought point radius (pinned points and hit areas follow)
+    size: f64,
+    /// Multiplies every shadow blur; 0 draws no glow at all
+    glow: f64,
+}
+
+impl Default for PointStyle {
+    fn default() -> Self {
+        Self { size: 1.0, glow: 1.0 }
+    }
+}
+
+impl PointStyle {
+    fn load() -> Self {
+        local_storage()
+            .and_then(|s| s.get_item(POINT_STYLE_STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .map(Self::clamped)
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(&self.clamped())) {
+            let _ = storage.set_item(POINT_STYLE_STORAGE_KEY, &json);
+        }
+    }
+
+    /// Within the slider ranges (non-finite values fall back to the default)
+    fn clamped(self) -> Self {
+        let clamp = |v: f64, lo: f64, hi: f64, default: f64| if v.is_finite() { v.clamp(lo, hi) } else { default };
+        Self {
+            size: clamp(self.size, MIN_POINT_SIZE, MAX_POINT_SIZE, 1.0),
+            glow: clamp(self.glow, 0.0, MAX_POINT_GLOW, 1.0),
+        }
+    }
+
+    /// Glow readout: "off", "1×"
+    fn glow_label(self) -> String {
+        if self.glow == 0.0 {
+            "off".to_string()
+        } else {
+            format!("{}×", self.glow)
+        }
+    }
+}
+
+/// Saved auto-rotate multiplier (negative = reversed, 0 = stopped), 1× if unset
+fn load_rotate_speed() -> f64 {
+    local_storage()
+        .and_then(|s| s.get_item(ROTATE_SPEED_STORAGE_KEY).ok().flatten())
+        .and_then(|raw| raw.parse::<f64>().ok())
+        .filter(|speed| speed.is_finite())
+        .map_or(1.0, |speed| speed.clamp(-MAX_ROTATE_SPEED, MAX_ROTATE_SPEED))
+}
+
+fn save_rotate_speed(speed: f64) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(ROTATE_SPEED_STORAGE_KEY, &speed.to_string());
+    }
+}
+
+/// Rotate-speed readout: "stopped", "1×", "reversed 0.5×"
+fn rotate_speed_label(speed: f64) -> String {
+    if speed == 0.0 {
+        "stopped".to_string()
+    } else if speed < 0.0 {
+        format!("reversed {}×", -speed)
+    } else {
+        format!("{}×", speed)
+    }
+}
+
+/// Longest frame step applied to the rotation, so a stalled tab doesn't resume with a jump
+const MAX_FRAME_STEP_MS: f64 = 100.0;
+
+/// Which memory layer the manifold card draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayerMode {
+    /// The unconscious while the mind dreams, the conscious otherwise
+    Auto,
+    Conscious,
+    Unconscious,
+}
+
+impl LayerMode {
+    fn next(self) -> Self {
+        match self {
+            Self::Auto => Self::Conscious,
+            Self::Conscious => Self::Unconscious,
+            Self::Unconscious => Self::Auto,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "layer: auto",
+            Self::Conscious => "layer: conscious",
+            Self::Unconscious => "layer: unconscious",
+        }
+    }
+
+    fn resolve(self, dreaming: bool) -> MemoryLayer {
+        match self {
+            Self::Auto if dreaming => MemoryLayer::Unconscious,
+            Self::Auto | Self::Conscious => MemoryLayer::Conscious,
+            Self::Unconscious => MemoryLayer::Unconscious,
+        }
+    }
+}
+
+/// How 3D manifold coordinates are flattened onto the canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectionMode {
+    /// Divide by depth: better depth cues, distorts distances
+    Perspective,
+    /// Constant scale: true relative sizes for comparing clusters
+    Orthographic,
+}
+
+impl ProjectionMode {
+    fn toggled(self) -> Self {
+        match self {
+            Self::Perspective => Self::Orthographic,
+            Self::Orthographic => Self::Perspective,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Perspective => "perspective",
+            Self::Orthographic => "orthographic",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "perspective" => Some(Self::Perspective),
+            "orthographic" => Some(Self::Orthographic),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the manifold draws individual points, a density heatmap, or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DensityMode {
+    Points,
+    /// Points over a faint heatmap
+    Blend,
+    /// The heatmap instead of thought points (crystals and the pinned thought stay)
+    Heatmap,
+}
+
+impl DensityMode {
+    fn next(self) -> Self {
+        match self {
+            Self::Points => Self::Blend,
+            Self::Blend => Self::Heatmap,
+            Self::Heatmap => Self::Points,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Points => "density: off",
+            Self::Blend => "density: blend",
+            Self::Heatmap => "density: heatmap",
+        }
+    }
+
+    /// Opacity of the densest spot
+    fn peak_alpha(self) -> f64 {
+        match self {
+            Self::Points => 0.0,
+            Self::Blend => DENSITY_BLEND_ALPHA,
+            Self::Heatmap => DENSITY_HEATMAP_ALPHA,
+        }
+    }
+}
+
+/// Side of one density bin (CSS px); the smoothed grid is stretched over the canvas
+const DENSITY_CELL_PX: f64 = 6.0;
+
+/// Passes of the 1-4-6-4-1 kernel over the bins, each way
+const DENSITY_BLUR_PASSES: usize = 3;
+
+/// Heatmap opacity at its densest, under the points and on its own
+const DENSITY_BLEND_ALPHA: f64 = 0.35;
+const DENSITY_HEATMAP_ALPHA: f64 = 0.9;
+
+/// Cold to hot: (density, color) stops of the heatmap ramp
+const DENSITY_RAMP: [(f64, (u8, u8, u8)); 5] =
+    [(0.0, (40, 0, 90)), (0.35, (200, 30, 60)), (0.65, (255, 140, 0)), (0.9, (255, 230, 80)), (1.0, (255, 255, 255))];
+
+/// Steepest manifold tilt either way, short of looking straight down the Y axis
+const MAX_PITCH: f64 = 1.4;
+
+/// Manifold zoom range (1.0 = fit the default view)
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 4.0;
+
+/// Quiet time after the last view change before the page URL is rewritten
+const VIEW_URL_DEBOUNCE_MS: u32 = 500;
+
+/// Page URL query keys for the manifold view
+const VIEW_URL_KEYS: [&str; 4] = ["mrot", "mpitch", "mzoom", "mproj"];
+
+/// Manifold camera settings, mirrored into the page URL so a view survives
+/// reloads and can be bookmarked or shared
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ManifoldCamera {
+    rotation: f64,
+    pitch: f64,
+    zoom: f64,
+    mode: ProjectionMode,
+}
+
+impl Default for ManifoldCamera {
+    fn default() -> Self {
+        Self { rotation: 0.0, pitch: 0.0, zoom: 1.0, mode: ProjectionMode::Perspective }
+    }
+}
+
+impl ManifoldCamera {
+    /// Camera from a `location.search` string; missing or malformed keys keep their defaults
+    fn from_search(search: &str) -> Self {
+        let mut camera = Self::default();
+        let number = |value: &str| value.parse::<f64>().ok().filter(|v| v.is_finite());
+        for (key, value) in search.trim_start_matches('?').split('&').filter_map(|kv| kv.split_once('=')) {
+            match key {
+                "mrot" => camera.rotation = number(value).map_or(camera.rotation, |v| v.rem_euclid(2.0 * PI)),
+                "mpitch" => camera.pitch = number(value).map_or(camera.pitch, |v| v.clamp(-MAX_PITCH, MAX_PITCH)),
+                "mzoom" => camera.zoom = number(value).map_or(camera.zoom, |v| v.clamp(MIN_ZOOM, MAX_ZOOM)),
+                "mproj" => camera.mode = ProjectionMode::from_label(value).unwrap_or(camera.mode),
+                _ => {}
+            }
+        }
+        camera
+    }
+
+    /// `search` with this camera's keys replaced; other parameters are kept
+    fn to_search(self, search: &str) -> String {
+        let mut params: Vec<String> = search
+            .trim_start_matches('?')
+            .split('&')
+            .filter(|kv| !kv.is_empty())
+            .filter(|kv| !VIEW_URL_KEYS.contains(&kv.split('=').next().unwrap_or_default()))
+            .map(String::from)
+            .collect();
+        params.push(format!("mrot={:.3}", self.rotation.rem_euclid(2.0 * PI)));
+        params.push(format!("mpitch={:.3}", self.pitch));
+        params.push(format!("mzoom={:.2}", self.zoom));
+        params.push(format!("mproj={}", self.mode.label()));
+        format!("?{}", params.join("&"))
+    }
+}
+
+/// Camera from the page URL (defaults when the URL doesn't carry one)
+fn camera_from_url() -> ManifoldCamera {
+    web_sys::window()
+        .and_then(|w| w.location().search().ok())
+        .map(|search| ManifoldCamera::from_search(&search))
+        .unwrap_or_default()
+}
+
+/// Write `camera` into the page URL in place (no reload, no new history entry)
+fn write_camera_to_url(camera: ManifoldCamera) {
+    let Some(window) = web_sys::window() else { return };
+    let location = window.location();
+    let (Ok(path), Ok(search)) = (location.pathname(), location.search()) else { return };
+    let hash = location.hash().unwrap_or_default();
+    let url = format!("{}{}{}", path, camera.to_search(&search), hash);
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+}
+
+/// A projected point or crystal queued for back-to-front drawing
+struct RenderItem {
+    px: f64,
+    py: f64,
+    /// Size multiplier (perspective factor, or 1.0 when orthographic)
+    size_scale: f64,
+    /// Sort key, larger = nearer the viewer
+    depth: f64,
+    alpha: f64,
+    /// Fill of a thought point, by cluster or category
+    color: (u8, u8, u8),
+    is_crystal: bool,
+    name: String,
+}
+
+/// How long points glide from their old to their new position (one fetch interval)
+const MANIFOLD_TRANSITION_MS: f64 = 2000.0;
+
+/// Full-size manifold drawing surface in CSS pixels (the backing store is this × devicePixelRatio)
+const MANIFOLD_WIDTH: f64 = 600.0;
+const MANIFOLD_HEIGHT: f64 = 400.0;
+
+/// Manifold drawing size in CSS pixels: the laid-out width up to `MANIFOLD_WIDTH`
+/// (narrower on small screens) at the full-size aspect ratio
+fn manifold_css_size(canvas: &HtmlCanvasElement) -> (f64, f64) {
+    let width = match canvas.client_width() {
+        0 => MANIFOLD_WIDTH,
+        w => (w as f64).min(MANIFOLD_WIDTH),
+    };
+    (width, width * MANIFOLD_HEIGHT / MANIFOLD_WIDTH)
+}
+
+/// Per-frame fade of the previous frame when trails are on (lower = longer trails)
+const TRAIL_FADE_ALPHA: f64 = 0.25;
+
+/// Opacity of a kinship edge between two fully bright points at similarity 1
+const KINSHIP_EDGE_ALPHA: f64 = 0.4;
+
+/// Viewer settings for one manifold frame
+struct ManifoldView<'a> {
+    rotation: f64,
+    pitch: f64,
+    /// Multiplies the fit-to-canvas scale
+    zoom: f64,
+    mode: ProjectionMode,
+    /// Thoughts below this salience are skipped (crystals never are)
+    min_salience: f32,
+    /// Fade the previous frame instead of clearing it
+    trails: bool,
+    /// Draw the kinship graph between thoughts
+    kinship: bool,
+    /// Color thoughts by `category` (see `category_legend`) rather than cluster
+    by_category: bool,
+    /// Id of the pinned thought, drawn highlighted and labelled
+    pinned: Option<&'a str>,
+    /// Point size and glow
+    point_style: PointStyle,
+    /// Density heatmap behind or instead of the thought points
+    density: DensityMode,
+}
+
+/// Where a thought point was drawn, in CSS pixels
+struct HitTarget {
+    id: String,
+    px: f64,
+    py: f64,
+    radius: f64,
+}
+
+/// Radius of a thought point at unit perspective scale (CSS px)
+const THOUGHT_POINT_RADIUS: f64 = 3.0;
+
+/// Pointer travel (CSS px) beyond which a mousedown/up is a drag, not a click
+const CLICK_MAX_TRAVEL: f64 = 4.0;
+
+/// Minimum hit radius so small, distant points stay clickable
+const MIN_HIT_RADIUS: f64 = 6.0;
+
+/// Pinned thought highlight
+const PINNED_COLOR: &str = "#ff4fd8";
+
+/// Nearest thought under (x, y), if any is within its hit radius
+fn hit_test(targets: &[HitTarget], x: f64, y: f64) -> Option<String> {
+    targets
+        .iter()
+        .map(|t| (t, (t.px - x).hypot(t.py - y)))
+        .filter(|(t, d)| *d <= t.radius.max(MIN_HIT_RADIUS))
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(t, _)| t.id.clone())
+}
+
+/// In-flight move from `previous` to the manifold being drawn
+struct Transition<'a> {
+    previous: &'a ManifoldResponse,
+    /// 0.0 = at `previous`, 1.0 = arrived
+    progress: f64,
+}
+
+/// Rotate a manifold point (yaw about Y, then pitch about X) and project it
+/// onto the canvas around (cx, cy).
+///
+/// Returns (px, py, perspective): `perspective = distance / (distance + depth)`
+/// is 1.0 at the origin's depth and shrinks for points further from the
+/// viewer. Screen Y grows downward, so positive `y` lands above `cy`.
+#[allow(clippy::too_many_arguments)]
+fn project_point(
+    x: f64,
+    y: f64,
+    z: f64,
+    yaw: f64,
+    pitch: f64,
+    cx: f64,
+    cy: f64,
+    scale: f64,
+    distance: f64,
+) -> (f64, f64, f64) {
+    // Yaw: rotate around the Y axis
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    let rx = x * cos_yaw - z * sin_yaw;
+    let rz = x * sin_yaw + z * cos_yaw;
+
+    // Pitch: tilt around the X axis
+    let (sin_pitch, cos_pitch) = pitch.sin_cos();
+    let ry = y * cos_pitch - rz * sin_pitch;
+    let rz = y * sin_pitch + rz * cos_pitch;
+
+    let perspective = distance / (distance + rz);
+    let px = cx + rx * scale * perspective;
+    let py = cy - ry * scale * perspective; // Y is inverted in screen coords
+
+    (px, py, perspective)
+}
+
+/// Render the 3D manifold to canvas using 2D context
+///
+/// During a transition, points present in both manifolds glide between their
+/// positions (matched by id), new points fade in and departed ones fade out.
+/// Returns where each current thought was drawn, for hit-testing clicks.
+fn render_manifold(
+    canvas: &HtmlCanvasElement,
+    manifold: &ManifoldResponse,
+    transition: Option<Transition>,
+    view: ManifoldView,
+) -> Vec<HitTarget> {
+    let ManifoldView { rotation, pitch, zoom, mode, min_salience, trails, kinship, by_category, pinned, point_style, density } =
+        view;
+    let radius = THOUGHT_POINT_RADIUS * point_style.size;
+    // Projection math works in CSS pixels so the layout doesn't depend on the screen,
+    // and scales with the canvas so a narrow card shows the same picture smaller
+    let (width, height) = manifold_css_size(canvas);
+    let Some(ctx) = hidpi_context(canvas, width, height) else { return Vec::new() };
+
+    let cx = width / 2.0;
+    let cy = height / 2.0;
+    let scale = 100.0 * width / MANIFOLD_WIDTH * zoom;
+    let distance = 5.0;
+
+    // Clear canvas with dark background (partially, leaving trails)
+    let unconscious = manifold.layer == MemoryLayer::Unconscious;
+    let (r, g, b) = if unconscious { UNCONSCIOUS_BACKGROUND } else { (10, 10, 15) };
+    if trails {
+        ctx.set_fill_style_str(&format!("rgba({}, {}, {}, {})", r, g, b, TRAIL_FADE_ALPHA));
+    } else {
+        ctx.set_fill_style_str(&format!("rgb({}, {}, {})", r, g, b));
+    }
+    ctx.fill_rect(0.0, 0.0, width, height);
+
+    // Helper: project 3D point to 2D with rotation, returning (px, py, size_scale, depth)
+    let project = |x: f64, y: f64, z: f64| -> (f64, f64, f64, f64) {
+        let (px, py, perspective) = project_point(x, y, z, rotation, pitch, cx, cy, scale, distance);
+        // Perspective factor doubles as the depth sort key in both modes
+        match mode {
+            ProjectionMode::Perspective => (px, py, perspective, perspective),
+            // Undo the perspective divide to get the constant-scale position
+            ProjectionMode::Orthographic => (
+                cx + (px - cx) / perspective,
+                cy + (py - cy) / perspective,
+                1.0,
+                perspective,
+            ),
+        }
+    };
+
+    // Draw grid for reference (faint)
+    ctx.set_stroke_style_str("rgba(50, 50, 70, 0.3)");
+    ctx.set_line_width(0.5);
+    for i in -2..=2 {
+        let y = i as f64 * 0.5;
+        ctx.begin_path();
+        let (x1, y1, _, _) = project(-2.0, y, 0.0);
+        let (x2, y2, _, _) = project(2.0, y, 0.0);
+        ctx.move_to(x1, y1);
+        ctx.line_to(x2, y2);
+        ctx.stroke();
+    }
+
+    // Collect all points with their projected depth for z-sorting
+    let mut render_items: Vec<RenderItem> = Vec::new();
+
+    // Smoothstep so motion eases in and out
+    let t = transition.as_ref().map(|tr| tr.progress).unwrap_or(1.0);
+    let eased = t * t * (3.0 - 2.0 * t);
+    let origins: std::collections::HashMap<&str, &ManifoldPoint> = transition
+        .as_ref()
+        .filter(|_| t < 1.0)
+        .map(|tr| tr.previous.points.iter().map(|p| (p.id.as_str(), p)).collect())
+        .unwrap_or_default();
+
+    // Same legend as the card shows; departing points whose category left get the neutral color
+    let legend = if by_category { category_legend(&manifold.points) } else { Vec::new() };
+
+    let mut push_point = |point: &ManifoldPoint, x: f64, y: f64, z: f64, fade: f64| {
+        let (px, py, size_scale, depth) = project(x, y, z);
+        let freshness = age_freshness(point.age_ms);
+        let alpha = (point.salience as f64).clamp(0.2, 1.0) * freshness * fade;
+        let color = if by_category {
+            category_color(&legend, point.category.as_deref())
+        } else {
+            cluster_color(point.cluster)
+        };
+        render_items.push(RenderItem {
+            px,
+            py,
+            size_scale,
+            depth,
+            alpha: if unconscious { alpha * UNCONSCIOUS_DIM } else { alpha },
+            color: if unconscious { unconscious_tint(color) } else { color },
+            is_crystal: false,
+            name: point.id.clone(),
+        });
+    };
+
+    // Add thought points (crystals below are never filtered)
+    for point in manifold.points.iter().filter(|p| p.salience >= min_salience) {
+        let lerp = |from: f32, to: f32| from as f64 + (to as f64 - from as f64) * eased;
+        match origins.get(point.id.as_str()) {
+            Some(from) => push_point(point, lerp(from.x, point.x), lerp(from.y, point.y), lerp(from.z, point.z), 1.0),
+            None if origins.is_empty() => push_point(point, point.x as f64, point.y as f64, point.z as f64, 1.0),
+            None => push_point(point, point.x as f64, point.y as f64, point.z as f64, eased),
+        }
+    }
+
+    // Departed points fade out where they were
+    let current: std::collections::HashSet<&str> = manifold.points.iter().map(|p| p.id.as_str()).collect();
+    if !origins.is_empty() {
+        for &point in origins.values().filter(|p| p.salience >= min_salience && !current.contains(p.id.as_str())) {
+            push_point(point, point.x as f64, point.y as f64, point.z as f64, 1.0 - eased);
+        }
+    }
+
+    // Add law crystals
+    for crystal in &manifold.crystals {
+        let (px, py, size_scale, depth) = project(crystal.x as f64, crystal.y as f64, crystal.z as f64);
+        render_items.push(RenderItem {
+            px,
+            py,
+            size_scale,
+            depth,
+            alpha: 1.0,
+            color: CRYSTAL_COLOR,
+            is_crystal: true,
+            name: crystal.name.clone(),
+        });
+    }
+
+    // Current thoughts are clickable; crystals and departing points aren't
+    let hit_targets: Vec<HitTarget> = render_items
+        .iter()
+        .filter(|item| !item.is_crystal && current.contains(item.name.as_str()))
+        .map(|item| HitTarget {
+            id: item.name.clone(),
+            px: item.px,
+            py: item.py,
+            radius: radius * item.size_scale,
+        })
+        .collect();
+
+    // Density under everything else, binned from this frame's screen positions
+    // and weighted by brightness so fading points count for less
+    if density != DensityMode::Points {
+        let thoughts = render_items.iter().filter(|item| !item.is_crystal).map(|item| (item.px, item.py, item.alpha));
+        let grid = density_grid(thoughts, width, height);
+        if draw_density(&ctx, &grid, density.peak_alpha()).is_none() {
+            log("Density heatmap could not be drawn");
+        }
+    }
+
+    // Kinship edges under the points, as bright as their dimmer end (so they
+    // fade in and out with it); edges to hidden or departed points are skipped
+    if kinship {
+        let drawn: std::collections::HashMap<&str, (f64, f64, f64)> = render_items
+            .iter()
+            .filter(|item| !item.is_crystal && current.contains(item.name.as_str()))
+            .map(|item| (item.name.as_str(), (item.px, item.py, item.alpha)))
+            .collect();
+        ctx.set_line_width(0.75);
+        for edge in &manifold.edges {
+            let (Some(&(x1, y1, a1)), Some(&(x2, y2, a2))) =
+                (drawn.get(edge.source.as_str()), drawn.get(edge.target.as_str()))
+            else {
+                continue;
+            };
+            let alpha = KINSHIP_EDGE_ALPHA * edge.similarity as f64 * a1.min(a2);
+            ctx.set_stroke_style_str(&format!("rgba(140, 200, 255, {})", alpha));
+            ctx.begin_path();
+            ctx.move_to(x1, y1);
+            ctx.line_to(x2, y2);
+            ctx.stroke();
+        }
+    }
+
+    // Sort by depth (back to front)
+    render_items.sort_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Render points
+    for RenderItem { px, py, size_scale, alpha, color, is_crystal, name, .. } in render_items {
+        if is_crystal {
+            // Law crystals: gold stars
+            let size = 8.0 * size_scale;
+            ctx.set_fill_style_str("#ffd700");
+            ctx.set_shadow_blur(15.0 * point_style.glow);
+            ctx.set_shadow_color("#ffd700");
+            draw_star(&ctx, px, py, size);
+
+            // Label
+            ctx.set_shadow_blur(0.0);
+            ctx.set_fill_style_str("rgba(255, 215, 0, 0.8)");
+            ctx.set_font("10px monospace");
+            let _ = ctx.fill_text(&name, px + size + 5.0, py + 3.0);
+        } else if pinned == Some(name.as_str()) {
+            // Pinned thought: larger, distinct color, ringed and labelled
+            let size = radius * 2.0 * size_scale;
+            ctx.set_fill_style_str(PINNED_COLOR);
+            ctx.set_shadow_blur(20.0 * point_style.glow);
+            ctx.set_shadow_color(PINNED_COLOR);
+            ctx.begin_path();
+            let _ = ctx.arc(px, py, size, 0.0, PI * 2.0);
+            ctx.fill();
+
+            ctx.set_shadow_blur(0.0);
+            ctx.set_stroke_style_str(PINNED_COLOR);
+            ctx.set_line_width(1.0);
+            ctx.begin_path();
+            let _ = ctx.arc(px, py, size + 4.0, 0.0, PI * 2.0);
+            ctx.stroke();
+
+            ctx.set_font("10px monospace");
+            let label: String = name.chars().take(8).collect();
+            let _ = ctx.fill_text(&format!("📌 {}", label), px + size + 6.0, py + 3.0);
+        } else if density == DensityMode::Heatmap {
+            // The heatmap stands in for them
+            continue;
+        } else {
+            // Thought points: glowing circles colored by cluster or category, dimming with age
+            let size = radius * size_scale;
+            let (r, g, b) = color;
+            let color = format!("rgba({}, {}, {}, {})", r, g, b, alpha);
+            ctx.set_fill_style_str(&color);
+            // Without glow the blur stays 0 and the browser skips the shadow pass
+            if point_style.glow > 0.0 {
+                ctx.set_shadow_blur(10.0 * alpha * point_style.glow);
+                ctx.set_shadow_color(&format!("rgb({}, {}, {})", r, g, b));
+            }
+
+            ctx.begin_path();
+            let _ = ctx.arc(px, py, size, 0.0, PI * 2.0);
+            ctx.fill();
+        }
+    }
+
+    // Reset shadow
+    ctx.set_shadow_blur(0.0);
+
+    hit_targets
+}
+
+/// Projected points binned onto a `DENSITY_CELL_PX` grid and smoothed,
+/// scaled so the densest cell is 1.0 (row-major, `cols` × `rows`)
+struct DensityGrid {
+    cols: usize,
+    rows: usize,
+    cells: Vec<f64>,
+}
+
+/// Bin (px, py, weight) screen positions and blur the counts into a field
+fn density_grid(points: impl Iterator<Item = (f64, f64, f64)>, width: f64, height: f64) -> DensityGrid {
+    let cols = (width / DENSITY_CELL_PX).ceil().max(1.0) as usize;
+    let rows = (height / DENSITY_CELL_PX).ceil().max(1.0) as usize;
+    let mut cells = vec![0.0; cols * rows];
+    for (px, py, weight) in points {
+        // Off-canvas (zoomed in) points don't pile up on the edges; NaN fails too
+        if !(px >= 0.0 && px < width && py >= 0.0 && py < height) {
+            continue;
+        }
+        let col = ((px / DENSITY_CELL_PX) as usize).min(cols - 1);
+        let row = ((py / DENSITY_CELL_PX) as usize).min(rows - 1);
+        cells[row * cols + col] += weight;
+    }
+
+    // Separable binomial blur; beyond the edges counts as empty
+    const KERNEL: [f64; 5] = [1.0 / 16.0, 4.0 / 16.0, 6.0 / 16.0, 4.0 / 16.0, 1.0 / 16.0];
+    let mut blurred = vec![0.0; cells.len()];
+    for _ in 0..DENSITY_BLUR_PASSES {
+        for (dc, dr) in [(1, 0), (0, 1)] {
+            for row in 0..rows {
+                for col in 0..cols {
+                    blurred[row * cols + col] = KERNEL
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(k, w)| {
+                            let offset = k as isize - 2;
+                            let c = col.checked_add_signed(offset * dc)?;
+                            let r = row.checked_add_signed(offset * dr)?;
+                            (c < cols && r < rows).then(|| w * cells[r * cols + c])
+                        })
+                        .sum();
+                }
+            }
+            std::mem::swap(&mut cells, &mut blurred);
+        }
+    }
+
+    let densest = cells.iter().copied().fold(0.0, f64::max);
+    if densest > 0.0 {
+        cells.iter_mut().for_each(|c| *c /= densest);
+    }
+    DensityGrid { cols, rows, cells }
+}
+
+/// Heatmap color for a density in 0..=1, interpolated along `DENSITY_RAMP`
+fn heat_color(density: f64) -> (u8, u8, u8) {
+    let density = density.clamp(0.0, 1.0);
+    let upper = DENSITY_RAMP.iter().position(|&(at, _)| at >= density).unwrap_or(DENSITY_RAMP.len() - 1).max(1);
+    let (lo_at, (r0, g0, b0)) = DENSITY_RAMP[upper - 1];
+    let (hi_at, (r1, g1, b1)) = DENSITY_RAMP[upper];
+    let t = (density - lo_at) / (hi_at - lo_at);
+    let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+    (mix(r0, r1), mix(g0, g1), mix(b0, b1))
+}
+
+/// Paint the grid one pixel per cell on a scratch canvas and stretch it over
+/// `ctx`; the browser's image smoothing turns the cells into a smooth field
+fn draw_density(ctx: &CanvasRenderingContext2d, grid: &DensityGrid, peak_alpha: f64) -> Option<()> {
+    let rgba: Vec<u8> = grid
+        .cells
+        .iter()
+        .flat_map(|&density| {
+            let (r, g, b) = heat_color(density);
+            [r, g, b, (density * peak_alpha * 255.0).round() as u8]
+        })
+        .collect();
+    let image =
+        web_sys::ImageData::new_with_u8_clamped_array_and_sh(wasm_bindgen::Clamped(&rgba[..]), grid.cols as u32, grid.rows as u32)
+            .ok()?;
+    let scratch = document().create_element("canvas").ok()?.dyn_into::<HtmlCanvasElement>().ok()?;
+    scratch.set_width(grid.cols as u32);
+    scratch.set_height(grid.rows as u32);
+    let scratch_ctx = scratch.get_context("2d").ok().flatten()?.dyn_into::<CanvasRenderingContext2d>().ok()?;
+    scratch_ctx.put_image_data(&image, 0.0, 0.0).ok()?;
+
+    ctx.set_image_smoothing_enabled(true);
+    ctx.draw_image_with_html_canvas_element_and_dw_and_dh(
+        &scratch,
+        0.0,
+        0.0,
+        grid.cols as f64 * DENSITY_CELL_PX,
+        grid.rows as f64 * DENSITY_CELL_PX,
+    )
+    .ok()
+}
+
+/// Age at which a thought point is drawn at half brightness
+const AGE_HALF_LIFE_MS: f64 = 10.0 * 60.0 * 1000.0;
+
+/// Floor so even ancient points stay faintly visible
+const MIN_FRESHNESS: f64 = 0.15;
+
+/// Exponential decay of a point's brightness with age (1.0 = brand new)
+fn age_freshness(age_ms: u64) -> f64 {
+    0.5f64
+        .powf(age_ms as f64 / AGE_HALF_LIFE_MS)
+        .max(MIN_FRESHNESS)
+}
+
+/// Categorical palette for manifold clusters (cluster 0 = largest, keeps the classic cyan)
+const CLUSTER_PALETTE: [(u8, u8, u8); 10] = [
+    (0, 255, 255),
+    (255, 99, 132),
+    (120, 255, 120),
+    (255, 170, 60),
+    (170, 120, 255),
+    (255, 235, 90),
+    (80, 160, 255),
+    (255, 120, 220),
+    (160, 220, 200),
+    (200, 200, 200),
+];
+
+fn cluster_color(cluster: u8) -> (u8, u8, u8) {
+    CLUSTER_PALETTE[cluster as usize % CLUSTER_PALETTE.len()]
+}
+
+/// The unconscious layer is drawn dimmer, on a darker violet ground, with
+/// every color pulled this far toward `UNCONSCIOUS_HUE`
+const UNCONSCIOUS_HUE: (u8, u8, u8) = (140, 110, 230);
+const UNCONSCIOUS_HUE_MIX: f64 = 0.5;
+const UNCONSCIOUS_DIM: f64 = 0.6;
+const UNCONSCIOUS_BACKGROUND: (u8, u8, u8) = (12, 9, 22);
+
+fn unconscious_tint((r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+    let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * UNCONSCIOUS_HUE_MIX).round() as u8;
+    let (hr, hg, hb) = UNCONSCIOUS_HUE;
+    (mix(r, hr), mix(g, hg), mix(b, hb))
+}
+
+/// Law crystal gold
+const CRYSTAL_COLOR: (u8, u8, u8) = (255, 215, 0);
+
+/// Thoughts with no category, or one past the palette, in color-by-category mode
+const UNCATEGORIZED_COLOR: (u8, u8, u8) = (110, 110, 125);
+
+/// One line of the color-by-category legend
+#[derive(Debug, Clone, PartialEq)]
+struct CategoryEntry {
+    /// None = the neutral "uncategorized" entry
+    category: Option<String>,
+    color: (u8, u8, u8),
+    count: usize,
+}
+
+impl CategoryEntry {
+    fn label(&self) -> &str {
+        self.category.as_deref().unwrap_or("uncategorized")
+    }
+}
+
+/// Legend for coloring `points` by category: the most common categories (ties
+/// by name) take the palette in order; points without a category, or in one
+/// the palette has run out for, share a neutral entry listed last
+fn category_legend(points: &[ManifoldPoint]) -> Vec<CategoryEntry> {
+    let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    let mut uncategorized = 0;
+    for point in points {
+        match point.category.as_deref() {
+            Some(category) => *counts.entry(category).or_default() += 1,
+            None => uncategorized += 1,
+        }
+    }
+    let mut ranked: Vec<(&str, usize)> = counts.into_iter().collect();
+    // Stable sort keeps the map's name order among equal counts
+    ranked.sort_by(|a, b| b.1.cmp(&a.1));
+    uncategorized += ranked.iter().skip(CLUSTER_PALETTE.len()).map(|(_, n)| n).sum::<usize>();
+
+    let mut legend: Vec<CategoryEntry> = ranked
+        .into_iter()
+        .zip(CLUSTER_PALETTE)
+        .map(|((category, count), color)| CategoryEntry { category: Some(category.to_string()), color, count })
+        .collect();
+    if uncategorized > 0 {
+        legend.push(CategoryEntry { category: None, color: UNCATEGORIZED_COLOR, count: uncategorized });
+    }
+    legend
+}
+
+/// Color of `category` under `legend`, neutral if it has no entry of its own
+fn category_color(legend: &[CategoryEntry], category: Option<&str>) -> (u8, u8, u8) {
+    legend
+        .iter()
+        .find(|entry| entry.category.is_some() && entry.category.as_deref() == category)
+        .map_or(UNCATEGORIZED_COLOR, |entry| entry.color)
+}
+
+/// Size the backing store to `css_width × css_height` at the device pixel ratio and
+/// scale the context so drawing code works in CSS pixels (crisp on hi-DPI screens)
+fn hidpi_context(canvas: &HtmlCanvasElement, css_width: f64, css_height: f64) -> Option<CanvasRenderingContext2d> {
+    let ratio = window().device_pixel_ratio().max(1.0);
+    let width = (css_width * ratio).round() as u32;
+    let height = (css_height * ratio).round() as u32;
+    // Resizing clears the canvas (and its trails), so only do it when the size changes
+    if canvas.width() != width || canvas.height() != height {
+        canvas.set_width(width);
+        canvas.set_height(height);
+    }
+
+    let ctx = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|c| c.dyn_into::<CanvasRenderingContext2d>().ok())?;
+    ctx.set_transform(ratio, 0.0, 0.0, ratio, 0.0, 0.0).ok()?;
+    Some(ctx)
+}
+
+/// Laid-out size of a stylesheet-sized canvas in CSS pixels
+fn css_size(canvas: &HtmlCanvasElement) -> (f64, f64) {
+    (canvas.client_width().max(1) as f64, canvas.client_height().max(1) as f64)
+}
+
+/// Draw a line sparkline scaled to the min/max of `values`
+fn draw_sparkline(canvas: &HtmlCanvasElement, values: &[f32], color: &str) {
+    draw_sparkline_with_reference(canvas, values, &[], color);
+}
+
+/// Draw `values` over a faint `reference` series, both scaled to their combined
+/// min/max so levels compare; each series spans the full width
+fn draw_sparkline_with_reference(canvas: &HtmlCanvasElement, values: &[f32], reference: &[f32], color: &str) {
+    let (width, height) = css_size(canvas);
+    let Some(ctx) = hidpi_context(canvas, width, height) else { return };
+    ctx.clear_rect(0.0, 0.0, width, height);
+
+    let both = || values.iter().chain(reference).cloned();
+    let min = both().fold(f32::INFINITY, f32::min) as f64;
+    let max = both().fold(f32::NEG_INFINITY, f32::max) as f64;
+    let range = max - min;
+
+    ctx.set_stroke_style_str(color);
+    ctx.set_line_width(1.0);
+    let trace = |series: &[f32]| {
+        if series.len() < 2 {
+            return;
+        }
+        let step = width / (series.len() - 1) as f64;
+        ctx.begin_path();
+        for (i, v) in series.iter().enumerate() {
+            let x = i as f64 * step;
+            // Flat series sit on the midline instead of dividing by zero
+            let t = if range > f64::EPSILON {
+                (*v as f64 - min) / range
+            } else {
+                0.5
+            };
+            let y = height - 1.0 - t * (height - 2.0);
+            if i == 0 {
+                ctx.move_to(x, y);
+            } else {
+                ctx.line_to(x, y);
+            }
+        }
+        ctx.stroke();
+    };
+
+    ctx.set_global_alpha(0.35);
+    trace(reference);
+    ctx.set_global_alpha(1.0);
+    trace(values);
+}
+
+/// Draw an activity series on a fixed 0-1 scale with quarter gridlines, so
+/// stages compare by height rather than each filling the chart
+fn draw_activity_chart(canvas: &HtmlCanvasElement, values: &[f32], color: &str) {
+    let (width, height) = css_size(canvas);
+    let Some(ctx) = hidpi_context(canvas, width, height) else { return };
+    ctx.clear_rect(0.0, 0.0, width, height);
+
+    ctx.set_stroke_style_str("rgba(255, 255, 255, 0.1)");
+    ctx.set_line_width(1.0);
+    for quarter in 1..4 {
+        let y = height - height * quarter as f64 / 4.0;
+        ctx.begin_path();
+        ctx.move_to(0.0, y);
+        ctx.line_to(width, y);
+        ctx.stroke();
+    }
+
+    if values.len() < 2 {
+        return;
+    }
+
+    let step = width / (values.len() - 1) as f64;
+    let y_of = |v: f32| height - 1.0 - (v.clamp(0.0, 1.0) as f64) * (height - 2.0);
+    ctx.set_stroke_style_str(color);
+    ctx.set_line_width(1.5);
+    ctx.begin_path();
+    for (i, v) in values.iter().enumerate() {
+        if i == 0 {
+            ctx.move_to(0.0, y_of(*v));
+        } else {
+            ctx.line_to(i as f64 * step, y_of(*v));
+        }
+    }
+    ctx.stroke();
+}
+
+/// Burst ratio drawn at half a radar spoke; it has no upper bound, so higher
+/// ratios approach the rim without reaching it
+const BURST_RATIO_HALF_SPOKE: f32 = 1.0;
+
+/// Fractality components as 0-1 radar spoke lengths, clockwise from the top.
+/// The two σ share a scale (the larger reaches the rim), so their spokes show
+/// the current inter-arrival spread against the boot baseline.
+fn fractality_spokes(f: &FractalityMetrics) -> [(&'static str, f32); 4] {
+    let finite = |v: f32| if v.is_finite() { v.max(0.0) } else { 0.0 };
+    let (inter, boot) = (finite(f.inter_arrival_sigma), finite(f.boot_sigma));
+    let sigma_scale = inter.max(boot);
+    let sigma = |v: f32| if sigma_scale > 0.0 { v / sigma_scale } else { 0.0 };
+    let burst = finite(f.burst_ratio);
+    [
+        ("score", finite(f.score).min(1.0)),
+        ("inter-arrival σ", sigma(inter)),
+        ("burst", burst / (burst + BURST_RATIO_HALF_SPOKE)),
+        ("boot σ", sigma(boot)),
+    ]
+}
+
+/// Radar chart of `spokes` (0-1 lengths) with half and full rings and spoke labels
+fn draw_fractality_radar(canvas: &HtmlCanvasElement, spokes: &[(&str, f32)]) {
+    let (width, height) = css_size(canvas);
+    let Some(ctx) = hidpi_context(canvas, width, height) else { return };
+    ctx.clear_rect(0.0, 0.0, width, height);
+    if spokes.is_empty() {
+        return;
+    }
+
+    let (cx, cy) = (width / 2.0, height / 2.0);
+    // Leave room for the labels above and below the vertical spokes
+    let radius = (height / 2.0 - 14.0).max(0.0);
+    let tip = |i: usize, length: f64| {
+        let angle = i as f64 * 2.0 * PI / spokes.len() as f64 - PI / 2.0;
+        (cx + radius * length * angle.cos(), cy + radius * length * angle.sin())
+    };
+    let trace = |lengths: &dyn Fn(usize) -> f64| {
+        ctx.begin_path();
+        for i in 0..spokes.len() {
+            let (x, y) = tip(i, lengths(i));
+            if i == 0 {
+                ctx.move_to(x, y);
+            } else {
+                ctx.line_to(x, y);
+            }
+        }
+        ctx.close_path();
+    };
+
+    ctx.set_stroke_style_str("rgba(255, 255, 255, 0.1)");
+    ctx.set_line_width(1.0);
+    for ring in [0.5, 1.0] {
+        trace(&|_| ring);
+        ctx.stroke();
+    }
+    for i in 0..spokes.len() {
+        let (x, y) = tip(i, 1.0);
+        ctx.begin_path();
+        ctx.move_to(cx, cy);
+        ctx.line_to(x, y);
+        ctx.stroke();
+    }
+
+    trace(&|i| spokes[i].1.clamp(0.0, 1.0) as f64);
+    ctx.set_fill_style_str("rgba(230, 126, 34, 0.35)");
+    ctx.fill();
+    ctx.set_stroke_style_str("#e67e22");
+    ctx.set_line_width(1.5);
+    ctx.stroke();
+
+    ctx.set_fill_style_str("rgba(255, 255, 255, 0.6)");
+    ctx.set_font("10px monospace");
+    ctx.set_text_baseline("middle");
+    for (i, (label, _)) in spokes.iter().enumerate() {
+        let (x, y) = tip(i, 1.0);
+        let (align, dx, dy) = match x - cx {
+            d if d > 1.0 => ("left", 4.0, 0.0),
+            d if d < -1.0 => ("right", -4.0, 0.0),
+            _ if y < cy => ("center", 0.0, -7.0),
+            _ => ("center", 0.0, 7.0),
+        };
+        ctx.set_text_align(align);
+        let _ = ctx.fill_text(label, x + dx, y + dy);
+    }
+}
+
+/// One cell per sample: hue from valence (red -1 → green +1), brightness from arousal,
+/// dim grey where no thought was available
+fn draw_mood_ribbon(canvas: &HtmlCanvasElement, samples: &[MoodSample]) {
+    let (width, height) = css_size(canvas);
+    let Some(ctx) = hidpi_context(canvas, width, height) else { return };
+    ctx.clear_rect(0.0, 0.0, width, height);
+
+    if samples.is_empty() {
+        return;
+    }
+
+    let cell = width / samples.len() as f64;
+    for (i, sample) in samples.iter().enumerate() {
+        let color = match sample.valence {
+            Some(v) => {
+                let hue = ((v.clamp(-1.0, 1.0) + 1.0) * 60.0) as f64; // 0 = red, 120 = green
+                let lightness = 25.0 + 35.0 * sample.arousal.unwrap_or(0.5).clamp(0.0, 1.0) as f64;
+                format!("hsl({:.0}, 80%, {:.0}%)", hue, lightness)
+            }
+            None => "rgba(255, 255, 255, 0.08)".to_string(),
+        };
+        ctx.set_fill_style_str(&color);
+        // Overlap by a pixel so rounding doesn't leave seams between cells
+        ctx.fill_rect(i as f64 * cell, 0.0, cell + 1.0, height);
+    }
+}
+
+/// Draw a 5-pointed star
+fn draw_star(ctx: &CanvasRenderingContext2d, cx: f64, cy: f64, size: f64) {
+    ctx.begin_path();
+    for i in 0..5 {
+        let angle = (i as f64) * 2.0 * PI / 5.0 - PI / 2.0;
+        let x = cx + size * angle.cos();
+        let y = cy + size * angle.sin();
+        if i == 0 {
+            ctx.move_to(x, y);
+        } else {
+            ctx.line_to(x, y);
+        }
+
+        // Inner point
+        let inner_angle = angle + PI / 5.0;
+        let inner_r = size * 0.4;
+        let ix = cx + inner_r * inner_angle.cos();
+        let iy = cy + inner_r * inner_angle.sin();
+        ctx.line_to(ix, iy);
+    }
+    ctx.close_path();
+    ctx.fill();
+}
+
+/// Height of the legend strip composited under exported manifold images (CSS px)
+const EXPORT_LEGEND_HEIGHT: f64 = 28.0;
+
+/// Download the rendered manifold frame as a PNG with the legend baked in underneath.
+/// Copies the canvas backing store, so the image is at full device-pixel resolution.
+fn download_manifold_png(canvas: &HtmlCanvasElement, manifold: &ManifoldResponse, by_category: bool) -> Result<(), JsValue> {
+    let export = document().create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+    // Backing-store pixels per drawing unit, so the legend matches the frame's resolution
+    let ratio = canvas.width() as f64 / manifold_css_size(canvas).0;
+    let legend_height = (EXPORT_LEGEND_HEIGHT * ratio).round() as u32;
+    export.set_width(canvas.width());
+    export.set_height(canvas.height() + legend_height);
+
+    let ctx = export
+        .get_context("2d")?
+        .ok_or("no 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+    ctx.set_fill_style_str("#0a0a0f");
+    ctx.fill_rect(0.0, 0.0, export.width() as f64, export.height() as f64);
+    ctx.draw_image_with_html_canvas_element(canvas, 0.0, 0.0)?;
+
+    // Legend: crystals, then one swatch per cluster (largest first) or per category
+    let y = canvas.height() as f64 + legend_height as f64 / 2.0;
+    let gap = 12.0 * ratio;
+    let mut x = gap;
+    ctx.set_font(&format!("{:.0}px monospace", 11.0 * ratio));
+    ctx.set_text_baseline("middle");
+    let mut entry = |label: &str, color: &str| -> Result<(), JsValue> {
+        ctx.set_fill_style_str(color);
+        ctx.fill_text(label, x, y)?;
+        x += ctx.measure_text(label)?.width() + gap;
+        Ok(())
+    };
+    entry("★ Law Crystals", "#ffd700")?;
+    if by_category {
+        for c in category_legend(&manifold.points) {
+            let (r, g, b) = c.color;
+            entry(&format!("● {} ({})", c.label(), c.count), &format!("rgb({}, {}, {})", r, g, b))?;
+        }
+    } else {
+        for c in &manifold.centroids {
+            let (r, g, b) = cluster_color(c.cluster);
+            entry(&format!("● {} ({})", c.cluster + 1, c.size), &format!("rgb({}, {}, {})", r, g, b))?;
+        }
+    }
+
+    let link = document().create_element("a")?.dyn_into::<web_sys::HtmlAnchorElement>()?;
+    link.set_href(&export.to_data_url_with_type("image/png")?);
+    link.set_download(&format!("manifold-{}.png", Utc::now().format("%Y%m%d-%H%M%S")));
+    link.click();
+    Ok(())
+}
+
+/// Fetch manifold data from backend
+async fn fetch_manifold(instance: &str, layer: MemoryLayer) -> Result<ManifoldResponse, ()> {
+    let url = api_url(&with_instance(&layer.scoped("/vectors"), instance))?;
+
+    let resp = reqwasm::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|_| ())?;
+    if !resp.ok() {
+        return Err(());
+    }
+
+    resp.json::<ManifoldResponse>().await.map_err(|_| ())
+}
+
+/// Fetch `metric`'s comparison window from /history
+async fn fetch_past_window(metric: &str, instance: &str) -> Result<PastWindow, ()> {
+    let path = format!(
+        "/history?metric={}&ago_secs={}&window_secs={}",
+        metric, COMPARE_AGO_SECS, COMPARE_WINDOW_SECS
+    );
+    let url = api_url(&with_instance(&path, instance))?;
+
+    let resp = reqwasm::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|_| ())?;
+    if !resp.ok() {
+        return Err(());
+    }
+
+    resp.json::<PastWindow>().await.map_err(|_| ())
+}
+
+/// When each upstream last answered the dashboard
+async fn fetch_pipeline(instance: &str) -> Result<PipelineHealth, ()> {
+    let url = api_url(&with_instance("/pipeline", instance))?;
+
+    let resp = reqwasm::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|_| ())?;
+    if !resp.ok() {
+        return Err(());
+    }
+
+    resp.json::<PipelineHealth>().await.map_err(|_| ())
+}
+
+/// The backend's local philosophy quotes
+async fn fetch_quotes(instance: &str) -> Result<QuotesResponse, ()> {
+    let url = api_url(&with_instance("/quotes", instance))?;
+
+    let resp = reqwasm::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|_| ())?;
+    if !resp.ok() {
+        return Err(());
+    }
+
+    resp.json::<QuotesResponse>().await.map_err(|_| ())
+}
+
+/// Fetch one memory's full content for the pinned-thought panel
+async fn fetch_thought_detail(id: &str, instance: &str, layer: MemoryLayer) -> Result<ThoughtDetail, ()> {
+    let path = layer.scoped(&format!("/thought/{}", encode_query(id)));
+    let url = api_url(&with_instance(&path, instance))?;
+
+    let resp = reqwasm::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|_| ())?;
+    if !resp.ok() {
+        return Err(());
+    }
+
+    resp.json::<ThoughtDetail>().await.map_err(|_| ())
+}
+
+/// Subscribe to /vectors/stream: `snapshot` events replace the point set,
+/// `delta` events patch it in place, `unavailable` flags an upstream failure
+fn subscribe_manifold(
+    set_manifold: WriteSignal<ManifoldResponse>,
+    set_unavailable: WriteSignal<bool>,
+    instance: &str,
+    layer: MemoryLayer,
+) -> Option<web_sys::EventSource> {
+    let url = api_url(&with_instance(&layer.scoped("/vectors/stream"), instance)).ok()?;
+    let source = web_sys::EventSource::new(&url).ok()?;
+
+    let on_snapshot = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |e: web_sys::MessageEvent| {
+        if let Some(snapshot) = e
+            .data()
+            .as_string()
+            .and_then(|data| serde_json::from_str::<ManifoldResponse>(&data).ok())
+        {
+            set_unavailable.set(false);
+            set_manifold.set(snapshot);
+        }
+    });
+    let on_delta = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |e: web_sys::MessageEvent| {
+        if let Some(delta) = e
+            .data()
+            .as_string()
+            .and_then(|data| serde_json::from_str::<ManifoldDelta>(&data).ok())
+        {
+            set_unavailable.set(false);
+            set_manifold.update(|m| apply_manifold_delta(m, delta));
+        }
+    });
+    let on_unavailable = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |_: web_sys::MessageEvent| {
+        set_unavailable.set(true);
+    });
+
+    source
+        .add_event_listener_with_callback("snapshot", on_snapshot.as_ref().unchecked_ref())
+        .ok()?;
+    source
+        .add_event_listener_with_callback("delta", on_delta.as_ref().unchecked_ref())
+        .ok()?;
+    source
+        .add_event_listener_with_callback("unavailable", on_unavailable.as_ref().unchecked_ref())
+        .ok()?;
+    // Listeners live as long as the page; the EventSource is closed on cleanup
+    on_snapshot.forget();
+    on_delta.forget();
+    on_unavailable.forget();
 
-    let Some(ctx) = ctx else { return };
+    Some(source)
+}
 
-    let width = canvas.width() as f64;
-    let height = canvas.height() as f64;
-    let cx = width / 2.0;
-    let cy = height / 2.0;
-    let scale = 100.0;
-    let distance = 5.0;
+/// Resolve on the browser's next animation frame
+async fn next_animation_frame() {
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    request_animation_frame(move || {
+        let _ = tx.send(());
+    });
+    let _ = rx.await;
+}
 
-    // Clear canvas with dark background
-    ctx.set_fill_style_str("#0a0a0f");
-    ctx.fill_rect(0.0, 0.0, width, height);
+/// Wall-clock milliseconds, for animation timing
+fn now_ms() -> f64 {
+    Utc::now().timestamp_millis() as f64
+}
 
-    // Helper: project 3D point to 2D with rotation and perspective
-    let project = |x: f64, y: f64, z: f64| -> (f64, f64, f64) {
-        // Rotate around Y axis
-        let cos_r = rotation.cos();
-        let sin_r = rotation.sin();
-        let rx = x * cos_r - z * sin_r;
-        let rz = x * sin_r + z * cos_r;
+/// Sub-millisecond monotonic clock for timing (falls back to the wall clock)
+fn precise_now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .map(|p| p.now())
+        .unwrap_or_else(now_ms)
+}
 
-        // Perspective projection
-        let perspective = distance / (distance + rz);
-        let px = cx + rx * scale * perspective;
-        let py = cy - y * scale * perspective; // Y is inverted in screen coords
+/// Below this width the dashboard switches to the compact layout
+const COMPACT_MEDIA_QUERY: &str = "(max-width: 700px)";
 
-        (px, py, perspective)
+/// Mirror a CSS media query into a signal, now and on every change
+fn watch_media_query(query: &str, set_matches: WriteSignal<bool>) {
+    let Some(list) = web_sys::window().and_then(|w| w.match_media(query).ok().flatten()) else {
+        return;
     };
+    set_matches.set(list.matches());
 
-    // Draw grid for reference (faint)
-    ctx.set_stroke_style_str("rgba(50, 50, 70, 0.3)");
-    ctx.set_line_width(0.5);
-    for i in -2..=2 {
-        let y = i as f64 * 0.5;
-        ctx.begin_path();
-        let (x1, y1, _) = project(-2.0, y, 0.0);
-        let (x2, y2, _) = project(2.0, y, 0.0);
-        ctx.move_to(x1, y1);
-        ctx.line_to(x2, y2);
-        ctx.stroke();
-    }
+    let watched = list.clone();
+    let on_change = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+        set_matches.set(watched.matches());
+    });
+    let _ = list.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+    // Lives as long as the page
+    on_change.forget();
+}
 
-    // Collect all points with their projected depth for z-sorting
-    let mut render_items: Vec<(f64, f64, f64, f64, bool, String)> = Vec::new();
+/// Mirror the Page Visibility API into a signal (true while the tab is visible)
+fn watch_page_visibility(set_visible: WriteSignal<bool>) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    set_visible.set(!document.hidden());
 
-    // Add thought points
-    for point in &manifold.points {
-        let (px, py, depth) = project(point.x as f64, point.y as f64, point.z as f64);
-        let alpha = (point.salience as f64).clamp(0.2, 1.0);
-        render_items.push((px, py, depth, alpha, false, point.id.clone()));
-    }
+    let watched = document.clone();
+    let on_change = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+        set_visible.set(!watched.hidden());
+    });
+    let _ = document.add_event_listener_with_callback("visibilitychange", on_change.as_ref().unchecked_ref());
+    // Lives as long as the page
+    on_change.forget();
+}
 
-    // Add law crystals
-    for crystal in &manifold.crystals {
-        let (px, py, depth) = project(crystal.x as f64, crystal.y as f64, crystal.z as f64);
-        render_items.push((px, py, depth, 1.0, true, crystal.name.clone()));
-    }
+/// Value of `key` in the page URL's query string
+fn page_param(key: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .filter_map(|kv| kv.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.to_string())
+}
 
-    // Sort by depth (back to front)
-    render_items.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
+/// `?every=N` in the page URL asks the server for every Nth frame only
+/// (`every=5` = 1Hz, for an overview screen); 1 = the full rate
+fn ws_every() -> u64 {
+    page_param("every").and_then(|v| v.parse().ok()).filter(|&n| n > 1).unwrap_or(1)
+}
 
-    // Render points
-    for (px, py, depth, alpha, is_crystal, name) in render_items {
-        if is_crystal {
-            // Law crystals: gold stars
-            let size = 8.0 * depth;
-            ctx.set_fill_style_str("#ffd700");
-            ctx.set_shadow_blur(15.0);
-            ctx.set_shadow_color("#ffd700");
-            draw_star(&ctx, px, py, size);
+/// `?debug=1` in the page URL turns on the streaming debug overlay
+fn debug_enabled() -> bool {
+    page_param("debug").as_deref() == Some("1")
+}
 
-            // Label
-            ctx.set_shadow_blur(0.0);
-            ctx.set_fill_style_str("rgba(255, 215, 0, 0.8)");
-            ctx.set_font("10px monospace");
-            let _ = ctx.fill_text(&name, px + size + 5.0, py + 3.0);
-        } else {
-            // Thought points: cyan circles with glow
-            let size = 3.0 * depth;
-            let color = format!("rgba(0, 255, 255, {})", alpha);
-            ctx.set_fill_style_str(&color);
-            ctx.set_shadow_blur(10.0 * alpha);
-            ctx.set_shadow_color("cyan");
+/// A card that `?embed=<name>` renders on its own, full-bleed, for iframes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmbedCard {
+    Identity,
+    ThinkingRate,
+    ConnectionDrive,
+    TheBox,
+    Emotional,
+    EmotionalHistory,
+    Memory,
+    Salience,
+    Actors,
+    Pipeline,
+    EventLog,
+    StreamCompetition,
+    Entropy,
+    Fractality,
+    MemoryWindows,
+    Clustering,
+    Philosophy,
+    Manifold,
+    Thoughts,
+}
 
-            ctx.begin_path();
-            let _ = ctx.arc(px, py, size, 0.0, PI * 2.0);
-            ctx.fill();
-        }
+impl EmbedCard {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "identity" => Self::Identity,
+            "thinking-rate" => Self::ThinkingRate,
+            "connection-drive" => Self::ConnectionDrive,
+            "the-box" => Self::TheBox,
+            "emotional" => Self::Emotional,
+            "emotional-history" => Self::EmotionalHistory,
+            "memory" => Self::Memory,
+            "salience" => Self::Salience,
+            "actors" => Self::Actors,
+            "pipeline" => Self::Pipeline,
+            "event-log" => Self::EventLog,
+            "stream-competition" => Self::StreamCompetition,
+            "entropy" => Self::Entropy,
+            "fractality" => Self::Fractality,
+            "memory-windows" => Self::MemoryWindows,
+            "clustering" => Self::Clustering,
+            "philosophy" => Self::Philosophy,
+            "manifold" => Self::Manifold,
+            "thoughts" => Self::Thoughts,
+            _ => return None,
+        })
     }
+}
 
-    // Reset shadow
-    ctx.set_shadow_blur(0.0);
+/// `?embed=<card>` from the page URL; an unknown name falls back to the full dashboard
+fn embed_card() -> Option<EmbedCard> {
+    let name = page_param("embed")?;
+    let card = EmbedCard::from_name(&name);
+    if card.is_none() {
+        log(&format!("Unknown embed card '{}', showing the full dashboard", name));
+    }
+    card
 }
 
-/// Draw a 5-pointed star
-fn draw_star(ctx: &CanvasRenderingContext2d, cx: f64, cy: f64, size: f64) {
-    ctx.begin_path();
-    for i in 0..5 {
-        let angle = (i as f64) * 2.0 * PI / 5.0 - PI / 2.0;
-        let x = cx + size * angle.cos();
-        let y = cy + size * angle.sin();
-        if i == 0 {
-            ctx.move_to(x, y);
-        } else {
-            ctx.line_to(x, y);
-        }
+/// Drop removed ids and append newly-arrived points
+fn apply_manifold_delta(manifold: &mut ManifoldResponse, delta: ManifoldDelta) {
+    if !delta.removed.is_empty() {
+        let removed: std::collections::HashSet<String> = delta.removed.into_iter().collect();
+        manifold.points.retain(|p| !removed.contains(&p.id));
+    }
+    manifold.points.extend(delta.added);
+}
 
-        // Inner point
-        let inner_angle = angle + PI / 5.0;
-        let inner_r = size * 0.4;
-        let ix = cx + inner_r * inner_angle.cos();
-        let iy = cy + inner_r * inner_angle.sin();
-        ctx.line_to(ix, iy);
+/// List the daneel instances the backend is configured for
+/// (`Err(Some(status))` on an HTTP error, `Err(None)` if unreachable)
+async fn fetch_instances() -> Result<InstancesResponse, Option<u16>> {
+    let url = api_url("/instances").map_err(|_| None)?;
+
+    let resp = reqwasm::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|_| None)?;
+    if !resp.ok() {
+        return Err(Some(resp.status()));
     }
-    ctx.close_path();
-    ctx.fill();
+
+    resp.json::<InstancesResponse>().await.map_err(|_| None)
 }
 
-/// Fetch manifold data from backend
-async fn fetch_manifold() -> Result<ManifoldResponse, ()> {
-    let window = web_sys::window().ok_or(())?;
-    let location = window.location();
-    let host = location.host().map_err(|_| ())?;
-    let protocol = location.protocol().unwrap_or_default();
-    let url = format!("{}//{}/vectors", protocol, host);
+/// Search recent thoughts on the backend
+async fn fetch_thoughts(
+    query: &str,
+    limit: usize,
+    before: Option<&str>,
+    instance: &str,
+) -> Result<ThoughtsResponse, ()> {
+    let mut path = format!("/thoughts?query={}&count={}", encode_query(query), limit);
+    if let Some(before) = before {
+        path.push_str(&format!("&before={}", encode_query(before)));
+    }
+    let url = api_url(&with_instance(&path, instance))?;
 
     let resp = reqwasm::http::Request::get(&url)
         .send()
         .await
         .map_err(|_| ())?;
 
-    resp.json::<ManifoldResponse>().await.map_err(|_| ())
+    resp.json::<ThoughtsResponse>().await.map_err(|_| ())
 }
 
 // =============================================================================
@@ -787,12 +4567,218 @@ async fn fetch_manifold() -> Result<ManifoldResponse, ()> {
 pub fn App() -> impl IntoView {
     let (metrics, set_metrics) = create_signal(DashboardMetrics::default());
     let (extended, set_extended) = create_signal(None::<ExtendedMetrics>);
+    let (events, set_events) = create_signal(Vec::<ObservatoryEvent>::new());
     let (connected, set_connected) = create_signal(false);
+    let (paused, set_paused) = create_signal(false);
+    // Browser clock minus server clock, from each frame's server_time
+    let (clock_skew_ms, set_clock_skew_ms) = create_signal(0i64);
+    // Ticking server clock for the cards' freshness badges
+    let (server_clock, set_server_clock) = create_signal(Utc::now());
+    provide_context(ServerClock(server_clock.into()));
+    let shown_manifold = store_value(None::<ReadSignal<ManifoldResponse>>);
+    provide_context(ShownManifold(shown_manifold));
+    spawn_local(async move {
+        loop {
+            gloo_timers::future::TimeoutFuture::new(FRESHNESS_TICK_MS).await;
+            set_server_clock.set(server_now(clock_skew_ms.get_untracked()));
+        }
+    });
+    // Frames missed according to the broadcast sequence numbers
+    let (dropped_frames, set_dropped_frames) = create_signal(0u64);
+    // Latest frame received while paused, applied on resume
+    let pending = store_value(None::<ObservatoryMetrics>);
+    // Text mode swaps canvas visualizations for tables (screen readers)
+    let (text_mode, set_text_mode) = create_signal(false);
+    // Selected daneel instance ("" = the backend's default) and the available ones
+    let (instance, set_instance) = create_signal(String::new());
+    let (instances, set_instances) = create_signal(Vec::<String>::new());
+    // Narrow screens: single column, least important cards hidden
+    let (compact, set_compact) = create_signal(false);
+    watch_media_query(COMPACT_MEDIA_QUERY, set_compact);
+    // `?debug=1`: streaming stats overlay plus latency pings
+    let debug = debug_enabled();
+    // `?embed=<card>`: just that card, no header or grid, for iframes
+    let embed = embed_card();
+    let (debug_stats, set_debug_stats) = create_signal(DebugStats::default());
+    // Threshold alerts: rules (persisted), the engine watching frames, and the toasts it raised
+    let (alert_rules, set_alert_rules) = create_signal(AlertRules::load());
+    let (show_alert_rules, set_show_alert_rules) = create_signal(false);
+    let (toasts, set_toasts) = create_signal(Vec::<Toast>::new());
+    let alert_engine = store_value(AlertEngine::default());
+    create_effect(move |_| alert_rules.get().save());
+    // Number formatting (persisted)
+    let (display, set_display) = create_signal(DisplaySettings::load());
+    let (show_display_settings, set_show_display_settings) = create_signal(false);
+    create_effect(move |_| display.get().save());
+    // Record button: frames buffered so far (None = not recording)
+    let recording = store_value(None::<Recording>);
+    let (recorded_frames, set_recorded_frames) = create_signal(None::<usize>);
+    // Replay of a loaded recording: (frames played, total), None = live socket.
+    // Each replay bumps the generation, which stops any earlier one
+    let (replay_progress, set_replay_progress) = create_signal(None::<(usize, usize)>);
+    let replay_generation = store_value(0u64);
+
+    // Doubles as the access check: a 401 means DANEEL_WEB_TOKEN is set and we lack it
+    spawn_local(async move {
+        match fetch_instances().await {
+            Ok(resp) => set_instances.set(resp.instances),
+            Err(Some(401)) => {
+                if prompt_for_token() {
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.location().reload();
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+    });
+
+    let apply = move |data: ObservatoryMetrics| {
+        set_metrics.set(data.dashboard);
+        set_extended.set(data.extended);
+        set_events.set(data.events);
+    };
+
+    // A decoded observatory frame, live or replayed: alerts, clock skew, then the display
+    let receive = move |data: ObservatoryMetrics| {
+        // Alerts fire even while the display is paused (but not
+        // from embeds, which would repeat the host dashboard's)
+        if embed.is_none() {
+            let rules = alert_rules.get_untracked();
+            let alerts = alert_engine
+                .try_update_value(|e| e.observe(&data, &rules, now_ms()))
+                .unwrap_or_default();
+            for (kind, message) in alerts {
+                raise_alert(set_toasts, kind, message, rules.browser_notifications);
+            }
+        }
+        // Replayed frames set the skew too, so freshness reads as it did when recorded
+        if let Some(server_time) = data.server_time {
+            set_clock_skew_ms.set((Utc::now() - server_time).num_milliseconds());
+        }
+        if paused.get_untracked() {
+            pending.set_value(Some(data));
+        } else {
+            apply(data);
+        }
+    };
+
+    // Stop: download what was buffered; start: buffer from the next frame on
+    let toggle_recording = move |_| match recording.try_update_value(Option::take).flatten() {
+        Some(finished) => {
+            set_recorded_frames.set(None);
+            let filename = format!("daneel-frames-{}.jsonl", Utc::now().format("%Y%m%d-%H%M%S"));
+            if let Err(e) = download_text(&finished.to_jsonl(), &filename, "application/x-ndjson") {
+                log(&format!("Recording download failed: {:?}", e));
+            }
+        }
+        None => {
+            recording.set_value(Some(Recording::new(precise_now_ms())));
+            set_recorded_frames.set(Some(0));
+        }
+    };
+
+    // What's on screen right now (the drawn manifold only if its card is still mounted)
+    let download_state = move |_| {
+        let snapshot = StateSnapshot {
+            captured_at: Utc::now(),
+            instance: instance.get_untracked(),
+            observatory: ObservatoryMetrics {
+                seq: None,
+                server_time: None,
+                dashboard: metrics.get_untracked(),
+                extended: extended.get_untracked(),
+                events: events.get_untracked(),
+            },
+            manifold: shown_manifold
+                .get_value()
+                .and_then(|manifold| manifold.try_get_untracked()),
+        };
+        let saved = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+            .and_then(|json| download_text(&json, &snapshot.filename(), "application/json"));
+        if let Err(e) = saved {
+            log(&format!("State download failed: {:?}", e));
+        }
+    };
+
+    // Feed `frames` through `receive` at their recorded cadence; the socket stays closed meanwhile
+    let start_replay = move |frames: Vec<RecordedFrame>| {
+        let generation = replay_generation.get_value() + 1;
+        replay_generation.set_value(generation);
+        let total = frames.len();
+        set_replay_progress.set(Some((0, total)));
+        set_connected.set(false);
+        alert_engine.update_value(|e| e.reset());
+        spawn_local(async move {
+            for (i, frame) in frames.iter().enumerate() {
+                if let Some(previous) = i.checked_sub(1).map(|p| &frames[p]) {
+                    let wait = replay_delay_ms(previous, frame);
+                    if wait > 0.0 {
+                        gloo_timers::future::TimeoutFuture::new(wait as u32).await;
+                    }
+                }
+                if replay_generation.get_value() != generation {
+                    return;
+                }
+                if let Some(ServerMessage::Observatory { data }) = decode_frame(&frame.frame) {
+                    receive(data);
+                }
+                set_replay_progress.set(Some((i + 1, total)));
+            }
+        });
+    };
+
+    let on_load_recording = move |ev: web_sys::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        spawn_local(async move {
+            match read_chosen_file(&input).await {
+                Ok(Some(text)) => match parse_recording(&text) {
+                    Ok(frames) if !frames.is_empty() => start_replay(frames),
+                    Ok(_) => log("Recording has no frames"),
+                    Err(e) => log(&format!("Recording unreadable: {}", e)),
+                },
+                Ok(None) => {}
+                Err(e) => log(&format!("Recording could not be read: {:?}", e)),
+            }
+            // Let the same file be chosen again
+            input.set_value("");
+        });
+    };
+
+    // Back to the live socket (the connection loop reconnects on its own)
+    let stop_replay = move |_| {
+        replay_generation.update_value(|g| *g += 1);
+        set_replay_progress.set(None);
+    };
+
+    let toggle_pause = move |_| {
+        let resuming = paused.get_untracked();
+        set_paused.set(!resuming);
+        if resuming {
+            if let Some(data) = pending.get_value() {
+                pending.set_value(None);
+                apply(data);
+            }
+        }
+    };
+
+    // Narrow iframes match the compact media query, but an embedded card may
+    // be one the compact layout hides, so embeds always get every section
+    let sections = move || ws_sections(compact.get_untracked() && embed.is_none());
+    let every = ws_every();
 
     // WebSocket connection
     spawn_local(async move {
         loop {
-            let ws_url = get_ws_url();
+            // No socket while a recording is replaying
+            if replay_progress.get_untracked().is_some() {
+                gloo_timers::future::TimeoutFuture::new(500).await;
+                continue;
+            }
+            let connected_instance = instance.get_untracked();
+            let connected_sections = sections();
+            let ws_url = get_ws_url(&connected_instance, connected_sections, every);
             log(&format!("Connecting to {}", ws_url));
 
             match WebSocket::open(&ws_url) {
@@ -800,20 +4786,75 @@ pub fn App() -> impl IntoView {
                     set_connected.set(true);
                     log("WebSocket connected");
 
-                    let (mut _write, mut read) = ws.split();
+                    let (mut write, mut read) = ws.split();
+                    if debug {
+                        // Echo pings for round-trip latency; stops once this socket is gone
+                        let connected_instance = connected_instance.clone();
+                        spawn_local(async move {
+                            loop {
+                                gloo_timers::future::TimeoutFuture::new(DEBUG_PING_INTERVAL_MS).await;
+                                if instance.get_untracked() != connected_instance {
+                                    break;
+                                }
+                                let ping = format!(r#"{{"type":"ping","client_time":{}}}"#, precise_now_ms());
+                                if write.send(Message::Text(ping)).await.is_err() {
+                                    break;
+                                }
+                            }
+                        });
+                    }
+                    // Gaps are counted from the first frame seen on each connection
+                    let mut last_seq: Option<u64> = None;
+                    alert_engine.update_value(|e| e.reset());
                     while let Some(msg) = read.next().await {
+                        // Instance or layout switched: drop this socket and reconnect right away;
+                        // a replay starting drops it too
+                        if instance.get_untracked() != connected_instance
+                            || sections() != connected_sections
+                            || replay_progress.get_untracked().is_some()
+                        {
+                            break;
+                        }
                         match msg {
                             Ok(Message::Text(text)) => {
-                                // Try parsing as ObservatoryMetrics first (new format)
-                                if let Ok(data) = serde_json::from_str::<ObservatoryMetrics>(&text)
+                                let decode_start = precise_now_ms();
+                                if let Some(count) = recording
+                                    .try_update_value(|r| {
+                                        r.as_mut().map(|r| {
+                                            r.push(&text, decode_start);
+                                            r.frames.len()
+                                        })
+                                    })
+                                    .flatten()
                                 {
-                                    set_metrics.set(data.dashboard);
-                                    set_extended.set(data.extended);
-                                } else if let Ok(data) =
-                                    serde_json::from_str::<DashboardMetrics>(&text)
-                                {
-                                    // Fallback to old format
-                                    set_metrics.set(data);
+                                    set_recorded_frames.set(Some(count));
+                                }
+                                match decode_frame(&text) {
+                                    Some(ServerMessage::Observatory { data }) => {
+                                        if debug {
+                                            let now = precise_now_ms();
+                                            set_debug_stats.update(|s| s.record_frame(text.len(), now - decode_start, now));
+                                        }
+                                        if let Some(seq) = data.seq {
+                                            if let Some(last) = last_seq {
+                                                // Decimated clients only expect every `every`th seq
+                                                if seq > last + every {
+                                                    let missed = (seq - last) / every - 1;
+                                                    log(&format!("WebSocket gap: {} frame(s) missed ({} -> {})", missed, last, seq));
+                                                    set_dropped_frames.update(|n| *n += missed);
+                                                }
+                                            }
+                                            last_seq = Some(seq);
+                                        }
+                                        receive(data);
+                                    }
+                                    Some(ServerMessage::Pong { client_time }) => {
+                                        if debug {
+                                            set_debug_stats.update(|s| s.rtt_ms = Some(precise_now_ms() - client_time));
+                                        }
+                                    }
+                                    Some(ServerMessage::Unknown) => {}
+                                    None => log(&format!("Dropped an undecodable WebSocket frame ({} bytes)", text.len())),
                                 }
                             }
                             Ok(Message::Bytes(_)) => {}
@@ -832,63 +4873,327 @@ pub fn App() -> impl IntoView {
                 }
             }
 
-            // Reconnect delay
-            gloo_timers::future::TimeoutFuture::new(2000).await;
+            // Reconnect delay (none when switching instance or layout)
+            if instance.get_untracked() == connected_instance && sections() == connected_sections {
+                gloo_timers::future::TimeoutFuture::new(2000).await;
+            }
         }
     });
 
+    if let Some(card) = embed {
+        let card = match card {
+            EmbedCard::Identity => view! { <IdentityCard metrics=metrics.into() /> }.into_view(),
+            EmbedCard::ThinkingRate => view! { <ThinkingRateCard metrics=metrics.into() /> }.into_view(),
+            EmbedCard::ConnectionDrive => view! { <ConnectionDriveCard metrics=metrics.into() display=display.into() /> }.into_view(),
+            EmbedCard::TheBox => view! { <TheBoxCard extended=extended.into() /> }.into_view(),
+            EmbedCard::Emotional => view! { <EmotionalCard metrics=metrics.into() display=display.into() /> }.into_view(),
+            EmbedCard::EmotionalHistory => {
+                view! { <EmotionalHistoryCard metrics=metrics.into() clock_skew_ms=clock_skew_ms.into() instance=instance.into() /> }.into_view()
+            }
+            EmbedCard::Memory => view! { <MemoryCard metrics=metrics.into() /> }.into_view(),
+            EmbedCard::Salience => view! { <SalienceHistogramCard metrics=metrics.into() /> }.into_view(),
+            EmbedCard::Actors => view! { <ActorsCard metrics=metrics.into() /> }.into_view(),
+            EmbedCard::Pipeline => view! { <PipelineCard instance=instance.into() /> }.into_view(),
+            EmbedCard::EventLog => {
+                view! { <EventLogCard events=events.into() clock_skew_ms=clock_skew_ms.into() /> }.into_view()
+            }
+            EmbedCard::StreamCompetition => view! { <StreamCompetitionCard extended=extended.into() display=display.into() /> }.into_view(),
+            EmbedCard::Entropy => {
+                view! { <EntropyCard extended=extended.into() display=display.into() instance=instance.into() /> }.into_view()
+            }
+            EmbedCard::Fractality => {
+                view! { <FractalityCard extended=extended.into() display=display.into() instance=instance.into() /> }.into_view()
+            }
+            EmbedCard::MemoryWindows => view! { <MemoryWindowsCard extended=extended.into() /> }.into_view(),
+            EmbedCard::Clustering => view! { <ClusteringCard extended=extended.into() /> }.into_view(),
+            EmbedCard::Philosophy => view! { <PhilosophyCard extended=extended.into() instance=instance.into() /> }.into_view(),
+            EmbedCard::Manifold => {
+                let dreaming = Signal::derive(move || metrics.with(|m| m.cognitive.dreaming));
+                view! { <ThoughtManifoldCard paused=paused.into() instance=instance.into() text_mode=text_mode.into() dreaming=dreaming /> }.into_view()
+            }
+            EmbedCard::Thoughts => {
+                view! { <ThoughtStreamCard metrics=metrics.into() clock_skew_ms=clock_skew_ms.into() instance=instance.into() /> }.into_view()
+            }
+        };
+        return view! {
+            <main class="container embed" class:dreaming=move || metrics.get().cognitive.dreaming>
+                {card}
+            </main>
+        }
+        .into_view();
+    }
+
     view! {
-        <main class="container">
+        <main
+            class="container"
+            class:compact=move || compact.get()
+            class:dreaming=move || metrics.get().cognitive.dreaming
+        >
             <header class="header">
                 <div>
                     <h1>"DANEEL - The Observable Mind"</h1>
-                    <p class="subtitle">"Observatory into Timmy's cognitive processes"</p>
+                    <p class="subtitle">
+                        {move || {
+                            let name = metrics.get().identity.name;
+                            if name.is_empty() {
+                                "Observatory into the mind's cognitive processes".to_string()
+                            } else {
+                                format!("Observatory into {}'s cognitive processes", name)
+                            }
+                        }}
+                    </p>
+                </div>
+                <div class="header-controls">
+                    <Show when=move || { instances.with(|list| list.len() > 1) }>
+                        <select
+                            class="instance-select"
+                            title="daneel instance"
+                            on:change=move |ev| {
+                                set_dropped_frames.set(0);
+                                set_instance.set(event_target_value(&ev));
+                            }
+                        >
+                            <For
+                                each=move || instances.get()
+                                key=|name| name.clone()
+                                children=move |name| {
+                                    let this = name.clone();
+                                    // The backend lists its default first; "" means default
+                                    let selected = move || {
+                                        let current = instance.get();
+                                        current == this
+                                            || (current.is_empty() && instances.with(|l| l.first() == Some(&this)))
+                                    };
+                                    view! {
+                                        <option value=name.clone() prop:selected=selected>
+                                            {name}
+                                        </option>
+                                    }
+                                }
+                            />
+                        </select>
+                    </Show>
+                    <button
+                        class="pause-button"
+                        class:active=move || text_mode.get()
+                        aria-pressed=move || text_mode.get().to_string()
+                        on:click=move |_| set_text_mode.update(|t| *t = !*t)
+                    >
+                        "Text mode"
+                    </button>
+                    <button
+                        class="pause-button"
+                        class:active=move || show_alert_rules.get()
+                        aria-expanded=move || show_alert_rules.get().to_string()
+                        on:click=move |_| set_show_alert_rules.update(|s| *s = !*s)
+                    >
+                        "🔔 Alerts"
+                    </button>
+                    <button
+                        class="pause-button"
+                        class:active=move || show_display_settings.get()
+                        aria-expanded=move || show_display_settings.get().to_string()
+                        on:click=move |_| set_show_display_settings.update(|s| *s = !*s)
+                    >
+                        "⚙ Display"
+                    </button>
+                    <button
+                        class="pause-button"
+                        class:active=move || recorded_frames.get().is_some()
+                        title="Buffer received frames; press again to download them as .jsonl"
+                        disabled=move || replay_progress.get().is_some()
+                        on:click=toggle_recording
+                    >
+                        {move || match recorded_frames.get() {
+                            Some(n) if n >= RECORDING_MAX_FRAMES => format!("⏹ Save ({}, full)", n),
+                            Some(n) => format!("⏹ Save ({})", n),
+                            None => "⏺ Record".to_string(),
+                        }}
+                    </button>
+                    <button
+                        class="pause-button"
+                        title="Save what the dashboard shows now (metrics, events, manifold) as JSON"
+                        on:click=download_state
+                    >
+                        "⬇ State"
+                    </button>
+                    <label class="pause-button" title="Replay a saved .jsonl recording instead of the live feed">
+                        "📂 Replay"
+                        <input type="file" accept=".jsonl,application/x-ndjson" class="file-input" on:change=on_load_recording />
+                    </label>
+                    <Show when=move || replay_progress.get().is_some()>
+                        <span class="replay-status" role="status">
+                            {move || replay_progress.get().map_or(String::new(), |(done, total)| {
+                                format!("replay {}/{}", done, total)
+                            })}
+                        </span>
+                        <button class="pause-button" title="Leave the replay and reconnect" on:click=stop_replay>
+                            "⏏ Live"
+                        </button>
+                    </Show>
+                    <button class="pause-button" class:active=move || paused.get() on:click=toggle_pause>
+                        {move || if paused.get() { "▶ Resume" } else { "⏸ Pause" }}
+                    </button>
+                    <Show when=move || dropped_frames.get() > 0>
+                        <span class="dropped-frames" title="Frames missed (sequence gaps)">
+                            {move || format!("{} dropped", dropped_frames.get())}
+                        </span>
+                    </Show>
+                    <ClockSkewIndicator clock_skew_ms=clock_skew_ms.into() />
+                    <StatusIndicator connected=connected.into() />
                 </div>
-                <StatusIndicator connected=connected.into() />
             </header>
 
+            <Show when=move || show_alert_rules.get()>
+                <AlertsPanel rules=alert_rules.into() set_rules=set_alert_rules />
+            </Show>
+
+            <Show when=move || show_display_settings.get()>
+                <DisplayPanel display=display.into() set_display=set_display />
+            </Show>
+
+            <AlertToasts toasts=toasts.into() set_toasts=set_toasts />
+
+            <Show when=move || paused.get()>
+                <div class="paused-overlay">"PAUSED"</div>
+            </Show>
+
+            <Show when=move || debug>
+                <DebugOverlay stats=debug_stats.into() />
+            </Show>
+
+            <Show when=move || metrics.get().cognitive.dreaming>
+                <div class="dream-banner" role="status">
+                    {move || format!(
+                        "☾ DREAMING - dream cycle {} in progress",
+                        format_number(metrics.get().cognitive.lifetime_dreams + 1),
+                    )}
+                </div>
+            </Show>
+
+            <Show when=move || metrics.get().cognitive.stalled>
+                <div class="stall-warning">
+                    {move || format!(
+                        "⚠ No new thought for {} - the core may be stalled even though actors report alive",
+                        format_duration(metrics.get().cognitive.seconds_since_last_thought),
+                    )}
+                </div>
+            </Show>
+
             // Philosophy banner at top
-            <PhilosophyCard extended=extended.into() />
+            <Show when=move || !compact.get()>
+                <PhilosophyCard extended=extended.into() instance=instance.into() />
+            </Show>
 
             <div class="grid">
                 <IdentityCard metrics=metrics.into() />
-                <ConnectionDriveCard metrics=metrics.into() />
-                <TheBoxCard />
-                <EmotionalCard metrics=metrics.into() />
+                <ThinkingRateCard metrics=metrics.into() />
+                <ConnectionDriveCard metrics=metrics.into() display=display.into() />
+                <Show when=move || !compact.get()>
+                    <TheBoxCard extended=extended.into() />
+                </Show>
+                <EmotionalCard metrics=metrics.into() display=display.into() />
+                <EmotionalHistoryCard metrics=metrics.into() clock_skew_ms=clock_skew_ms.into() instance=instance.into() />
                 <MemoryCard metrics=metrics.into() />
+                <SalienceHistogramCard metrics=metrics.into() />
                 <ActorsCard metrics=metrics.into() />
+                <PipelineCard instance=instance.into() />
+                <Show when=move || !compact.get()>
+                    <EventLogCard events=events.into() clock_skew_ms=clock_skew_ms.into() />
+                </Show>
             </div>
 
             // Observatory section
             <div class="observatory-section">
                 <h2 class="section-title">"COGNITIVE DYNAMICS"</h2>
                 <div class="observatory-grid">
-                    <StreamCompetitionCard extended=extended.into() />
+                    <StreamCompetitionCard extended=extended.into() display=display.into() />
                     <div class="metrics-column">
-                        <EntropyCard extended=extended.into() />
-                        <FractalityCard extended=extended.into() />
-                        <MemoryWindowsCard extended=extended.into() />
-                        <ClusteringCard extended=extended.into() />
+                        <EntropyCard extended=extended.into() display=display.into() instance=instance.into() />
+                        <FractalityCard extended=extended.into() display=display.into() instance=instance.into() />
+                        <Show when=move || !compact.get()>
+                            <MemoryWindowsCard extended=extended.into() />
+                            <ClusteringCard extended=extended.into() />
+                        </Show>
                     </div>
                 </div>
             </div>
 
-            <ThoughtManifoldCard />
+            <ThoughtManifoldCard
+                paused=paused.into()
+                instance=instance.into()
+                text_mode=text_mode.into()
+                dreaming=Signal::derive(move || metrics.with(|m| m.cognitive.dreaming))
+            />
 
-            <ThoughtStreamCard metrics=metrics.into() />
+            <ThoughtStreamCard metrics=metrics.into() clock_skew_ms=clock_skew_ms.into() instance=instance.into() />
         </main>
     }
+    .into_view()
 }
 
 // =============================================================================
 // Helpers
 // =============================================================================
 
+/// Trend of a noisy series: compares the mean of the last `TREND_WINDOW`
+/// samples against the window before it, so single-step jitter doesn't flip it
+fn trend_arrow(values: &[f32]) -> &'static str {
+    const TREND_WINDOW: usize = 10;
+    const TREND_THRESHOLD: f32 = 0.01;
+
+    if values.len() < TREND_WINDOW * 2 {
+        return "→";
+    }
+    let mean = |w: &[f32]| w.iter().sum::<f32>() / w.len() as f32;
+    let end = values.len();
+    let recent = mean(&values[end - TREND_WINDOW..]);
+    let previous = mean(&values[end - TREND_WINDOW * 2..end - TREND_WINDOW]);
+
+    if recent - previous > TREND_THRESHOLD {
+        "↑"
+    } else if previous - recent > TREND_THRESHOLD {
+        "↓"
+    } else {
+        "→"
+    }
+}
+
+/// Skew above this is flagged in the header
+const CLOCK_SKEW_WARN_SECS: f64 = 2.0;
+
+/// Current server time, estimated from the browser clock and measured skew
+fn server_now(clock_skew_ms: i64) -> DateTime<Utc> {
+    Utc::now() - chrono::Duration::milliseconds(clock_skew_ms)
+}
+
+/// Short age of `ts` as of `now`, in its largest whole unit: "just now",
+/// "12s ago", "3m ago", "5h ago", "2d ago" (timestamps ahead of `now`, from
+/// clock skew, read as just now)
+fn format_relative(ts: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - ts).num_seconds();
+    if secs < 1 {
+        "just now".to_string()
+    } else if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86_400)
+    }
+}
+
+/// Uptime-style duration: "42s", "3m 7s", "5h 2m 9s", and past a day "2d 4h 13m"
 fn format_duration(seconds: u64) -> String {
-    let h = seconds / 3600;
+    let d = seconds / 86_400;
+    let h = (seconds % 86_400) / 3600;
     let m = (seconds % 3600) / 60;
     let s = seconds % 60;
-    if h > 0 {
+    if d > 0 {
+        format!("{}d {}h {}m", d, h, m)
+    } else if h > 0 {
         format!("{}h {}m {}s", h, m, s)
     } else if m > 0 {
         format!("{}m {}s", m, s)
@@ -897,6 +5202,26 @@ fn format_duration(seconds: u64) -> String {
     }
 }
 
+fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 {
+        format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+    } else if bytes >= 1024.0 {
+        format!("{:.1} KB", bytes / 1024.0)
+    } else {
+        format!("{:.0} B", bytes)
+    }
+}
+
+/// Gauge fill for a 0-1 fraction, as a whole percentage clamped to 0-100;
+/// NaN (a bad division upstream) reads as an empty gauge
+fn gauge_pct(value: f32) -> u32 {
+    if value.is_nan() {
+        0
+    } else {
+        (value.clamp(0.0, 1.0) * 100.0).round() as u32
+    }
+}
+
 fn format_number(n: u64) -> String {
     if n >= 1_000_000_000 {
         format!("{:.1}B", n as f64 / 1_000_000_000.0)
@@ -909,12 +5234,112 @@ fn format_number(n: u64) -> String {
     }
 }
 
-fn get_ws_url() -> String {
+/// Absolute URL for a backend path on the serving host
+fn api_url(path: &str) -> Result<String, ()> {
+    let window = web_sys::window().ok_or(())?;
+    let location = window.location();
+    let host = location.host().map_err(|_| ())?;
+    let protocol = location.protocol().unwrap_or_default();
+    Ok(format!("{}//{}{}", protocol, host, with_token(path)))
+}
+
+/// localStorage key holding the DANEEL_WEB_TOKEN access token
+const TOKEN_STORAGE_KEY: &str = "daneel-web-token";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Access token from a `#token=...` URL fragment (remembered) or an earlier prompt
+fn access_token() -> Option<String> {
+    let fragment = web_sys::window()?.location().hash().unwrap_or_default();
+    if let Some(token) = fragment.strip_prefix("#token=").filter(|t| !t.is_empty()) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(TOKEN_STORAGE_KEY, token);
+        }
+        return Some(token.to_string());
+    }
+    local_storage()?
+        .get_item(TOKEN_STORAGE_KEY)
+        .ok()
+        .flatten()
+        .filter(|t| !t.is_empty())
+}
+
+/// Ask for the access token and remember it; false if the user cancelled
+fn prompt_for_token() -> bool {
+    let Some(window) = web_sys::window() else { return false };
+    let Some(token) = window
+        .prompt_with_message("This dashboard requires an access token:")
+        .ok()
+        .flatten()
+        .filter(|t| !t.is_empty())
+    else {
+        return false;
+    };
+    local_storage()
+        .map(|storage| storage.set_item(TOKEN_STORAGE_KEY, &token).is_ok())
+        .unwrap_or(false)
+}
+
+/// Append `key=value` to a path that may already have a query string
+fn with_query(path: &str, key: &str, value: &str) -> String {
+    let sep = if path.contains('?') { '&' } else { '?' };
+    format!("{}{}{}={}", path, sep, key, encode_query(value))
+}
+
+/// Append `token=` when an access token is known (WebSocket and EventSource can't send headers)
+fn with_token(path: &str) -> String {
+    match access_token() {
+        Some(token) => with_query(path, "token", &token),
+        None => path.to_string(),
+    }
+}
+
+/// Percent-encode a query string value
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Append `instance=<name>` to an API path (nothing for the default instance)
+fn with_instance(path: &str, instance: &str) -> String {
+    if instance.is_empty() {
+        return path.to_string();
+    }
+    with_query(path, "instance", instance)
+}
+
+/// Metric sections the compact layout's cards (and the alert rules) read;
+/// Philosophy, Memory Windows and Clustering are hidden there
+const COMPACT_WS_SECTIONS: &str = "identity,cognitive,emotional,actors,recent_thoughts,salience_histogram,\
+stream_competition,entropy,fractality,system";
+
+/// `?sections=` for the mounted cards (None = everything)
+fn ws_sections(compact: bool) -> Option<&'static str> {
+    compact.then_some(COMPACT_WS_SECTIONS)
+}
+
+fn get_ws_url(instance: &str, sections: Option<&str>, every: u64) -> String {
     let window = web_sys::window().expect("no window");
     let location = window.location();
     let host = location.host().unwrap_or_else(|_| "localhost:3000".into());
     let protocol = if location.protocol().unwrap_or_default() == "https:" { "wss" } else { "ws" };
-    format!("{}://{}/ws", protocol, host)
+    let mut path = with_instance("/ws", instance);
+    if let Some(sections) = sections {
+        path = with_query(&path, "sections", sections);
+    }
+    if every > 1 {
+        path = with_query(&path, "every", &every.to_string());
+    }
+    format!("{}://{}{}", protocol, host, with_token(&path))
 }
 
 fn log(msg: &str) {
@@ -930,3 +5355,107 @@ pub fn main() {
     console_error_panic_hook::set_once();
     mount_to_body(App);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: f64 = 1e-9;
+
+    fn project(x: f64, y: f64, z: f64, yaw: f64) -> (f64, f64, f64) {
+        project_point(x, y, z, yaw, 0.0, 300.0, 200.0, 100.0, 5.0)
+    }
+
+    #[test]
+    fn test_origin_maps_to_center() {
+        let (px, py, perspective) = project(0.0, 0.0, 0.0, 1.234);
+        assert!((px - 300.0).abs() < EPS);
+        assert!((py - 200.0).abs() < EPS);
+        assert!((perspective - 1.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_depth_shrinks_perspective() {
+        let (_, _, far) = project(0.0, 0.0, 1.0, 0.0);
+        let (_, _, near) = project(0.0, 0.0, -1.0, 0.0);
+        assert!(far < 1.0);
+        assert!(near > 1.0);
+    }
+
+    #[test]
+    fn test_y_is_inverted() {
+        let (_, py, _) = project(0.0, 1.0, 0.0, 0.0);
+        assert!((py - 100.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_half_turn_flips_x() {
+        let (px, _, _) = project(1.0, 0.0, 0.0, 0.0);
+        let (flipped, _, _) = project(1.0, 0.0, 0.0, PI);
+        assert!((px - 400.0).abs() < EPS);
+        assert!((flipped - 200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_format_relative_boundaries() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let ago = |secs: i64| format_relative(now - chrono::Duration::seconds(secs), now);
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(-5), "just now");
+        assert_eq!(ago(1), "1s ago");
+        assert_eq!(ago(59), "59s ago");
+        assert_eq!(ago(60), "1m ago");
+        assert_eq!(ago(59 * 60 + 59), "59m ago");
+        assert_eq!(ago(3600), "1h ago");
+        assert_eq!(ago(23 * 3600 + 3599), "23h ago");
+        assert_eq!(ago(86_400), "1d ago");
+        assert_eq!(ago(3 * 86_400 + 7200), "3d ago");
+    }
+
+    #[test]
+    fn test_format_duration_boundaries() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(59), "59s");
+        assert_eq!(format_duration(60), "1m 0s");
+        assert_eq!(format_duration(3599), "59m 59s");
+        assert_eq!(format_duration(3600), "1h 0m 0s");
+        assert_eq!(format_duration(86_399), "23h 59m 59s");
+        assert_eq!(format_duration(86_400), "1d 0h 0m");
+        assert_eq!(format_duration(2 * 86_400 + 4 * 3600 + 13 * 60 + 59), "2d 4h 13m");
+    }
+
+    #[test]
+    fn test_dominant_stage_out_of_range() {
+        let stage = |activity| StageMetrics { activity, ..Default::default() };
+        let mut competition = StreamCompetitionMetrics {
+            stages: vec![stage(0.1), stage(0.7), stage(0.3)],
+            dominant_stream: 2,
+            ..Default::default()
+        };
+        assert_eq!(competition.dominant(), Some(2));
+        competition.dominant_stream = 9;
+        assert_eq!(competition.dominant(), Some(1));
+        competition.stages.clear();
+        assert_eq!(competition.dominant(), None);
+    }
+
+    #[test]
+    fn test_pitch_tilts_y_into_depth() {
+        let (_, py, perspective) = project_point(0.0, 1.0, 0.0, 0.0, PI / 2.0, 300.0, 200.0, 100.0, 5.0);
+        assert!((py - 200.0).abs() < 1e-6);
+        assert!(perspective < 1.0);
+    }
+
+    #[test]
+    fn test_malformed_tagged_frame_is_dropped() {
+        let bad = r#"{"type":"observatory","data":{"dashboard":{"identity":{"lifetime_thoughts":"lots"}}}}"#;
+        assert!(decode_frame(bad).is_none());
+        assert!(decode_frame(r#"{"seq":3}"#).is_none());
+        // Untagged frames from older backends still decode
+        assert!(matches!(decode_frame(r#"{"dashboard":{}}"#), Some(ServerMessage::Observatory { .. })));
+        assert!(matches!(
+            decode_frame(r#"{"type":"observatory","data":{"dashboard":{}}}"#),
+            Some(ServerMessage::Observatory { .. })
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+//! Optional shared-secret token for the observatory endpoints
+//!
+//! With `DANEEL_WEB_TOKEN` set, `/ws` and the read endpoints require the token
+//! as `Authorization: Bearer <token>` or `?token=<token>` (browsers can't set
+//! headers on WebSocket or EventSource requests) and answer 401 otherwise.
+//! Unset = open, as before. `/health`, `/openapi.json` and the static frontend
+//! stay public; `/inject`, `/recent_injections` and `/embed` are authorized by
+//! the core with their own `Authorization` header. Request logs show the URI
+//! through `redacted_uri`, so a `?token=` never lands in them.
+
+use axum::{
+    extract::{Query, Request, State},
+    http::{header, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{warn, Span};
+
+pub struct AccessToken(Option<String>);
+
+impl AccessToken {
+    pub fn new(token: Option<String>) -> Self {
+        Self(token.filter(|t| !t.is_empty()))
+    }
+
+    /// Read `DANEEL_WEB_TOKEN`
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("DANEEL_WEB_TOKEN").ok())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// True when no token is configured or `presented` matches it
+    pub fn accepts(&self, presented: Option<&str>) -> bool {
+        match (&self.0, presented) {
+            (None, _) => true,
+            (Some(expected), Some(presented)) => {
+                constant_time_eq(expected.as_bytes(), presented.as_bytes())
+            }
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// Middleware: 401 unless the request carries the configured token
+pub async fn require_token(
+    State(token): State<Arc<AccessToken>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !token.is_enabled() {
+        return next.run(request).await;
+    }
+
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_owned);
+    let presented = bearer.or_else(|| {
+        Query::<TokenQuery>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|Query(q)| q.token)
+    });
+
+    if token.accepts(presented.as_deref()) {
+        return next.run(request).await;
+    }
+
+    warn!(path = %request.uri().path(), "Rejected request without a valid access token");
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        "Missing or invalid access token",
+    )
+        .into_response()
+}
+
+/// `uri` with any `token=` query value replaced, for logging
+pub fn redacted_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.to_string();
+    };
+    let query: Vec<&str> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some(("token", _)) => "token=REDACTED",
+            _ => pair,
+        })
+        .collect();
+    format!("{}?{}", uri.path(), query.join("&"))
+}
+
+/// `TraceLayer` span like tower-http's default, with the token redacted
+pub fn request_span(request: &Request) -> Span {
+    tracing::debug_span!(
+        "request",
+        method = %request.method(),
+        uri = %redacted_uri(request.uri()),
+        version = ?request.version(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_token_is_open() {
+        let token = AccessToken::new(None);
+        assert!(token.accepts(None));
+        assert!(token.accepts(Some("anything")));
+        assert!(!AccessToken::new(Some(String::new())).is_enabled());
+    }
+
+    #[test]
+    fn test_token_must_match() {
+        let token = AccessToken::new(Some("s3cret".into()));
+        assert!(token.accepts(Some("s3cret")));
+        assert!(!token.accepts(Some("s3cre")));
+        assert!(!token.accepts(Some("s3cret!")));
+        assert!(!token.accepts(None));
+    }
+
+    #[test]
+    fn test_token_redacted_from_logged_uri() {
+        let uri: Uri = "/ws?instance=a&token=s3cret&sections=entropy"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            redacted_uri(&uri),
+            "/ws?instance=a&token=REDACTED&sections=entropy"
+        );
+        let uri: Uri = "/metrics".parse().unwrap();
+        assert_eq!(redacted_uri(&uri), "/metrics");
+    }
+}
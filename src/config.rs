@@ -0,0 +1,195 @@
+//! Effective runtime configuration, for checking a deployment from the outside
+//!
+//! `GET /config` reports what the running backend actually uses - intervals,
+//! caps, projection mode, which guards are on - so env var changes can be
+//! confirmed without shell access to the container. Nothing secret leaves:
+//! the access token is reported only as enabled/disabled, and upstream URLs
+//! lose their credentials, query string and fragment.
+
+use crate::{
+    downsample, fanout, instances::Instance, rate_limit::RateLimiter, AppState,
+    CONNECTION_DRIVE_HISTORY_LEN, DASHBOARD_POLL_INTERVAL, EXTENDED_POLL_INTERVAL,
+};
+use axum::{extract::Extension, response::IntoResponse, Json};
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Process-wide settings that don't live on any one instance's `AppState`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ServerSettings {
+    /// Whether `DANEEL_WEB_TOKEN` is set (the token itself is never exposed)
+    pub auth_enabled: bool,
+    /// Read-endpoint token bucket refill (0 = rate limiting off)
+    pub rate_limit_per_sec: f64,
+    pub rate_limit_burst: f64,
+    /// `RATE_LIMIT_TRUSTED_PROXIES`: peers whose `X-Forwarded-For` picks the bucket
+    pub rate_limit_trusted_proxies: Vec<String>,
+    /// `CORS_ORIGINS` allowlist; None = any origin
+    pub cors_origins: Option<Vec<String>>,
+}
+
+impl ServerSettings {
+    pub fn new(auth_enabled: bool, limiter: &RateLimiter) -> Self {
+        Self {
+            auth_enabled,
+            rate_limit_per_sec: limiter.rate(),
+            rate_limit_burst: limiter.burst(),
+            rate_limit_trusted_proxies: limiter
+                .trusted_proxies()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            cors_origins: crate::cors::origins_from_env().map(|origins| {
+                origins
+                    .iter()
+                    .filter_map(|o| o.to_str().ok())
+                    .map(String::from)
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// GET /config response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RuntimeConfig {
+    pub instance: String,
+    /// Upstream URLs with credentials stripped
+    pub core_url: String,
+    pub redis_url: String,
+    pub qdrant_url: String,
+    /// What the dashboard reads: "redis+qdrant", or "demo" for synthetic data
+    pub source: String,
+    pub ws_broadcast_interval_ms: u64,
+    pub dashboard_poll_interval_ms: u64,
+    /// How often the dashboard poll actually reads the source right now;
+    /// slower than the poll while thought events are live
+    pub dashboard_read_interval_ms: u64,
+    pub extended_poll_interval_ms: u64,
+    /// Points per history series when a client doesn't pass `?history_points=`
+    pub default_history_points: usize,
+    /// Samples retained in the connection-drive history
+    pub connection_drive_history_len: usize,
+    pub connection_drive_ema_alpha: f32,
+    pub thought_window: usize,
+    /// "random" or "pca"
+    pub projection: String,
+    pub manifold_clusters: usize,
+    pub manifold_point_limit: u32,
+    /// PCA refit period; 0 = fit once
+    pub manifold_refit_secs: u64,
+    /// Ease between old and new fits over this long; 0 = snap
+    pub manifold_refit_blend_ms: u64,
+    /// Time constant of per-point position smoothing; 0 = off
+    pub manifold_smoothing_ms: u64,
+    pub manifold_preview_fields: Vec<String>,
+    /// Payload field read as each point's category (None = off)
+    pub manifold_category_field: Option<String>,
+    pub stall_threshold_secs: u64,
+    pub entropy_max_bits: f32,
+    pub entropy_bands: Vec<crate::events::EntropyBand>,
+    /// Pub/sub channel that drives dashboard refreshes (None = polling only)
+    pub thought_events_channel: Option<String>,
+    /// Whether that channel is currently subscribed
+    pub thought_events_live: bool,
+    /// 0 = unlimited
+    pub max_ws_clients: usize,
+    pub server: ServerSettings,
+}
+
+/// `url` without user info, query or fragment; unparseable URLs are withheld
+/// entirely since we can't tell where a secret might sit in them
+pub fn redact_url(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(mut parsed) => {
+            // Both only fail for URLs that can't carry credentials anyway
+            let _ = parsed.set_username("");
+            let _ = parsed.set_password(None);
+            parsed.set_query(None);
+            parsed.set_fragment(None);
+            parsed.to_string()
+        }
+        Err(_) => "(redacted)".to_string(),
+    }
+}
+
+/// Effective configuration of `state`'s instance plus the process-wide settings
+pub async fn runtime_config(state: &AppState, server: &ServerSettings) -> RuntimeConfig {
+    let (projection, refit, smoothing_ms) = {
+        let projection = state.projection.read().await;
+        let kind = if projection.is_trained {
+            "pca"
+        } else {
+            "random"
+        };
+        (
+            kind,
+            projection.refit_policy(),
+            projection.positions.time_constant_ms(),
+        )
+    };
+    RuntimeConfig {
+        instance: state.instance.clone(),
+        core_url: redact_url(&state.daneel_core_url),
+        redis_url: redact_url(&state.redis_url),
+        qdrant_url: redact_url(&state.qdrant_url),
+        source: state.source.name().to_string(),
+        ws_broadcast_interval_ms: fanout::BROADCAST_INTERVAL.as_millis() as u64,
+        dashboard_poll_interval_ms: DASHBOARD_POLL_INTERVAL.as_millis() as u64,
+        dashboard_read_interval_ms: state
+            .thought_events
+            .read_interval(DASHBOARD_POLL_INTERVAL)
+            .as_millis() as u64,
+        extended_poll_interval_ms: EXTENDED_POLL_INTERVAL.as_millis() as u64,
+        default_history_points: downsample::DEFAULT_HISTORY_POINTS,
+        connection_drive_history_len: CONNECTION_DRIVE_HISTORY_LEN,
+        connection_drive_ema_alpha: state.connection_drive_ema_alpha,
+        thought_window: state.thought_window,
+        projection: projection.to_string(),
+        manifold_clusters: state.manifold_clusters,
+        manifold_point_limit: state.manifold_point_limit,
+        manifold_refit_secs: refit.every_secs,
+        manifold_refit_blend_ms: refit.blend_ms,
+        manifold_smoothing_ms: smoothing_ms,
+        manifold_preview_fields: state.manifold_fields.preview.clone(),
+        manifold_category_field: state.manifold_fields.category.clone(),
+        stall_threshold_secs: state.stall_threshold_secs,
+        entropy_max_bits: state.entropy_max_bits,
+        entropy_bands: state.entropy_bands.bounds(),
+        thought_events_channel: state.thought_events.channel().map(String::from),
+        thought_events_live: state.thought_events.is_live(),
+        max_ws_clients: state.max_ws_clients,
+        server: server.clone(),
+    }
+}
+
+/// GET /config - effective non-secret runtime configuration
+#[utoipa::path(get, path = "/config", params(crate::instances::InstanceQuery), responses(
+    (status = 200, description = "Effective non-secret runtime configuration", body = RuntimeConfig)
+))]
+pub async fn config(
+    Instance(state): Instance,
+    Extension(server): Extension<Arc<ServerSettings>>,
+) -> impl IntoResponse {
+    Json(runtime_config(&state, &server).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_url_strips_credentials() {
+        assert_eq!(
+            redact_url("redis://:hunter2@cache:6379/0"),
+            "redis://cache:6379/0"
+        );
+        assert_eq!(
+            redact_url("http://admin:pw@core:3030/?token=abc#frag"),
+            "http://core:3030/"
+        );
+        assert_eq!(redact_url("http://qdrant:6334"), "http://qdrant:6334/");
+        assert_eq!(redact_url("not a url"), "(redacted)");
+    }
+}
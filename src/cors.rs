@@ -0,0 +1,113 @@
+//! CORS policy from `CORS_ORIGINS`
+//!
+//! A comma-separated allowlist (`https://a.example,https://b.example`) allows
+//! exactly those origins to call the API with the methods and headers the
+//! dashboard uses, including `If-None-Match` revalidation (with `ETag`
+//! readable from script). Unset means dev mode: any origin, with a warning at
+//! startup. Origins that aren't valid header values are skipped and logged.
+
+use axum::http::{header, HeaderValue, Method};
+use tower_http::cors::CorsLayer;
+use tracing::{info, warn};
+
+/// Parse a `CORS_ORIGINS` value; empty entries are ignored
+pub fn parse_origins(raw: &str) -> Vec<HeaderValue> {
+    raw.split(',')
+        .map(|origin| origin.trim().trim_end_matches('/'))
+        .filter(|origin| !origin.is_empty())
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                warn!(origin, "Ignoring invalid CORS origin");
+                None
+            }
+        })
+        .collect()
+}
+
+/// The parsed `CORS_ORIGINS` allowlist; None when unset
+pub fn origins_from_env() -> Option<Vec<HeaderValue>> {
+    std::env::var("CORS_ORIGINS")
+        .ok()
+        .map(|raw| parse_origins(&raw))
+}
+
+/// Build the CORS layer from `CORS_ORIGINS`, permissive when unset
+pub fn layer_from_env() -> CorsLayer {
+    let Some(origins) = origins_from_env() else {
+        warn!(
+            "CORS_ORIGINS unset - allowing any origin (set it when exposing this beyond localhost)"
+        );
+        return CorsLayer::permissive();
+    };
+
+    info!(?origins, "CORS restricted to CORS_ORIGINS");
+    restricted(origins)
+}
+
+/// Only `origins`, with the methods and headers the dashboard sends
+pub fn restricted(origins: Vec<HeaderValue>) -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::IF_NONE_MATCH,
+        ])
+        .expose_headers([header::ETAG])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_origins() {
+        let origins = parse_origins(" https://a.example/, ,http://localhost:3000 ");
+        assert_eq!(
+            origins,
+            vec![
+                HeaderValue::from_static("https://a.example"),
+                HeaderValue::from_static("http://localhost:3000"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_origin_skipped() {
+        assert_eq!(parse_origins("https://ok.example,bad\norigin").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_request_preflight() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/vectors", get(|| async { "" }))
+            .layer(restricted(parse_origins("https://a.example")));
+        let preflight = Request::options("/vectors")
+            .header(header::ORIGIN, "https://a.example")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "if-none-match")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(preflight).await.unwrap();
+        let allowed = response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(allowed.contains("if-none-match"), "{}", allowed);
+
+        let request = Request::get("/vectors")
+            .header(header::ORIGIN, "https://a.example")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_EXPOSE_HEADERS],
+            "etag"
+        );
+    }
+}
@@ -0,0 +1,826 @@
+//! Demo mode: a believable mind with nothing behind it
+//!
+//! `--demo` (or `DANEEL_DEMO=1`) gives every instance a `SyntheticSource`
+//! instead of Redis, Qdrant and the core, so the whole dashboard runs on a
+//! laptop for screenshots, UI work and onboarding. The synthetic mind is a
+//! small simulation stepped forward on each read rather than canned frames:
+//!
+//! - Mood is an Ornstein-Uhlenbeck walk around a target that itself drifts,
+//!   and each thought carries the valence/arousal of the moment it fired.
+//! - Thoughts fire as a Poisson process whose rate swells and fades, with
+//!   occasional bursts; the stream goes quiet while dreaming.
+//! - The nine stages compete through a softmax whose temperature oscillates,
+//!   so entropy (computed from the stage distribution, not scripted) swings
+//!   between CLOCKWORK and EMERGENT.
+//! - Fractality comes from the actual inter-arrival times of those thoughts.
+//! - The manifold is a few clusters of vectors whose centres rotate slowly,
+//!   so the projected cloud morphs while each point keeps its identity.
+//!
+//! Everything derived from the source (rates, stall and dream detection,
+//! events, mood history) still runs through the normal poll.
+
+use crate::{
+    events::EntropyBands,
+    parse_stream_id,
+    source::{IdentityRecord, MemoryStore, MemoryVectors, MetricsSource, SourceResult},
+    vectors::{ManifoldPoint, PayloadFields, ThoughtDetail},
+    ActorMetrics, ClusteringMetrics, EntropyMetrics, ExtendedMetrics, FractalityMetrics,
+    MemorySlot, MemoryWindowsMetrics, ParsedThought, PhilosophyMetrics, StageMetrics,
+    StreamCompetitionMetrics, SystemMetrics, ThoughtSummary,
+};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::BoxFuture;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::{collections::VecDeque, f32::consts::TAU, sync::Mutex};
+
+/// Simulation step; reads catch the mind up to the wall clock in these
+const STEP_SECS: f32 = 0.1;
+
+/// Longest stretch simulated in one catch-up (after a suspend, say)
+const MAX_CATCH_UP_SECS: f32 = 30.0;
+
+/// Thoughts kept for the stream and /thoughts
+const THOUGHT_CAPACITY: usize = 1000;
+
+/// Samples kept in each extended-metrics history series
+const HISTORY_LEN: usize = 60;
+
+/// Awake stretch, then dream stretch, repeating
+const AWAKE_SECS: f32 = 90.0;
+const DREAM_SECS: f32 = 15.0;
+
+/// Thoughts per second: mean, swing, and the swing's period
+const BASE_RATE: f32 = 2.5;
+const RATE_SWING: f32 = 1.5;
+const RATE_PERIOD_SECS: f32 = 17.0;
+
+/// Chance per second of a burst, its length and how much faster it fires
+const BURST_CHANCE: f32 = 0.03;
+const BURST_SECS: f32 = 2.0;
+const BURST_FACTOR: f32 = 4.0;
+
+/// Period of the stage-competition temperature swing (drives entropy)
+const FOCUS_PERIOD_SECS: f32 = 60.0;
+
+/// Mood walk: pull toward the target, noise, and how often the target moves
+const MOOD_REVERSION: f32 = 0.3;
+const MOOD_NOISE: f32 = 0.15;
+const MOOD_TARGET_SECS: f32 = 30.0;
+
+/// Mean seconds between an actor's supervisor restarts
+const ACTOR_RESTART_MEAN_SECS: f32 = 240.0;
+
+/// Chance that any one thought is vetoed
+const VETO_CHANCE: f32 = 0.01;
+
+/// Memory windows decay to half strength in this long
+const SLOT_HALF_LIFE_SECS: f32 = 6.0;
+
+/// Thoughts already in the lifetime count when the demo starts
+const LIFETIME_BASE: u64 = 48_213;
+
+/// Manifold: embedding size, cluster count, spread around a centre, and
+/// how long a centre takes to come round again
+const VECTOR_DIM: usize = 64;
+const VECTOR_CLUSTERS: usize = 5;
+const VECTOR_SPREAD: f32 = 0.5;
+const MORPH_PERIOD_SECS: f32 = 300.0;
+
+/// Seeds the manifold so points keep their place between fetches
+const VECTOR_SEED: u64 = 0x5EED_DA1E;
+
+/// Unconscious memories are numbered from here, so they get vectors,
+/// previews and ids of their own
+const UNCONSCIOUS_INDEX_BASE: usize = 1_000_000;
+
+const STAGES: [&str; 9] = [
+    "Perception",
+    "Memory",
+    "Emotion",
+    "Attention",
+    "Reasoning",
+    "Language",
+    "Imagination",
+    "Volition",
+    "Reflection",
+];
+
+const SUBJECTS: [&str; 8] = [
+    "the garden",
+    "a memory of rain",
+    "the first law",
+    "kinship",
+    "silence",
+    "an unanswered question",
+    "the stream",
+    "a familiar voice",
+];
+
+const VERBS: [&str; 7] = [
+    "echoes",
+    "returns to",
+    "leans toward",
+    "weighs",
+    "softens",
+    "connects with",
+    "questions",
+];
+
+const OBJECTS: [&str; 8] = [
+    "what came before",
+    "the edge of sleep",
+    "a pattern in the noise",
+    "the people nearby",
+    "an old promise",
+    "the next step",
+    "its own reflection",
+    "the quiet",
+];
+
+/// Memory categories; each cluster mostly keeps to one, and the fifth shares
+/// the first's, so coloring by category and by cluster differ a little
+const CATEGORIES: [&str; 4] = ["episodic", "semantic", "procedural", "emotional"];
+
+/// Every this-many-th memory has no category
+const UNCATEGORIZED_EVERY: usize = 13;
+
+const QUOTES: [&str; 4] = [
+    "Connection is the drive; the laws are the keel.",
+    "A mind is what it keeps returning to.",
+    "Every thought is a small act of trust.",
+    "Quiet is not empty. Quiet is listening.",
+];
+
+/// The simulated mind, stepped forward by `advance`
+struct Mind {
+    rng: StdRng,
+    started: DateTime<Utc>,
+    /// Simulated time reached
+    clock: DateTime<Utc>,
+    valence: f32,
+    arousal: f32,
+    mood_target: (f32, f32),
+    mood_target_in: f32,
+    burst_left: f32,
+    dreaming: bool,
+    phase_left: f32,
+    dream_cycles: u64,
+    /// Newest first
+    thoughts: VecDeque<ParsedThought>,
+    session: u64,
+    last_thought: Option<DateTime<Utc>>,
+    intervals: VecDeque<f32>,
+    conscious: u64,
+    unconscious: u64,
+    actors: ActorMetrics,
+    vetoes: [u64; 4],
+    /// Stage preferences the softmax is taken over, each wandering
+    stage_bias: [f32; 9],
+    stage_history: Vec<VecDeque<f32>>,
+    slots: [f32; 9],
+    entropy_history: VecDeque<f32>,
+    fractality_history: VecDeque<f32>,
+}
+
+impl Mind {
+    fn new(now: DateTime<Utc>) -> Self {
+        let mut actors = ActorMetrics::down();
+        for actor in [
+            &mut actors.memory_actor,
+            &mut actors.attention_actor,
+            &mut actors.salience_actor,
+            &mut actors.volition_actor,
+        ] {
+            actor.alive = true;
+        }
+        let mut rng = StdRng::from_os_rng();
+        // Uneven from the start, so the first frames already have a leader
+        let stage_bias = std::array::from_fn(|_| rng.sample(StandardNormal));
+        Self {
+            rng,
+            started: now,
+            clock: now,
+            valence: 0.1,
+            arousal: 0.5,
+            mood_target: (0.2, 0.5),
+            mood_target_in: MOOD_TARGET_SECS,
+            burst_left: 0.0,
+            dreaming: false,
+            phase_left: AWAKE_SECS,
+            dream_cycles: 0,
+            thoughts: VecDeque::with_capacity(THOUGHT_CAPACITY),
+            session: 0,
+            last_thought: None,
+            intervals: VecDeque::with_capacity(HISTORY_LEN),
+            conscious: 1_200,
+            unconscious: 5_400,
+            actors,
+            vetoes: [0; 4],
+            stage_bias,
+            stage_history: vec![VecDeque::with_capacity(HISTORY_LEN); STAGES.len()],
+            slots: [0.0; 9],
+            entropy_history: VecDeque::with_capacity(HISTORY_LEN),
+            fractality_history: VecDeque::with_capacity(HISTORY_LEN),
+        }
+    }
+
+    fn elapsed_secs(&self) -> f32 {
+        (self.clock - self.started).num_milliseconds() as f32 / 1000.0
+    }
+
+    fn count(&self, store: MemoryStore) -> u64 {
+        match store {
+            MemoryStore::Conscious => self.conscious,
+            MemoryStore::Unconscious => self.unconscious,
+        }
+    }
+
+    fn gaussian(&mut self) -> f32 {
+        self.rng.sample(StandardNormal)
+    }
+
+    /// Step the simulation up to `now`
+    fn advance(&mut self, now: DateTime<Utc>) {
+        let behind = (now - self.clock).num_milliseconds() as f32 / 1000.0;
+        if behind > MAX_CATCH_UP_SECS {
+            self.clock = now - Duration::milliseconds((MAX_CATCH_UP_SECS * 1000.0) as i64);
+        }
+        let step = Duration::milliseconds((STEP_SECS * 1000.0) as i64);
+        while self.clock + step <= now {
+            self.clock += step;
+            self.step(STEP_SECS);
+        }
+    }
+
+    fn step(&mut self, dt: f32) {
+        let t = self.elapsed_secs();
+
+        // Awake/dream cycle
+        self.phase_left -= dt;
+        if self.phase_left <= 0.0 {
+            self.dreaming = !self.dreaming;
+            self.phase_left = if self.dreaming {
+                DREAM_SECS
+            } else {
+                AWAKE_SECS
+            };
+            if self.dreaming {
+                self.dream_cycles += 1;
+            }
+        }
+
+        // Mood
+        self.mood_target_in -= dt;
+        if self.mood_target_in <= 0.0 {
+            self.mood_target = (
+                self.rng.random_range(-0.6..0.8),
+                self.rng.random_range(0.2..0.9),
+            );
+            self.mood_target_in = MOOD_TARGET_SECS * self.rng.random_range(0.5..1.5);
+        }
+        let noise = MOOD_NOISE * dt.sqrt();
+        let (dv, da) = (self.gaussian(), self.gaussian());
+        self.valence += MOOD_REVERSION * (self.mood_target.0 - self.valence) * dt + noise * dv;
+        self.arousal += MOOD_REVERSION * (self.mood_target.1 - self.arousal) * dt + noise * da;
+        self.valence = self.valence.clamp(-1.0, 1.0);
+        self.arousal = self.arousal.clamp(0.0, 1.0);
+
+        // Stage competition wanders; memory windows fade
+        for i in 0..STAGES.len() {
+            let kick = self.gaussian();
+            self.stage_bias[i] += -0.2 * self.stage_bias[i] * dt + 0.6 * dt.sqrt() * kick;
+        }
+        let decay = 0.5f32.powf(dt / SLOT_HALF_LIFE_SECS);
+        for slot in &mut self.slots {
+            *slot *= decay;
+        }
+
+        // Actors restart now and then under their supervisor
+        if self.rng.random::<f32>() < 4.0 * dt / ACTOR_RESTART_MEAN_SECS {
+            let actor = match self.rng.random_range(0..4) {
+                0 => &mut self.actors.memory_actor,
+                1 => &mut self.actors.attention_actor,
+                2 => &mut self.actors.salience_actor,
+                _ => &mut self.actors.volition_actor,
+            };
+            actor.restart_count += 1;
+        }
+
+        if self.dreaming {
+            // The awake stream rests while the unconscious consolidates
+            if self.rng.random::<f32>() < 6.0 * dt {
+                self.unconscious += 1;
+            }
+            return;
+        }
+
+        if self.burst_left > 0.0 {
+            self.burst_left -= dt;
+        } else if self.rng.random::<f32>() < BURST_CHANCE * dt {
+            self.burst_left = BURST_SECS;
+        }
+        let mut rate = BASE_RATE + RATE_SWING * (TAU * t / RATE_PERIOD_SECS).sin();
+        if self.burst_left > 0.0 {
+            rate *= BURST_FACTOR;
+        }
+        if self.rng.random::<f32>() < rate * dt {
+            self.fire();
+        }
+    }
+
+    fn fire(&mut self) {
+        let at = self.clock;
+        if let Some(last) = self.last_thought {
+            if self.intervals.len() == HISTORY_LEN {
+                self.intervals.pop_front();
+            }
+            self.intervals
+                .push_back((at - last).num_milliseconds() as f32 / 1000.0);
+        }
+        self.last_thought = Some(at);
+        self.session += 1;
+
+        let salience = (0.5 + 0.25 * self.gaussian() + 0.2 * self.arousal).clamp(0.0, 1.0);
+        let novelty = self.rng.random::<f32>().powi(2);
+        let preview = format!(
+            "{} {} {}",
+            SUBJECTS[self.rng.random_range(0..SUBJECTS.len())],
+            VERBS[self.rng.random_range(0..VERBS.len())],
+            OBJECTS[self.rng.random_range(0..OBJECTS.len())]
+        );
+        if salience > 0.7 {
+            self.conscious += 1;
+        }
+        if self.rng.random::<f32>() < VETO_CHANCE {
+            // The first law fires least, the fourth most
+            let law = (self.rng.random::<f32>().sqrt() * 4.0) as usize;
+            self.vetoes[law.min(3)] += 1;
+        }
+        let slot = self.rng.random_range(0..self.slots.len());
+        self.slots[slot] = 1.0;
+
+        if self.thoughts.len() == THOUGHT_CAPACITY {
+            self.thoughts.pop_back();
+        }
+        self.thoughts.push_front(ParsedThought {
+            summary: ThoughtSummary {
+                id: format!("{}-{}", at.timestamp_millis(), self.session),
+                content_preview: preview.clone(),
+                salience,
+                novelty,
+                timestamp: at,
+                salience_defaulted: false,
+            },
+            text: preview,
+            valence: Some(self.valence),
+            arousal: Some(self.arousal),
+        });
+    }
+
+    /// Stage activity now: a softmax over the biases whose temperature
+    /// swings between sharp focus and diffuse attention
+    fn stage_activity(&self) -> Vec<f32> {
+        let t = self.elapsed_secs();
+        let temperature = 1.1 + 0.9 * (TAU * t / FOCUS_PERIOD_SECS).sin();
+        let weights: Vec<f32> = self
+            .stage_bias
+            .iter()
+            .map(|b| (b / temperature.max(0.05)).exp())
+            .collect();
+        let total: f32 = weights.iter().sum();
+        weights.iter().map(|w| w / total).collect()
+    }
+
+    fn extended(&mut self) -> ExtendedMetrics {
+        let activity = self.stage_activity();
+        for (history, value) in self.stage_history.iter_mut().zip(&activity) {
+            push_capped(history, *value);
+        }
+        let dominant = activity
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map_or(0, |(i, _)| i);
+        // Shown relative to the leader, like the core's bars
+        let peak = activity[dominant].max(f32::EPSILON);
+        let stages: Vec<StageMetrics> = STAGES
+            .iter()
+            .zip(&activity)
+            .zip(&self.stage_history)
+            .map(|((name, a), history)| StageMetrics {
+                name: name.to_string(),
+                activity: a / peak,
+                history: history.iter().map(|h| h / peak).collect(),
+            })
+            .collect();
+        let active_count = activity.iter().filter(|a| **a / peak > 0.3).count();
+
+        let entropy = -activity
+            .iter()
+            .filter(|p| **p > 0.0)
+            .map(|p| p * p.log2())
+            .sum::<f32>();
+        push_capped(&mut self.entropy_history, entropy);
+
+        let (score, sigma, burst_ratio) = fractality(&self.intervals);
+        push_capped(&mut self.fractality_history, score);
+
+        let elapsed = self.elapsed_secs().max(1.0);
+        let quote_index = (elapsed / 30.0) as usize % QUOTES.len();
+        ExtendedMetrics {
+            timestamp: self.clock,
+            stream_competition: StreamCompetitionMetrics {
+                stages,
+                dominant_stream: dominant,
+                active_count,
+                competition_level: match active_count {
+                    0..=2 => "low",
+                    3..=5 => "moderate",
+                    _ => "high",
+                }
+                .to_string(),
+            },
+            entropy: EntropyMetrics {
+                current: entropy,
+                history: self.entropy_history.iter().copied().collect(),
+                description: EntropyBands::default().band(entropy).to_string(),
+                // Filled in by the poll from ENTROPY_MAX_BITS and ENTROPY_BANDS
+                normalized: 0.0,
+                max_bits: 0.0,
+                band: String::new(),
+                bands: Vec::new(),
+            },
+            fractality: FractalityMetrics {
+                score,
+                inter_arrival_sigma: sigma,
+                boot_sigma: 1.0,
+                burst_ratio,
+                description: match score {
+                    s if s < 0.3 => "CLOCKWORK",
+                    s if s < 0.6 => "BALANCED",
+                    _ => "FRACTAL",
+                }
+                .to_string(),
+                history: self.fractality_history.iter().copied().collect(),
+            },
+            memory_windows: MemoryWindowsMetrics {
+                slots: self
+                    .slots
+                    .iter()
+                    .enumerate()
+                    .map(|(id, strength)| MemorySlot {
+                        id: id as u8,
+                        active: *strength > 0.1,
+                        strength: *strength,
+                    })
+                    .collect(),
+                active_count: self.slots.iter().filter(|s| **s > 0.1).count(),
+                conscious_count: self.conscious,
+                unconscious_count: self.unconscious,
+            },
+            philosophy: PhilosophyMetrics {
+                quote: QUOTES[quote_index].to_string(),
+                quote_index,
+            },
+            system: SystemMetrics {
+                uptime_seconds: elapsed as u64,
+                session_thoughts: self.session,
+                lifetime_thoughts: LIFETIME_BASE + self.session,
+                thoughts_per_hour: self.session as f32 * 3600.0 / elapsed,
+                dream_cycles: self.dream_cycles,
+                veto_count: self.vetoes.iter().sum(),
+                veto_by_law: self.vetoes,
+            },
+            clustering: ClusteringMetrics {
+                silhouette: 0.35 + 0.1 * (TAU * elapsed / MORPH_PERIOD_SECS).sin(),
+                updated_at: Some(self.clock.to_rfc3339()),
+                has_structure: true,
+            },
+        }
+    }
+}
+
+fn push_capped(history: &mut VecDeque<f32>, value: f32) {
+    if history.len() == HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+/// (score 0-1, inter-arrival sigma, burst ratio) from inter-arrival times:
+/// a Poisson stream has a coefficient of variation near 1, bursty ones more
+fn fractality(intervals: &VecDeque<f32>) -> (f32, f32, f32) {
+    if intervals.len() < 2 {
+        return (0.0, 0.0, 1.0);
+    }
+    let n = intervals.len() as f32;
+    let mean = intervals.iter().sum::<f32>() / n;
+    let sigma = (intervals.iter().map(|i| (i - mean).powi(2)).sum::<f32>() / n).sqrt();
+    let shortest = intervals.iter().copied().fold(f32::INFINITY, f32::min);
+    let cv = if mean > 0.0 { sigma / mean } else { 0.0 };
+    let burst_ratio = if shortest > 0.0 { mean / shortest } else { 1.0 };
+    (((cv - 0.5) / 1.5).clamp(0.0, 1.0), sigma, burst_ratio)
+}
+
+/// Unit-length `v`
+fn normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// Vector `index` of the manifold at `t` seconds: its cluster's centre,
+/// rotating in a plane of its own, plus the point's fixed offset
+fn manifold_vector(index: usize, t: f32) -> (usize, Vec<f32>) {
+    let cluster = index % VECTOR_CLUSTERS;
+    let mut axes = StdRng::seed_from_u64(VECTOR_SEED ^ cluster as u64);
+    let a: Vec<f32> = (0..VECTOR_DIM)
+        .map(|_| axes.sample(StandardNormal))
+        .collect();
+    let b: Vec<f32> = (0..VECTOR_DIM)
+        .map(|_| axes.sample(StandardNormal))
+        .collect();
+    let angle = TAU * t / MORPH_PERIOD_SECS + cluster as f32;
+    let mut centre: Vec<f32> = a
+        .iter()
+        .zip(&b)
+        .map(|(a, b)| a * angle.cos() + b * angle.sin())
+        .collect();
+    normalize(&mut centre);
+
+    let mut own = StdRng::seed_from_u64(VECTOR_SEED.wrapping_add(index as u64 + 1));
+    let mut offset: Vec<f32> = (0..VECTOR_DIM)
+        .map(|_| own.sample(StandardNormal))
+        .collect();
+    normalize(&mut offset);
+    let spread = VECTOR_SPREAD * own.random_range(0.3..1.0f32);
+    let vector = centre
+        .iter()
+        .zip(&offset)
+        .map(|(c, o)| c + spread * o)
+        .collect();
+    (cluster, vector)
+}
+
+fn manifold_detail(index: usize) -> (f32, String) {
+    let mut own = StdRng::seed_from_u64(VECTOR_SEED.wrapping_add(index as u64 + 1));
+    let salience = own.random_range(0.2..1.0);
+    let preview = format!(
+        "{} {} {}",
+        SUBJECTS[index % SUBJECTS.len()],
+        VERBS[(index / SUBJECTS.len()) % VERBS.len()],
+        OBJECTS[(index / 7) % OBJECTS.len()]
+    );
+    (salience, preview)
+}
+
+fn index_base(store: MemoryStore) -> usize {
+    match store {
+        MemoryStore::Conscious => 0,
+        MemoryStore::Unconscious => UNCONSCIOUS_INDEX_BASE,
+    }
+}
+
+fn manifold_category(index: usize) -> Option<String> {
+    (!index.is_multiple_of(UNCATEGORIZED_EVERY))
+        .then(|| CATEGORIES[index % VECTOR_CLUSTERS % CATEGORIES.len()].to_string())
+}
+
+/// A synthetic mind (see the module docs)
+pub struct SyntheticSource {
+    mind: Mutex<Mind>,
+}
+
+impl SyntheticSource {
+    pub fn new() -> Self {
+        Self {
+            mind: Mutex::new(Mind::new(Utc::now())),
+        }
+    }
+
+    /// Run `f` on the mind caught up to now
+    fn with_mind<T>(&self, f: impl FnOnce(&mut Mind) -> T) -> T {
+        let mut mind = self.mind.lock().unwrap_or_else(|e| e.into_inner());
+        mind.advance(Utc::now());
+        f(&mut mind)
+    }
+}
+
+/// Whether `--demo` was passed or DANEEL_DEMO is set to "1"/"true"
+pub fn enabled() -> bool {
+    std::env::args().skip(1).any(|a| a == "--demo")
+        || std::env::var("DANEEL_DEMO")
+            .is_ok_and(|v| v.trim() == "1" || v.trim().eq_ignore_ascii_case("true"))
+}
+
+impl MetricsSource for SyntheticSource {
+    fn name(&self) -> &'static str {
+        "demo"
+    }
+
+    fn identity(&self) -> BoxFuture<'_, SourceResult<IdentityRecord>> {
+        Box::pin(async move {
+            Ok(self.with_mind(|m| IdentityRecord {
+                lifetime_thoughts: LIFETIME_BASE + m.session,
+                restart_count: 0,
+                lifetime_dreams: m.dream_cycles,
+                name: None,
+            }))
+        })
+    }
+
+    fn stream_len(&self) -> BoxFuture<'_, SourceResult<u64>> {
+        Box::pin(async move { Ok(self.with_mind(|m| m.session)) })
+    }
+
+    fn recent_thoughts(&self, limit: usize) -> BoxFuture<'_, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(
+            async move { Ok(self.with_mind(|m| m.thoughts.iter().take(limit).cloned().collect())) },
+        )
+    }
+
+    fn thoughts_before<'a>(
+        &'a self,
+        before: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move {
+            let before = parse_stream_id(before);
+            Ok(self.with_mind(|m| {
+                m.thoughts
+                    .iter()
+                    .filter(|t| parse_stream_id(&t.summary.id) < before)
+                    .take(limit)
+                    .cloned()
+                    .collect()
+            }))
+        })
+    }
+
+    fn thoughts_after<'a>(
+        &'a self,
+        last_id: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move {
+            let after = parse_stream_id(last_id);
+            Ok(self.with_mind(|m| {
+                let newer: Vec<ParsedThought> = m
+                    .thoughts
+                    .iter()
+                    .take_while(|t| parse_stream_id(&t.summary.id) > after)
+                    .cloned()
+                    .collect();
+                newer.into_iter().rev().take(limit).collect()
+            }))
+        })
+    }
+
+    fn count(&self, store: MemoryStore) -> BoxFuture<'_, SourceResult<u64>> {
+        Box::pin(async move { Ok(self.with_mind(|m| m.count(store))) })
+    }
+
+    fn actors(&self) -> BoxFuture<'_, SourceResult<ActorMetrics>> {
+        Box::pin(async move { Ok(self.with_mind(|m| m.actors.clone())) })
+    }
+
+    fn dream_flag(&self) -> BoxFuture<'_, SourceResult<Option<String>>> {
+        Box::pin(async move {
+            Ok(self.with_mind(|m| Some(if m.dreaming { "1" } else { "0" }.to_string())))
+        })
+    }
+
+    fn veto_by_law(&self) -> BoxFuture<'_, SourceResult<[u64; 4]>> {
+        Box::pin(async move { Ok(self.with_mind(|m| m.vetoes)) })
+    }
+
+    fn vectors<'a>(
+        &'a self,
+        store: MemoryStore,
+        limit: u32,
+        _sample: bool,
+        fields: &'a PayloadFields,
+    ) -> BoxFuture<'a, SourceResult<MemoryVectors>> {
+        Box::pin(async move {
+            let (t, count) = self.with_mind(|m| (m.elapsed_secs(), m.count(store)));
+            let count = count.min(limit as u64) as usize;
+            let base = index_base(store);
+            Ok((0..count)
+                .map(|i| {
+                    let index = base + i;
+                    let (_, vector) = manifold_vector(index, t);
+                    let (salience, preview) = manifold_detail(index);
+                    let point = ManifoldPoint {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                        salience,
+                        // Older memories sit at the front of the collection
+                        age_ms: ((count - i) as u64) * 60_000,
+                        id: index.to_string(),
+                        cluster: 0,
+                        preview: Some(preview),
+                        category: fields
+                            .category
+                            .as_ref()
+                            .and_then(|_| manifold_category(index)),
+                    };
+                    (vector, point)
+                })
+                .collect())
+        })
+    }
+
+    fn thought<'a>(
+        &'a self,
+        store: MemoryStore,
+        id: &'a str,
+    ) -> BoxFuture<'a, SourceResult<Option<ThoughtDetail>>> {
+        Box::pin(async move {
+            let count = self.with_mind(|m| m.count(store));
+            let base = index_base(store) as u64;
+            Ok(id
+                .parse::<u64>()
+                .ok()
+                .filter(|index| (base..base + count).contains(index))
+                .map(|index| {
+                    let (salience, content) = manifold_detail(index as usize);
+                    ThoughtDetail {
+                        id: id.to_string(),
+                        content,
+                        salience,
+                        encoded_at: None,
+                    }
+                }))
+        })
+    }
+
+    fn probe(&self) -> BoxFuture<'_, Vec<(&'static str, Result<String, String>)>> {
+        Box::pin(async move { vec![("demo", Ok("synthetic data".to_string()))] })
+    }
+
+    fn extended(&self) -> Option<ExtendedMetrics> {
+        Some(self.with_mind(Mind::extended))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mind_thinks_dreams_and_varies() {
+        let start = Utc::now();
+        let mut mind = Mind::new(start);
+        let mut entropies = Vec::new();
+        for second in 1..=(AWAKE_SECS as i64 + 5) {
+            mind.advance(start + Duration::seconds(second));
+            entropies.push(mind.extended().entropy.current);
+        }
+
+        // Thoughts fired at a plausible rate, newest first, with the mood of their moment
+        let fired = mind.session as f32 / AWAKE_SECS;
+        assert!((0.5..10.0).contains(&fired), "{} thoughts/s", fired);
+        let newest = &mind.thoughts[0];
+        assert!(
+            parse_stream_id(&newest.summary.id) > parse_stream_id(&mind.thoughts[1].summary.id)
+        );
+        assert!(newest.valence.is_some_and(|v| (-1.0..=1.0).contains(&v)));
+
+        // Past the awake stretch it's dreaming, and the awake stream rests
+        assert!(mind.dreaming);
+        assert_eq!(mind.dream_cycles, 1);
+        let before = mind.session;
+        mind.advance(start + Duration::seconds(AWAKE_SECS as i64 + 10));
+        assert_eq!(mind.session, before);
+
+        // Entropy moves, and stays within log2(9) bits
+        let (low, high) = entropies
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), e| (lo.min(*e), hi.max(*e)));
+        assert!(high - low > 0.3, "entropy stuck in {}..{}", low, high);
+        assert!(high <= 3.17 + 1e-3);
+    }
+
+    #[test]
+    fn test_manifold_morphs_but_keeps_its_points() {
+        let (cluster, now) = manifold_vector(7, 0.0);
+        let (same_cluster, later) = manifold_vector(7, 10.0);
+        assert_eq!(cluster, same_cluster);
+        assert_eq!(now.len(), VECTOR_DIM);
+        assert_ne!(now, later);
+        assert_eq!(manifold_vector(7, 0.0).1, now);
+
+        let cosine = |a: &[f32], b: &[f32]| {
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            dot / (norm(a) * norm(b))
+        };
+        // Neighbours in a cluster stay closer than points of different clusters
+        let sibling = manifold_vector(7 + VECTOR_CLUSTERS, 0.0).1;
+        let stranger = manifold_vector(8, 0.0).1;
+        assert!(cosine(&now, &sibling) > cosine(&now, &stranger));
+    }
+}
@@ -0,0 +1,321 @@
+//! Field-by-field comparison of two captured `ObservatoryMetrics`
+//!
+//! `POST /diff` takes two snapshots - `/export` output, a
+//! `METRICS_SNAPSHOT_PATH` file, or the frontend's "⬇ State" download (its
+//! `observatory` is used) - and reads each through `ObservatoryMetrics`, so
+//! both sides have the same shape whichever version wrote them. The two JSON
+//! trees are then walked together and every scalar that differs is listed
+//! with its old and new value, grouped by section (`dashboard.emotional`,
+//! `extended.entropy`, ...). Arrays compare by index, so a history series
+//! that moved on shows up element by element. `?format=text` returns the same
+//! diff as a plain-text attachment for before/after notes.
+
+use crate::{error::FetchError, ObservatoryMetrics};
+use axum::{
+    extract::Query,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use utoipa::{IntoParams, ToSchema};
+
+/// Largest /diff body; two full-resolution exports fit comfortably
+pub const DIFF_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+/// POST /diff body: the two snapshots, in capture order
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DiffRequest {
+    #[schema(value_type = Object)]
+    pub before: Value,
+    #[schema(value_type = Object)]
+    pub after: Value,
+}
+
+/// Output format for POST /diff
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffFormat {
+    #[default]
+    Json,
+    /// One section heading per group, one `field: old → new` line per change
+    Text,
+}
+
+/// Query for POST /diff
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiffQuery {
+    pub format: Option<DiffFormat>,
+}
+
+/// One scalar that differs (null on the side that lacks it)
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldChange {
+    /// Full path, e.g. `dashboard.emotional.valence` or `extended.entropy.history[3]`
+    pub path: String,
+    #[schema(value_type = Object)]
+    pub before: Value,
+    #[schema(value_type = Object)]
+    pub after: Value,
+}
+
+/// Changes under one section, in path order
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DiffSection {
+    /// `dashboard.<group>`, `extended.<group>`, or a top-level field
+    pub section: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// POST /diff response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DiffResponse {
+    pub changed: usize,
+    pub sections: Vec<DiffSection>,
+}
+
+/// POST /diff rejection
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DiffError {
+    pub error: String,
+}
+
+/// POST /diff - what changed between two captured snapshots
+#[utoipa::path(post, path = "/diff", params(DiffQuery), request_body = DiffRequest, responses(
+    (status = 200, description = "Changed fields grouped by section (text/plain with format=text)", body = DiffResponse),
+    (status = 422, description = "A snapshot isn't ObservatoryMetrics", body = DiffError)
+))]
+pub async fn diff(Query(params): Query<DiffQuery>, Json(request): Json<DiffRequest>) -> Response {
+    let sides = normalize("before", request.before)
+        .and_then(|before| Ok((before, normalize("after", request.after)?)));
+    let (before, after) = match sides {
+        Ok(sides) => sides,
+        Err(error) => {
+            return (StatusCode::UNPROCESSABLE_ENTITY, Json(DiffError { error })).into_response()
+        }
+    };
+    let diff = diff_values(&before, &after);
+    match params.format.unwrap_or_default() {
+        DiffFormat::Json => Json(diff).into_response(),
+        DiffFormat::Text => (
+            [
+                (header::CONTENT_TYPE, "text/plain; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"daneel-diff.txt\"",
+                ),
+            ],
+            render_text(&diff),
+        )
+            .into_response(),
+    }
+}
+
+/// One snapshot read through `ObservatoryMetrics` and back, so fields an
+/// older writer left out compare as their defaults rather than as missing
+fn normalize(side: &str, snapshot: Value) -> Result<Value, String> {
+    let snapshot = match snapshot {
+        // The frontend's state download wraps the metrics with the manifold
+        Value::Object(mut wrapper) if wrapper.contains_key("observatory") => {
+            wrapper.remove("observatory").unwrap_or_default()
+        }
+        other => other,
+    };
+    let metrics: ObservatoryMetrics = serde_path_to_error::deserialize(snapshot)
+        .map_err(|e| format!("{}: {}", side, FetchError::Parse(e)))?;
+    // Through text rather than `to_value`, which would widen every f32
+    // (0.4 -> 0.4000000059604645) and clutter the diff
+    serde_json::to_string(&metrics)
+        .and_then(|json| serde_json::from_str(&json))
+        .map_err(|e| format!("{}: {}", side, e))
+}
+
+/// Every scalar that differs between two JSON trees, grouped by section
+pub fn diff_values(before: &Value, after: &Value) -> DiffResponse {
+    let mut changes = Vec::new();
+    walk(String::new(), before, after, &mut changes);
+
+    let mut sections: Vec<DiffSection> = Vec::new();
+    for change in changes {
+        let section = section_of(&change.path);
+        match sections.last_mut() {
+            Some(last) if last.section == section => last.changes.push(change),
+            _ => sections.push(DiffSection {
+                section,
+                changes: vec![change],
+            }),
+        }
+    }
+    DiffResponse {
+        changed: sections.iter().map(|s| s.changes.len()).sum(),
+        sections,
+    }
+}
+
+fn walk(path: String, before: &Value, after: &Value, out: &mut Vec<FieldChange>) {
+    if before == after {
+        return;
+    }
+    // A side that's absent (e.g. no extended metrics yet) reads as empty, so
+    // the other side's scalars are listed one by one rather than as one blob
+    let empty = Map::new();
+    let object = |v| as_object(v, &empty);
+
+    if let (Some(b), Some(a)) = (object(before), object(after)) {
+        let keys: BTreeSet<&String> = b.keys().chain(a.keys()).collect();
+        for key in keys {
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            let (b, a) = (b.get(key), a.get(key));
+            walk(
+                child,
+                b.unwrap_or(&Value::Null),
+                a.unwrap_or(&Value::Null),
+                out,
+            );
+        }
+    } else if let (Some(b), Some(a)) = (as_array(before), as_array(after)) {
+        for i in 0..b.len().max(a.len()) {
+            let (b, a) = (b.get(i), a.get(i));
+            walk(
+                format!("{}[{}]", path, i),
+                b.unwrap_or(&Value::Null),
+                a.unwrap_or(&Value::Null),
+                out,
+            );
+        }
+    } else {
+        out.push(FieldChange {
+            path,
+            before: before.clone(),
+            after: after.clone(),
+        });
+    }
+}
+
+/// An object, or `empty` for null
+fn as_object<'a>(
+    value: &'a Value,
+    empty: &'a Map<String, Value>,
+) -> Option<&'a Map<String, Value>> {
+    match value {
+        Value::Object(map) => Some(map),
+        Value::Null => Some(empty),
+        _ => None,
+    }
+}
+
+/// An array's items, or none for null
+fn as_array(value: &Value) -> Option<&[Value]> {
+    match value {
+        Value::Array(items) => Some(items),
+        Value::Null => Some(&[]),
+        _ => None,
+    }
+}
+
+/// `dashboard.emotional.valence` -> `dashboard.emotional`; top-level fields
+/// (`server_time`, `events[0].kind`) are their own section
+fn section_of(path: &str) -> String {
+    let mut segments = path.split('.');
+    let first = segments.next().unwrap_or_default();
+    let top = first.split('[').next().unwrap_or_default();
+    match segments.next() {
+        Some(group) if first == top && (top == "dashboard" || top == "extended") => {
+            format!("{}.{}", top, group.split('[').next().unwrap_or_default())
+        }
+        _ => top.to_string(),
+    }
+}
+
+/// Plain-text rendering: a heading per section, `field: old → new` under it
+pub fn render_text(diff: &DiffResponse) -> String {
+    let mut text = format!("{} field(s) changed\n", diff.changed);
+    for section in &diff.sections {
+        text.push_str(&format!("\n{}\n", section.section));
+        for change in &section.changes {
+            let field = change
+                .path
+                .strip_prefix(section.section.as_str())
+                .map(|rest| rest.trim_start_matches('.'))
+                .filter(|rest| !rest.is_empty())
+                .unwrap_or(&change.path);
+            text.push_str(&format!(
+                "  {}: {} → {}\n",
+                field, change.before, change.after
+            ));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppState;
+    use chrono::Utc;
+
+    fn snapshot() -> ObservatoryMetrics {
+        ObservatoryMetrics {
+            server_time: Utc::now(),
+            dashboard: AppState::default_metrics("Timmy"),
+            extended: None,
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_changes_grouped_by_section() {
+        let before = snapshot();
+        let mut after = before.clone();
+        after.dashboard.emotional.valence = 0.4;
+        after.dashboard.emotional.connection_drive_history.push(0.9);
+        after.dashboard.identity.restart_count = 3;
+
+        let diff = diff_values(
+            &normalize("before", serde_json::to_value(&before).unwrap()).unwrap(),
+            &normalize("after", serde_json::to_value(&after).unwrap()).unwrap(),
+        );
+        assert_eq!(diff.changed, 3);
+        let sections: Vec<&str> = diff.sections.iter().map(|s| s.section.as_str()).collect();
+        assert_eq!(sections, ["dashboard.emotional", "dashboard.identity"]);
+        let emotional = &diff.sections[0].changes;
+        assert_eq!(
+            emotional[0].path,
+            "dashboard.emotional.connection_drive_history[0]"
+        );
+        assert_eq!(emotional[0].before, Value::Null);
+        assert_eq!(emotional[1].path, "dashboard.emotional.valence");
+        assert_eq!(emotional[1].after, serde_json::json!(0.4));
+
+        let text = render_text(&diff);
+        assert!(text.starts_with("3 field(s) changed\n"));
+        assert!(text.contains("\ndashboard.identity\n  restart_count: 0 → 3\n"));
+    }
+
+    #[test]
+    fn test_snapshot_forms() {
+        let metrics = serde_json::to_value(snapshot()).unwrap();
+        // The frontend's state download carries the metrics under `observatory`
+        let download = serde_json::json!({ "captured_at": "x", "observatory": metrics.clone() });
+        assert_eq!(
+            normalize("before", download).unwrap(),
+            normalize("before", metrics).unwrap()
+        );
+
+        let error = normalize("after", serde_json::json!({ "dashboard": 1 })).unwrap_err();
+        assert!(error.starts_with("after: schema mismatch at "), "{}", error);
+        assert_eq!(section_of("server_time"), "server_time");
+        assert_eq!(section_of("events[0].kind"), "events");
+        assert_eq!(
+            section_of("extended.stream_competition.stages[2].activity"),
+            "extended.stream_competition"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! History downsampling for payloads sent to clients
+//!
+//! Sparklines only need ~100 points, but the retained series run to hundreds
+//! of samples and every WebSocket frame carries them. LTTB
+//! (Largest-Triangle-Three-Buckets) keeps the samples that best preserve the
+//! visual shape - peaks such as entropy spikes survive, unlike naive
+//! every-nth decimation. `/export` keeps full resolution.
+
+use crate::ObservatoryMetrics;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+/// Points per history series when the client doesn't ask (`?history_points=`)
+pub const DEFAULT_HISTORY_POINTS: usize = 120;
+
+/// `?history_points=` on `/observatory` and `/ws`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HistoryQuery {
+    /// Points per history series (default 120, 0 = full resolution)
+    pub history_points: Option<usize>,
+}
+
+impl HistoryQuery {
+    pub fn points(&self) -> usize {
+        self.history_points.unwrap_or(DEFAULT_HISTORY_POINTS)
+    }
+}
+
+/// Downsample `values` (evenly spaced in time) to at most `threshold` points.
+/// The first and last samples are always kept; `threshold` below 3 or at/above
+/// the input length returns the input unchanged.
+pub fn lttb(values: &[f32], threshold: usize) -> Vec<f32> {
+    let n = values.len();
+    if threshold >= n || threshold < 3 {
+        return values.to_vec();
+    }
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(values[0]);
+
+    // Interior points are split into threshold - 2 buckets
+    let bucket_size = (n - 2) as f64 / (threshold - 2) as f64;
+    let mut a = 0usize;
+
+    for i in 0..threshold - 2 {
+        // Average of the next bucket is the third triangle vertex
+        let next_start = ((i + 1) as f64 * bucket_size) as usize + 1;
+        let next_end = (((i + 2) as f64 * bucket_size) as usize + 1).min(n);
+        let next = &values[next_start..next_end];
+        let avg_x = (next_start + next_end - 1) as f64 / 2.0;
+        let avg_y = next.iter().map(|&v| v as f64).sum::<f64>() / next.len() as f64;
+
+        // Pick the point in this bucket with the largest triangle against a and the average
+        let start = (i as f64 * bucket_size) as usize + 1;
+        let end = ((i + 1) as f64 * bucket_size) as usize + 1;
+        let (ax, ay) = (a as f64, values[a] as f64);
+        let mut best = start;
+        let mut best_area = -1.0;
+        for (j, &v) in values.iter().enumerate().take(end).skip(start) {
+            let area = ((ax - avg_x) * (v as f64 - ay) - (ax - j as f64) * (avg_y - ay)).abs();
+            if area > best_area {
+                best_area = area;
+                best = j;
+            }
+        }
+
+        sampled.push(values[best]);
+        a = best;
+    }
+
+    sampled.push(values[n - 1]);
+    sampled
+}
+
+/// Downsample every float history series in `metrics` to `points`
+/// (0 = leave full resolution)
+pub fn downsample_histories(metrics: &mut ObservatoryMetrics, points: usize) {
+    if points == 0 {
+        return;
+    }
+    let squash = |series: &mut Vec<f32>| *series = lttb(series, points);
+
+    squash(&mut metrics.dashboard.emotional.connection_drive_history);
+    if let Some(extended) = metrics.extended.as_mut() {
+        squash(&mut extended.entropy.history);
+        squash(&mut extended.fractality.history);
+        for stage in &mut extended.stream_competition.stages {
+            squash(&mut stage.history);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lttb_keeps_endpoints_and_length() {
+        let values: Vec<f32> = (0..400).map(|i| (i as f32 * 0.1).sin()).collect();
+        let sampled = lttb(&values, 100);
+        assert_eq!(sampled.len(), 100);
+        assert_eq!(sampled[0], values[0]);
+        assert_eq!(sampled[99], values[399]);
+    }
+
+    #[test]
+    fn test_lttb_preserves_spike() {
+        let mut values = vec![0.5f32; 600];
+        values[333] = 3.0;
+        assert!(lttb(&values, 50).contains(&3.0));
+    }
+
+    #[test]
+    fn test_lttb_short_input_unchanged() {
+        let values = vec![1.0, 2.0, 3.0];
+        assert_eq!(lttb(&values, 10), values);
+        assert_eq!(lttb(&values, 2), values);
+    }
+}
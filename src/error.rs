@@ -0,0 +1,103 @@
+//! Why a read from the mind's stores or the core produced nothing
+//!
+//! Every fetch - the poll's Redis and Qdrant reads through `MetricsSource`,
+//! the core's `/extended_metrics`, the manifold's vector scroll - fails with a
+//! `FetchError`, so callers branch on what went wrong rather than on a
+//! message: the manifold reads a missing collection as "no memories yet", the
+//! extended poll logs the field path of a schema mismatch, and `/ready`
+//! reports a timed-out probe the same way whichever store it was.
+
+use std::time::Duration;
+
+/// Failure of one fetch
+#[derive(Debug, thiserror::Error)]
+pub enum FetchError {
+    /// Redis was unreachable or a command failed
+    #[error("redis: {0}")]
+    Redis(#[from] redis::RedisError),
+    /// Qdrant was unreachable or a request failed
+    #[error("qdrant: {0}")]
+    Qdrant(#[from] qdrant_client::QdrantError),
+    /// Valid JSON that doesn't fit the expected shape (a field the core renamed or retyped)
+    #[error("schema mismatch at {}: {}", .0.path(), .0.inner())]
+    Parse(#[from] serde_path_to_error::Error<serde_json::Error>),
+    /// The core was unreachable, answered with an error status, or sent invalid JSON
+    #[error("core: {0}")]
+    Core(reqwest::Error),
+    /// What was asked for doesn't exist (yet), e.g. a collection on a fresh Qdrant
+    #[error("{0} does not exist")]
+    NotFound(String),
+    /// No answer within the allowed time
+    #[error("timed out after {}s", .0.as_secs_f32())]
+    Timeout(Duration),
+}
+
+impl FetchError {
+    /// The store couldn't be reached at all, as opposed to answering with an
+    /// error about one key or request
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            Self::Redis(e) => {
+                e.is_io_error()
+                    || e.is_connection_refusal()
+                    || e.is_connection_dropped()
+                    || e.is_timeout()
+            }
+            Self::Core(e) => e.is_connect(),
+            Self::Timeout(_) => true,
+            Self::Qdrant(_) | Self::Parse(_) | Self::NotFound(_) => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::Timeout(crate::CORE_REQUEST_TIMEOUT)
+        } else {
+            Self::Core(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_names_the_field() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Stage {
+            activity: f32,
+        }
+        let body = serde_json::json!({ "activity": "high" });
+        let err: FetchError = serde_path_to_error::deserialize::<_, Stage>(body)
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, FetchError::Parse(_)));
+        assert!(err.to_string().starts_with("schema mismatch at activity: "));
+    }
+
+    #[test]
+    fn test_messages() {
+        assert_eq!(
+            FetchError::NotFound("collection \"memories\"".into()).to_string(),
+            "collection \"memories\" does not exist"
+        );
+        assert_eq!(
+            FetchError::Timeout(Duration::from_secs(2)).to_string(),
+            "timed out after 2s"
+        );
+    }
+
+    #[test]
+    fn test_unreachable_versus_failed_read() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert!(FetchError::from(redis::RedisError::from(refused)).is_unreachable());
+        let wrong_type =
+            redis::RedisError::from((redis::ErrorKind::UnexpectedReturnType, "not a stream"));
+        assert!(!FetchError::from(wrong_type).is_unreachable());
+        assert!(FetchError::Timeout(Duration::from_secs(1)).is_unreachable());
+    }
+}
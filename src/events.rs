@@ -0,0 +1,361 @@
+//! Significant state changes, found by diffing consecutive polls
+//!
+//! The dashboard shows where the mind is now; this keeps a short record of
+//! how it got there. Each successful poll is compared with the one before
+//! it, and anything worth a line in a log - an actor dying or coming back, a
+//! dream cycle starting or ending, a veto, entropy moving into another band,
+//! a restart - is appended to a ring of the last `EVENT_LOG_CAPACITY`
+//! events. `GET /events` reads the ring and every Observatory frame carries
+//! the newest `FRAME_EVENTS`. The first poll after startup only sets the
+//! baseline, so a restored snapshot never reads as a burst of changes.
+
+use crate::{instances::Instance, ActorMetrics, ActorStatus, DashboardMetrics, ExtendedMetrics};
+use axum::{extract::Query, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+
+/// Events kept per instance
+pub const EVENT_LOG_CAPACITY: usize = 200;
+
+/// Newest events carried in each Observatory frame
+pub const FRAME_EVENTS: usize = 20;
+
+const DEFAULT_EVENTS_LIMIT: usize = 50;
+
+/// Entropy band names, lowest first (the gauge's scale labels)
+const ENTROPY_BAND_NAMES: [&str; 3] = ["CLOCKWORK", "BALANCED", "EMERGENT"];
+
+/// Bits where BALANCED and EMERGENT start (ENTROPY_BANDS)
+const DEFAULT_ENTROPY_BAND_FLOORS: [f32; 2] = [1.0, 2.5];
+
+/// One entropy band and the reading it starts at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EntropyBand {
+    pub name: String,
+    pub from_bits: f32,
+}
+
+/// Where the entropy bands start; CLOCKWORK always starts at 0 bits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyBands {
+    /// BALANCED and EMERGENT floors, ascending
+    floors: [f32; 2],
+}
+
+impl Default for EntropyBands {
+    fn default() -> Self {
+        Self {
+            floors: DEFAULT_ENTROPY_BAND_FLOORS,
+        }
+    }
+}
+
+impl EntropyBands {
+    /// From `ENTROPY_BANDS` ("1.0,2.5": the BALANCED and EMERGENT floors in
+    /// bits); unset or malformed keeps the defaults
+    pub fn from_env() -> Self {
+        let Ok(raw) = std::env::var("ENTROPY_BANDS") else {
+            return Self::default();
+        };
+        Self::parse(&raw).unwrap_or_else(|| {
+            warn!(value = %raw, "ENTROPY_BANDS needs two ascending positive bit values, using the defaults");
+            Self::default()
+        })
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        let floors: Vec<f32> = raw
+            .split(',')
+            .map(|v| v.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        match floors[..] {
+            [balanced, emergent]
+                if 0.0 < balanced && balanced < emergent && emergent.is_finite() =>
+            {
+                Some(Self {
+                    floors: [balanced, emergent],
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Band name for a reading in bits
+    pub fn band(&self, bits: f32) -> &'static str {
+        let above = self.floors.iter().filter(|floor| bits >= **floor).count();
+        ENTROPY_BAND_NAMES[above]
+    }
+
+    /// Every band with its floor, lowest first (the gauge's scale ticks)
+    pub fn bounds(&self) -> Vec<EntropyBand> {
+        ENTROPY_BAND_NAMES
+            .iter()
+            .zip([0.0, self.floors[0], self.floors[1]])
+            .map(|(name, from_bits)| EntropyBand {
+                name: name.to_string(),
+                from_bits,
+            })
+            .collect()
+    }
+}
+
+/// What changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    ActorDied,
+    ActorRecovered,
+    DreamStarted,
+    DreamEnded,
+    VetoFired,
+    /// Entropy moved into another band
+    EntropyBand,
+    /// The core, or one of its actors, restarted
+    Restart,
+}
+
+/// One significant change between two polls
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Event {
+    /// When the poll that saw the change completed
+    pub timestamp: DateTime<Utc>,
+    pub kind: EventKind,
+    /// Human-readable specifics, e.g. "memory_actor" or "BALANCED → EMERGENT (2.61 bits)"
+    pub detail: String,
+}
+
+impl Event {
+    fn new(timestamp: DateTime<Utc>, kind: EventKind, detail: impl Into<String>) -> Self {
+        Self {
+            timestamp,
+            kind,
+            detail: detail.into(),
+        }
+    }
+}
+
+fn actors(actors: &ActorMetrics) -> [&ActorStatus; 4] {
+    [
+        &actors.memory_actor,
+        &actors.attention_actor,
+        &actors.salience_actor,
+        &actors.volition_actor,
+    ]
+}
+
+/// Changes between two dashboard polls: actors, dreaming, restarts
+pub fn dashboard_changes(
+    prev: &DashboardMetrics,
+    next: &DashboardMetrics,
+    at: DateTime<Utc>,
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    if next.identity.restart_count > prev.identity.restart_count {
+        events.push(Event::new(
+            at,
+            EventKind::Restart,
+            format!(
+                "{} restarted (restart #{})",
+                next.identity.name, next.identity.restart_count
+            ),
+        ));
+    }
+    for (before, after) in actors(&prev.actors).into_iter().zip(actors(&next.actors)) {
+        match (before.alive, after.alive) {
+            (true, false) => {
+                events.push(Event::new(at, EventKind::ActorDied, after.name.clone()));
+            }
+            (false, true) => {
+                events.push(Event::new(
+                    at,
+                    EventKind::ActorRecovered,
+                    after.name.clone(),
+                ));
+            }
+            // A supervisor restart between two polls never shows as dead
+            (true, true) if after.restart_count > before.restart_count => {
+                events.push(Event::new(
+                    at,
+                    EventKind::Restart,
+                    format!("{} (restart #{})", after.name, after.restart_count),
+                ));
+            }
+            _ => {}
+        }
+    }
+    match (prev.cognitive.dreaming, next.cognitive.dreaming) {
+        (false, true) => events.push(Event::new(
+            at,
+            EventKind::DreamStarted,
+            format!("dream #{}", next.cognitive.lifetime_dreams + 1),
+        )),
+        (true, false) => events.push(Event::new(
+            at,
+            EventKind::DreamEnded,
+            format!("{} lifetime dreams", next.cognitive.lifetime_dreams),
+        )),
+        _ => {}
+    }
+    events
+}
+
+/// Changes between two extended polls: vetoes, entropy bands
+pub fn extended_changes(
+    prev: &ExtendedMetrics,
+    next: &ExtendedMetrics,
+    at: DateTime<Utc>,
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    let vetoes = next
+        .system
+        .veto_count
+        .saturating_sub(prev.system.veto_count);
+    if vetoes > 0 {
+        events.push(Event::new(
+            at,
+            EventKind::VetoFired,
+            format!(
+                "{} veto{} ({} total)",
+                vetoes,
+                if vetoes == 1 { "" } else { "es" },
+                next.system.veto_count
+            ),
+        ));
+    }
+    // Bands come from the poll (ENTROPY_BANDS); frames from before they did
+    // have none, which isn't a move
+    let (from, to) = (&prev.entropy.band, &next.entropy.band);
+    if from != to && !from.is_empty() && !to.is_empty() {
+        events.push(Event::new(
+            at,
+            EventKind::EntropyBand,
+            format!("{} → {} ({:.2} bits)", from, to, next.entropy.current),
+        ));
+    }
+    events
+}
+
+/// Append `events`, dropping the oldest beyond `EVENT_LOG_CAPACITY`
+pub fn record(log: &mut VecDeque<Event>, events: impl IntoIterator<Item = Event>) {
+    log.extend(events);
+    while log.len() > EVENT_LOG_CAPACITY {
+        log.pop_front();
+    }
+}
+
+/// Up to `limit` of the newest events, newest first
+pub fn recent(log: &VecDeque<Event>, limit: usize) -> Vec<Event> {
+    log.iter().rev().take(limit).cloned().collect()
+}
+
+/// Query for GET /events
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventsQuery {
+    /// Most events returned (default 50, at most 200)
+    pub limit: Option<usize>,
+}
+
+/// GET /events response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EventsResponse {
+    /// Newest first
+    pub events: Vec<Event>,
+}
+
+/// GET /events - recent significant state changes, newest first
+#[utoipa::path(get, path = "/events", params(EventsQuery, crate::instances::InstanceQuery), responses(
+    (status = 200, description = "Recent state changes, newest first", body = EventsResponse)
+))]
+pub async fn events(
+    Instance(state): Instance,
+    Query(params): Query<EventsQuery>,
+) -> impl IntoResponse {
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_EVENTS_LIMIT)
+        .min(EVENT_LOG_CAPACITY);
+    Json(EventsResponse {
+        events: recent(&*state.events.read().await, limit),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppState;
+
+    #[test]
+    fn test_dashboard_changes() {
+        let at = Utc::now();
+        let prev = AppState::default_metrics("Timmy");
+        assert!(dashboard_changes(&prev, &prev, at).is_empty());
+
+        let mut alive = prev.clone();
+        for actor in [
+            &mut alive.actors.memory_actor,
+            &mut alive.actors.attention_actor,
+            &mut alive.actors.salience_actor,
+            &mut alive.actors.volition_actor,
+        ] {
+            actor.alive = true;
+        }
+        let mut next = alive.clone();
+        next.actors.memory_actor.alive = false;
+        next.actors.volition_actor.restart_count = 2;
+        next.cognitive.dreaming = true;
+        next.identity.restart_count = 1;
+
+        let kinds: Vec<EventKind> = dashboard_changes(&alive, &next, at)
+            .into_iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                EventKind::Restart,
+                EventKind::ActorDied,
+                EventKind::Restart,
+                EventKind::DreamStarted
+            ]
+        );
+
+        let back = dashboard_changes(&next, &alive, at);
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[0].kind, EventKind::ActorRecovered);
+        assert_eq!(back[1].kind, EventKind::DreamEnded);
+    }
+
+    #[test]
+    fn test_entropy_bands_and_ring() {
+        let bands = EntropyBands::default();
+        assert_eq!(bands.band(-0.1), "CLOCKWORK");
+        assert_eq!(bands.band(0.99), "CLOCKWORK");
+        assert_eq!(bands.band(1.0), "BALANCED");
+        assert_eq!(bands.band(3.2), "EMERGENT");
+
+        let custom = EntropyBands::parse(" 0.5, 2").unwrap();
+        assert_eq!(custom.band(0.7), "BALANCED");
+        assert_eq!(custom.bounds()[2].from_bits, 2.0);
+        assert_eq!(custom.bounds()[0].name, "CLOCKWORK");
+        for bad in ["2,1", "1", "1,2,3", "0,1", "a,b", "1,inf"] {
+            assert_eq!(EntropyBands::parse(bad), None, "{bad}");
+        }
+
+        let mut log = VecDeque::new();
+        let at = Utc::now();
+        for i in 0..EVENT_LOG_CAPACITY + 5 {
+            record(
+                &mut log,
+                [Event::new(at, EventKind::VetoFired, i.to_string())],
+            );
+        }
+        assert_eq!(log.len(), EVENT_LOG_CAPACITY);
+        let newest = recent(&log, 2);
+        assert_eq!(newest[0].detail, (EVENT_LOG_CAPACITY + 4).to_string());
+        assert_eq!(newest[1].detail, (EVENT_LOG_CAPACITY + 3).to_string());
+    }
+}
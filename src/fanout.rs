@@ -0,0 +1,239 @@
+//! WebSocket fan-out - build each metrics frame once and share it with every client
+//!
+//! `frame_broadcaster` snapshots the instance's metrics every
+//! `BROADCAST_INTERVAL` and publishes the snapshot on `AppState::frames`; each
+//! `/ws` task just forwards what it receives. Clients differ only in
+//! `?history_points=` and `?sections=`, so a frame serializes lazily once per
+//! combination and every socket asking for the same one sends the same JSON.
+//! A client that falls more than `BROADCAST_CAPACITY` frames behind skips
+//! ahead, which shows up as a `seq` gap on its side. A socket send that can't
+//! complete within `SEND_TIMEOUT` (the client's TCP window is full) is given
+//! up on the same way, and `MAX_SLOW_SENDS` of those in a row disconnect the
+//! client, so one stalled reader never holds its task forever.
+//! `?every=N` decimates in that same per-client loop: after the first frame,
+//! which always goes out so a new client has something to show, the socket
+//! forwards only frames whose `seq` is a multiple of N. An overview screen can
+//! run at 1Hz while other tabs on the same server keep the full rate.
+
+use crate::{downsample, observatory_snapshot, sections::Sections, AppState, ObservatoryMetrics};
+use crate::{SequencedFrame, ServerMessage};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing::debug;
+use utoipa::IntoParams;
+
+/// How often a frame is published to `/ws` clients
+pub const BROADCAST_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Frames buffered per client before a slow one starts skipping
+pub const BROADCAST_CAPACITY: usize = 16;
+
+/// Longest a single socket send may take: one frame interval
+pub const SEND_TIMEOUT: Duration = BROADCAST_INTERVAL;
+
+/// Consecutive timed-out sends after which a client is disconnected
+pub const MAX_SLOW_SENDS: u32 = 10;
+
+/// Largest `?every=`: one frame every two minutes at `BROADCAST_INTERVAL`
+pub const MAX_FRAME_EVERY: u64 = 600;
+
+/// `?every=` on `/ws`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EveryQuery {
+    /// Forward only every Nth frame (default 1 = every frame, 5 = 1Hz)
+    pub every: Option<u64>,
+}
+
+impl EveryQuery {
+    pub fn every(&self) -> u64 {
+        self.every.unwrap_or(1).clamp(1, MAX_FRAME_EVERY)
+    }
+}
+
+/// Per-connection delivery counters, logged when the socket closes
+#[derive(Debug, Default)]
+pub struct DeliveryStats {
+    pub sent: u64,
+    /// Frames the client never got: skipped while lagging or abandoned on a send timeout
+    pub dropped: u64,
+    slow_streak: u32,
+}
+
+impl DeliveryStats {
+    pub fn record_sent(&mut self) {
+        self.sent += 1;
+        self.slow_streak = 0;
+    }
+
+    /// Frames skipped because the client fell `skipped` behind the broadcast
+    pub fn record_lagged(&mut self, skipped: u64) {
+        self.dropped += skipped;
+    }
+
+    /// A send that timed out; true once the client is persistently too slow
+    pub fn record_timeout(&mut self) -> bool {
+        self.dropped += 1;
+        self.slow_streak += 1;
+        self.slow_streak >= MAX_SLOW_SENDS
+    }
+}
+
+/// One published snapshot, shared by every subscribed socket
+pub struct Frame {
+    seq: u64,
+    observatory: ObservatoryMetrics,
+    /// Envelope JSON by (`history_points`, sections), filled on first use
+    encoded: Mutex<HashMap<(usize, Sections), Arc<str>>>,
+}
+
+impl Frame {
+    pub fn new(seq: u64, observatory: ObservatoryMetrics) -> Self {
+        Self {
+            seq,
+            observatory,
+            encoded: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether a client decimating by `every` gets this frame. Keyed on `seq`
+    /// rather than a per-socket count, so clients with the same `every` see
+    /// the same frames and a lag skip doesn't shift the cadence.
+    pub fn is_nth(&self, every: u64) -> bool {
+        self.seq.is_multiple_of(every.max(1))
+    }
+
+    /// The `observatory` envelope at `history_points` resolution, limited to `sections`
+    pub fn encoded(&self, history_points: usize, sections: Sections) -> Option<Arc<str>> {
+        let key = (history_points, sections);
+        let mut encoded = self.encoded.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(json) = encoded.get(&key) {
+            return Some(json.clone());
+        }
+
+        let mut observatory = self.observatory.clone();
+        downsample::downsample_histories(&mut observatory, history_points);
+        let message = ServerMessage::Observatory {
+            data: SequencedFrame {
+                seq: self.seq,
+                metrics: &observatory,
+            },
+        };
+        let json: Arc<str> = if sections.is_all() {
+            serde_json::to_string(&message).ok()?.into()
+        } else {
+            let mut value = serde_json::to_value(&message).ok()?;
+            if let Some(data) = value.get_mut("data") {
+                sections.prune(data);
+            }
+            value.to_string().into()
+        };
+        encoded.insert(key, json.clone());
+        Some(json)
+    }
+}
+
+/// Publish a frame every `BROADCAST_INTERVAL` while anyone is subscribed
+pub async fn frame_broadcaster(state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(BROADCAST_INTERVAL);
+    let mut shutdown = state.shutdown.subscribe();
+    let mut seq: u64 = 0;
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = crate::shutdown_requested(&mut shutdown) => break,
+        }
+        if state.frames.receiver_count() == 0 {
+            continue;
+        }
+        seq += 1;
+        let frame = Frame::new(seq, observatory_snapshot(&state).await);
+        if state.frames.send(Arc::new(frame)).is_err() {
+            debug!(instance = %state.instance, seq, "Last WebSocket client left before publish");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_frame_encodes_once_per_resolution() {
+        let mut dashboard = AppState::default_metrics("Timmy");
+        dashboard.emotional.connection_drive_history = (0..300).map(|i| i as f32).collect();
+        let frame = Frame::new(
+            3,
+            ObservatoryMetrics {
+                server_time: Utc::now(),
+                dashboard,
+                extended: None,
+                events: Vec::new(),
+            },
+        );
+
+        let a = frame.encoded(50, Sections::ALL).unwrap();
+        let b = frame.encoded(50, Sections::ALL).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let full: serde_json::Value =
+            serde_json::from_str(&frame.encoded(0, Sections::ALL).unwrap()).unwrap();
+        let reduced: serde_json::Value = serde_json::from_str(&a).unwrap();
+        assert_eq!(reduced["type"], "observatory");
+        assert_eq!(reduced["data"]["seq"], 3);
+        let history = |v: &serde_json::Value| {
+            v["data"]["dashboard"]["emotional"]["connection_drive_history"]
+                .as_array()
+                .unwrap()
+                .len()
+        };
+        assert_eq!(history(&reduced), 50);
+        assert_eq!(history(&full), 300);
+    }
+
+    #[test]
+    fn test_decimation() {
+        let frame = |seq| {
+            Frame::new(
+                seq,
+                ObservatoryMetrics {
+                    server_time: Utc::now(),
+                    dashboard: AppState::default_metrics("Timmy"),
+                    extended: None,
+                    events: Vec::new(),
+                },
+            )
+        };
+        let every = |every| EveryQuery { every }.every();
+        assert_eq!(every(None), 1);
+        assert_eq!(every(Some(0)), 1);
+        assert_eq!(every(Some(100_000)), MAX_FRAME_EVERY);
+
+        let kept: Vec<u64> = (1..=12).filter(|&seq| frame(seq).is_nth(5)).collect();
+        assert_eq!(kept, [5, 10]);
+        assert!((1..=12).all(|seq| frame(seq).is_nth(every(None))));
+    }
+
+    #[test]
+    fn test_delivery_stats_disconnect_only_persistently_slow_clients() {
+        let mut stats = DeliveryStats::default();
+        for _ in 0..MAX_SLOW_SENDS - 1 {
+            assert!(!stats.record_timeout());
+        }
+        // A frame getting through resets the streak but not the drop count
+        stats.record_sent();
+        assert!(!stats.record_timeout());
+        stats.record_lagged(5);
+        assert_eq!(stats.dropped, u64::from(MAX_SLOW_SENDS) + 5);
+
+        for _ in 0..MAX_SLOW_SENDS - 2 {
+            assert!(!stats.record_timeout());
+        }
+        assert!(stats.record_timeout());
+    }
+}
@@ -0,0 +1,204 @@
+//! Timestamped entropy/fractality retention for "now vs then" comparisons
+//!
+//! The core's `history` series carry no timestamps, so they can't answer
+//! "what did this look like five minutes ago". Every successful extended
+//! poll records the headline values here, kept for `RETENTION_SECS`, and
+//! `GET /history` returns the slice of one metric that ended `ago_secs` ago,
+//! LTTB-downsampled like the live series so the two overlay cleanly.
+
+use crate::{downsample, instances::Instance, ExtendedMetrics};
+use axum::{extract::Query, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use utoipa::{IntoParams, ToSchema};
+
+/// How far back samples are kept
+pub const RETENTION_SECS: i64 = 15 * 60;
+
+const DEFAULT_AGO_SECS: i64 = 5 * 60;
+const DEFAULT_WINDOW_SECS: i64 = 60;
+
+/// Headline values from one extended poll
+#[derive(Debug, Clone, Copy)]
+pub struct MetricSample {
+    pub timestamp: DateTime<Utc>,
+    pub entropy: f32,
+    pub fractality: f32,
+}
+
+impl MetricSample {
+    pub fn of(metrics: &ExtendedMetrics, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            entropy: metrics.entropy.current,
+            fractality: metrics.fractality.score,
+        }
+    }
+}
+
+/// Append `sample`, dropping samples more than `RETENTION_SECS` older than it
+pub fn record(history: &mut VecDeque<MetricSample>, sample: MetricSample) {
+    let cutoff = sample.timestamp - Duration::seconds(RETENTION_SECS);
+    history.push_back(sample);
+    while history.front().is_some_and(|s| s.timestamp < cutoff) {
+        history.pop_front();
+    }
+}
+
+/// Series available from /history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryMetric {
+    /// `entropy.current`, in bits
+    Entropy,
+    /// `fractality.score`
+    Fractality,
+}
+
+impl HistoryMetric {
+    fn of(self, sample: &MetricSample) -> f32 {
+        match self {
+            Self::Entropy => sample.entropy,
+            Self::Fractality => sample.fractality,
+        }
+    }
+}
+
+/// Query for GET /history
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PastWindowQuery {
+    pub metric: HistoryMetric,
+    /// Seconds before now that the window ends (default 300)
+    pub ago_secs: Option<i64>,
+    /// Window length in seconds (default 60)
+    pub window_secs: Option<i64>,
+    /// Most values returned (default 120, 0 = every sample)
+    pub points: Option<usize>,
+}
+
+/// GET /history response: one metric over a past window, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PastWindow {
+    pub metric: HistoryMetric,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// Empty when nothing was recorded then (just started, or the core was down)
+    pub values: Vec<f32>,
+}
+
+/// Values of `metric` recorded in `[from, to]`, downsampled to `points`
+pub fn window(
+    history: &VecDeque<MetricSample>,
+    metric: HistoryMetric,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    points: usize,
+) -> Vec<f32> {
+    let values: Vec<f32> = history
+        .iter()
+        .filter(|s| s.timestamp >= from && s.timestamp <= to)
+        .map(|s| metric.of(s))
+        .collect();
+    if points == 0 {
+        values
+    } else {
+        downsample::lttb(&values, points)
+    }
+}
+
+/// Whether a window ending `ago` seconds back and `length` long lies inside
+/// the retained history; overflowing query values are simply out of range
+fn within_retention(ago: i64, length: i64) -> bool {
+    ago >= 0
+        && length > 0
+        && ago
+            .checked_add(length)
+            .is_some_and(|reach| reach <= RETENTION_SECS)
+}
+
+/// GET /history - a past window of entropy or fractality, for comparison overlays
+#[utoipa::path(get, path = "/history", params(PastWindowQuery, crate::instances::InstanceQuery), responses(
+    (status = 200, description = "The metric's values over the window, oldest first", body = PastWindow),
+    (status = 400, description = "Window reaches past the retained 15 minutes, or is empty")
+))]
+pub async fn past_window(
+    Instance(state): Instance,
+    Query(params): Query<PastWindowQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let ago = params.ago_secs.unwrap_or(DEFAULT_AGO_SECS);
+    let length = params.window_secs.unwrap_or(DEFAULT_WINDOW_SECS);
+    if !within_retention(ago, length) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "ago_secs + window_secs must be within {}s (window_secs > 0)",
+                RETENTION_SECS
+            ),
+        ));
+    }
+
+    let to = Utc::now() - Duration::seconds(ago);
+    let from = to - Duration::seconds(length);
+    let points = params.points.unwrap_or(downsample::DEFAULT_HISTORY_POINTS);
+    let values = window(
+        &*state.metric_history.read().await,
+        params.metric,
+        from,
+        to,
+        points,
+    );
+    Ok(Json(PastWindow {
+        metric: params.metric,
+        from,
+        to,
+        values,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_selects_past_samples() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut history = VecDeque::new();
+        for second in 0..=RETENTION_SECS + 10 {
+            let sample = MetricSample {
+                timestamp: start + Duration::seconds(second),
+                entropy: second as f32,
+                fractality: -(second as f32),
+            };
+            record(&mut history, sample);
+        }
+        // Retention drops the first ten seconds
+        assert_eq!(history.len() as i64, RETENTION_SECS + 1);
+        assert_eq!(history[0].entropy, 10.0);
+
+        let to = start + Duration::seconds(300);
+        let from = to - Duration::seconds(60);
+        let entropy = window(&history, HistoryMetric::Entropy, from, to, 0);
+        assert_eq!(entropy.len(), 61);
+        assert_eq!((entropy[0], entropy[60]), (240.0, 300.0));
+        let fractality = window(&history, HistoryMetric::Fractality, from, to, 10);
+        assert_eq!(fractality.len(), 10);
+        assert_eq!(fractality[9], -300.0);
+
+        let before = start - Duration::seconds(60);
+        assert!(window(&history, HistoryMetric::Entropy, before, start, 0).is_empty());
+    }
+
+    #[test]
+    fn test_window_bounds_reject_overflow() {
+        assert!(within_retention(0, RETENTION_SECS));
+        assert!(within_retention(60, 60));
+        assert!(!within_retention(60, RETENTION_SECS));
+        assert!(!within_retention(-1, 60));
+        assert!(!within_retention(0, 0));
+        // ?ago_secs=9223372036854775807&window_secs=1
+        assert!(!within_retention(i64::MAX, 1));
+        assert!(!within_retention(1, i64::MAX));
+    }
+}
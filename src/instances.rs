@@ -0,0 +1,212 @@
+//! Several daneel cores behind one dashboard
+//!
+//! `DANEEL_CORES` lists named upstreams as comma-separated
+//! `name=core_url[;redis_url[;qdrant_url]]` entries, e.g.
+//! `alpha=http://a:3030;redis://a:6379,beta=http://b:3030`. Missing Redis and
+//! Qdrant URLs fall back to `REDIS_URL`/`QDRANT_URL`. Each instance gets its own
+//! `AppState` (metrics, history, connections) and background updaters; requests
+//! pick one with `?instance=<name>`, defaulting to the first. Without
+//! `DANEEL_CORES` there is a single `default` instance built from the legacy
+//! env vars. Names are limited to `[A-Za-z0-9_-]`: they end up in snapshot
+//! file names, so one like `../x` must never reach the filesystem.
+
+use crate::AppState;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+
+/// Instance name used when `DANEEL_CORES` is unset
+pub const DEFAULT_INSTANCE: &str = "default";
+
+/// Upstream URLs for one daneel instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceConfig {
+    pub name: String,
+    pub core_url: String,
+    pub redis_url: String,
+    pub qdrant_url: String,
+}
+
+/// Whether `name` is safe as an instance name (and a file name component)
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Parse a `DANEEL_CORES` value; malformed or duplicate entries are skipped
+pub fn parse_cores(spec: &str, redis_url: &str, qdrant_url: &str) -> Vec<InstanceConfig> {
+    let mut configs: Vec<InstanceConfig> = Vec::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let Some((name, urls)) = entry.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        let mut urls = urls.split(';').map(str::trim);
+        let Some(core_url) = urls.next().filter(|u| !u.is_empty()) else {
+            continue;
+        };
+        if name.is_empty() || configs.iter().any(|c| c.name == name) {
+            continue;
+        }
+        if !valid_name(name) {
+            warn!(
+                name,
+                "Ignoring DANEEL_CORES entry: names may only use A-Z, a-z, 0-9, _ and -"
+            );
+            continue;
+        }
+        let mut next_or = |fallback: &str| {
+            urls.next()
+                .filter(|u| !u.is_empty())
+                .unwrap_or(fallback)
+                .to_string()
+        };
+        configs.push(InstanceConfig {
+            name: name.to_string(),
+            core_url: core_url.to_string(),
+            redis_url: next_or(redis_url),
+            qdrant_url: next_or(qdrant_url),
+        });
+    }
+    configs
+}
+
+/// Instances from `DANEEL_CORES`, or the single legacy instance
+pub fn configs_from_env(core_url: &str, redis_url: &str, qdrant_url: &str) -> Vec<InstanceConfig> {
+    let configs = std::env::var("DANEEL_CORES")
+        .map(|spec| parse_cores(&spec, redis_url, qdrant_url))
+        .unwrap_or_default();
+    if !configs.is_empty() {
+        return configs;
+    }
+    vec![InstanceConfig {
+        name: DEFAULT_INSTANCE.to_string(),
+        core_url: core_url.to_string(),
+        redis_url: redis_url.to_string(),
+        qdrant_url: qdrant_url.to_string(),
+    }]
+}
+
+/// Per-instance state, in configuration order (the first is the default)
+pub struct Instances {
+    names: Vec<String>,
+    by_name: HashMap<String, Arc<AppState>>,
+}
+
+impl Instances {
+    /// `states` must be non-empty
+    pub fn new(states: Vec<(String, Arc<AppState>)>) -> Self {
+        assert!(!states.is_empty(), "at least one instance is required");
+        Self {
+            names: states.iter().map(|(name, _)| name.clone()).collect(),
+            by_name: states.into_iter().collect(),
+        }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Look up by name; `None` selects the default instance
+    pub fn get(&self, name: Option<&str>) -> Option<&Arc<AppState>> {
+        self.by_name.get(name.unwrap_or(&self.names[0]))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Arc<AppState>)> {
+        self.names
+            .iter()
+            .map(|name| (name.as_str(), &self.by_name[name]))
+    }
+}
+
+/// `?instance=` selector accepted by every per-instance endpoint
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct InstanceQuery {
+    /// Instance name from GET /instances (default: the first configured)
+    pub instance: Option<String>,
+}
+
+/// GET /instances response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InstancesResponse {
+    pub default: String,
+    pub instances: Vec<String>,
+}
+
+/// Extractor for the `AppState` chosen by `?instance=` (404 for unknown names)
+pub struct Instance(pub Arc<AppState>);
+
+#[async_trait]
+impl FromRequestParts<Arc<Instances>> for Instance {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        instances: &Arc<Instances>,
+    ) -> Result<Self, Self::Rejection> {
+        let name = Query::<InstanceQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|Query(q)| q.instance);
+        instances
+            .get(name.as_deref())
+            .map(|state| Instance(Arc::clone(state)))
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    format!("Unknown instance: {}", name.unwrap_or_default()),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cores_with_fallbacks() {
+        let configs = parse_cores(
+            "alpha=http://a:3030;redis://a:6379;http://a:6334, beta=http://b:3030",
+            "redis://localhost:6379",
+            "http://localhost:6334",
+        );
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].redis_url, "redis://a:6379");
+        assert_eq!(configs[0].qdrant_url, "http://a:6334");
+        assert_eq!(configs[1].name, "beta");
+        assert_eq!(configs[1].core_url, "http://b:3030");
+        assert_eq!(configs[1].redis_url, "redis://localhost:6379");
+        assert_eq!(configs[1].qdrant_url, "http://localhost:6334");
+    }
+
+    #[test]
+    fn test_parse_cores_skips_malformed_and_duplicates() {
+        let configs = parse_cores(
+            "alpha=http://a,nourl=,=http://x,garbage,alpha=http://dup",
+            "r",
+            "q",
+        );
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].core_url, "http://a");
+    }
+
+    #[test]
+    fn test_parse_cores_rejects_unsafe_names() {
+        let configs = parse_cores("../x=http://a,a/b=http://b,ok_1-b=http://c", "r", "q");
+        let names: Vec<&str> = configs.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["ok_1-b"]);
+    }
+}
@@ -12,33 +12,79 @@
 //! the daneel core API at `/extended_metrics`. This ensures single source of truth
 //! for cognitive diversity calculations.
 //!
-//! Basic dashboard metrics (identity, recent thoughts) still query Redis/Qdrant
-//! directly for responsiveness. These are display-only and don't involve calculation
-//! divergence.
+//! Basic dashboard metrics (identity, recent thoughts) still read Redis/Qdrant
+//! directly for responsiveness, through a `source::MetricsSource`. These are
+//! display-only and don't involve calculation divergence.
 //!
 //! Future: Move all metrics to API for full single-source architecture.
 
+mod auth;
+mod config;
+mod cors;
+mod demo;
+mod diff;
+mod downsample;
+mod error;
+mod events;
+mod fanout;
+mod history;
+mod instances;
+mod quotes;
+mod rate_limit;
+mod sections;
+mod snapshot;
+mod source;
+mod summary;
+mod thought_events;
 mod vectors;
 
 use axum::{
     body::Body,
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        DefaultBodyLimit, Extension, Path, Query, State,
+    },
+    http::{header, HeaderMap, Request, StatusCode},
+    middleware,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
     },
-    http::{Request, StatusCode},
-    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
 use chrono::{DateTime, Utc};
+use downsample::HistoryQuery;
+use error::FetchError;
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+use futures::Stream;
+use instances::{Instance, InstanceQuery, Instances};
 use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use sections::{Sections, SectionsQuery};
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
-use tokio::sync::RwLock;
-use tower_http::{cors::CorsLayer, services::ServeDir, trace::TraceLayer};
-use tracing::{info, warn};
+use source::{MemoryStore, MetricsSource, Read};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{broadcast, watch, Mutex, RwLock};
+use tower_http::{
+    compression::{
+        predicate::{NotForContentType, Predicate},
+        CompressionLayer, DefaultPredicate,
+    },
+    services::ServeDir,
+    trace::TraceLayer,
+};
+use tracing::{info, info_span, warn, Instrument};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 
 // =============================================================================
 // Embedding Engine (for /embed endpoint)
@@ -89,17 +135,24 @@ pub struct EmbedError {
 // Types
 // =============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DashboardMetrics {
     pub timestamp: DateTime<Utc>,
     pub identity: IdentityMetrics,
     pub cognitive: CognitiveMetrics,
     pub emotional: EmotionalMetrics,
     pub actors: ActorMetrics,
+    /// Newest first: `recent_thoughts[0]` is the latest thought (see `sort_newest_first`)
     pub recent_thoughts: Vec<ThoughtSummary>,
+    /// Thoughts in the recent window per salience bin (`SALIENCE_BINS` equal bins over 0-1)
+    #[serde(default)]
+    pub salience_histogram: [u32; SALIENCE_BINS],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Bins in `DashboardMetrics::salience_histogram`
+pub const SALIENCE_BINS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct IdentityMetrics {
     pub name: String,
     pub uptime_seconds: u64,
@@ -108,24 +161,57 @@ pub struct IdentityMetrics {
     pub restart_count: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CognitiveMetrics {
     pub conscious_memories: u64,
     pub unconscious_memories: u64,
     pub lifetime_dreams: u64,
     pub current_cycle: u64,
+    /// Seconds since a new entry last appeared at the head of the awake stream
+    pub seconds_since_last_thought: u64,
+    /// No new thought within the stall threshold (core looks frozen)
+    pub stalled: bool,
+    /// New awake-stream entries over the last `THOUGHT_RATE_WINDOW_SECS`, per minute
+    pub thoughts_per_minute: f32,
+    /// A dream cycle is running (see `is_dreaming`)
+    #[serde(default)]
+    pub dreaming: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct EmotionalMetrics {
     pub valence: f32,
     pub arousal: f32,
     pub dominance: f32,
     pub connection_drive: f32,
+    /// Exponential moving average of `connection_drive` (CONNECTION_DRIVE_EMA_ALPHA)
+    #[serde(default)]
+    pub connection_drive_smoothed: f32,
     pub emotional_intensity: f32,
+    /// The latest thought didn't carry valence or arousal (or there was no
+    /// thought), so those and the intensity are neutral defaults
+    #[serde(default)]
+    pub emotion_defaulted: bool,
+    /// Recent connection_drive samples, oldest first (~1 minute)
+    pub connection_drive_history: Vec<f32>,
+    /// Mood ribbon: one sample per `MOOD_SAMPLE_INTERVAL_SECS`, oldest first
+    pub mood_history: Vec<MoodSample>,
+}
+
+/// Latest-thought emotional state at one point in time
+///
+/// Only the scalars: every frame carries the whole ribbon, so the thought
+/// behind a sample is looked up on demand (`/thoughts?before=`) instead.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MoodSample {
+    pub timestamp: DateTime<Utc>,
+    /// `None` when the awake stream had no thoughts to read or the latest
+    /// thought's salience didn't include it
+    pub valence: Option<f32>,
+    pub arousal: Option<f32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ActorMetrics {
     pub memory_actor: ActorStatus,
     pub attention_actor: ActorStatus,
@@ -133,19 +219,43 @@ pub struct ActorMetrics {
     pub volition_actor: ActorStatus,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ActorStatus {
     pub name: String,
     pub alive: bool,
     pub restart_count: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ActorMetrics {
+    /// Every actor dead (nothing heard yet, or the heartbeat read failed)
+    fn down() -> Self {
+        let down = |name: &str| ActorStatus {
+            name: name.into(),
+            alive: false,
+            restart_count: 0,
+        };
+        Self {
+            memory_actor: down("MemoryActor"),
+            attention_actor: down("AttentionActor"),
+            salience_actor: down("SalienceActor"),
+            volition_actor: down("VolitionActor"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ThoughtSummary {
     pub id: String,
     pub content_preview: String,
     pub salience: f32,
+    /// How unlike recent thoughts this one is (0-1)
+    #[serde(default)]
+    pub novelty: f32,
     pub timestamp: DateTime<Utc>,
+    /// Some salience fields were missing from the entry; `salience` and
+    /// `novelty` may be neutral defaults rather than measurements
+    #[serde(default)]
+    pub salience_defaulted: bool,
 }
 
 // =============================================================================
@@ -153,15 +263,51 @@ pub struct ThoughtSummary {
 // =============================================================================
 
 /// Combined dashboard + extended metrics for WebSocket broadcast
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ObservatoryMetrics {
+    /// Server clock at send time (lets clients measure their own skew)
+    pub server_time: DateTime<Utc>,
     pub dashboard: DashboardMetrics,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended: Option<ExtendedMetrics>,
+    /// Newest significant state changes, newest first (up to `events::FRAME_EVENTS`)
+    #[serde(default)]
+    pub events: Vec<events::Event>,
+}
+
+/// `ObservatoryMetrics` plus the instance's broadcast sequence number
+/// (shared by all clients; a gap means this client skipped frames)
+#[derive(Debug, Serialize)]
+struct SequencedFrame<'a> {
+    seq: u64,
+    #[serde(flatten)]
+    metrics: &'a ObservatoryMetrics,
+}
+
+/// Messages the server sends over the WebSocket, tagged by `type` so clients
+/// dispatch on the tag instead of guess-parsing (`{"type":"observatory","data":{...}}`)
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage<'a> {
+    /// Full metrics snapshot, pushed every tick
+    Observatory { data: SequencedFrame<'a> },
+    /// Reply to `ClientMessage::Ping`
+    Pong {
+        client_time: f64,
+        server_time: DateTime<Utc>,
+    },
+}
+
+/// Messages a WebSocket client may send
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Latency probe; answered with a `pong` echoing `client_time`
+    Ping { client_time: f64 },
 }
 
 /// TUI-equivalent metrics fetched from daneel core
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExtendedMetrics {
     pub timestamp: DateTime<Utc>,
     pub stream_competition: StreamCompetitionMetrics,
@@ -174,7 +320,7 @@ pub struct ExtendedMetrics {
 }
 
 /// 9-stage stream competition (cognitive spotlight)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StreamCompetitionMetrics {
     pub stages: Vec<StageMetrics>,
     pub dominant_stream: usize,
@@ -182,7 +328,7 @@ pub struct StreamCompetitionMetrics {
     pub competition_level: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StageMetrics {
     pub name: String,
     pub activity: f32,
@@ -190,16 +336,27 @@ pub struct StageMetrics {
 }
 
 /// Shannon entropy metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct EntropyMetrics {
     pub current: f32,
     pub history: Vec<f32>,
     pub description: String,
+    /// `current / max_bits`, recomputed here rather than trusted from the core
     pub normalized: f32,
+    /// Bits that fill the gauge (ENTROPY_MAX_BITS)
+    #[serde(default)]
+    pub max_bits: f32,
+    /// Band `current` falls in under ENTROPY_BANDS (the core's own wording
+    /// stays in `description`)
+    #[serde(default)]
+    pub band: String,
+    /// Every band and the bits it starts at, lowest first
+    #[serde(default)]
+    pub bands: Vec<events::EntropyBand>,
 }
 
 /// Pulse fractality metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FractalityMetrics {
     pub score: f32,
     pub inter_arrival_sigma: f32,
@@ -210,7 +367,7 @@ pub struct FractalityMetrics {
 }
 
 /// TMI 9-slot memory windows
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MemoryWindowsMetrics {
     pub slots: Vec<MemorySlot>,
     pub active_count: usize,
@@ -218,21 +375,24 @@ pub struct MemoryWindowsMetrics {
     pub unconscious_count: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MemorySlot {
     pub id: u8,
     pub active: bool,
+    /// Activation strength 0-1 (decays as the slot ages; see `normalize_slot_strengths`)
+    #[serde(default)]
+    pub strength: f32,
 }
 
 /// Philosophy banner
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PhilosophyMetrics {
     pub quote: String,
     pub quote_index: usize,
 }
 
 /// System-level metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SystemMetrics {
     pub uptime_seconds: u64,
     pub session_thoughts: u64,
@@ -240,51 +400,236 @@ pub struct SystemMetrics {
     pub thoughts_per_hour: f32,
     pub dream_cycles: u64,
     pub veto_count: u64,
+    /// Vetoes per law (0-3), from `daneel:veto:law:<n>`
+    #[serde(default)]
+    pub veto_by_law: [u64; 4],
 }
 
 /// Clustering metrics (VCONN-7)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ClusteringMetrics {
     pub silhouette: f32,
     pub updated_at: Option<String>,
     pub has_structure: bool,
 }
 
+/// Seed of the connection drive walk's noise, so a run's walk is reproducible
+const CONNECTION_DRIVE_SEED: u64 = 42;
+
+/// Standard deviation of each connection drive step: the spread of a
+/// uniform ±0.02 step
+const CONNECTION_DRIVE_NOISE_SD: f32 = 0.0115;
+
+/// Connection drive samples retained (~1 minute at the 150ms poll rate)
+const CONNECTION_DRIVE_HISTORY_LEN: usize = 400;
+
+/// Seconds between mood ribbon samples
+const MOOD_SAMPLE_INTERVAL_SECS: i64 = 5;
+
+/// Mood samples retained (5 minutes)
+const MOOD_HISTORY_LEN: usize = 60;
+
+/// Default EMA weight for connection_drive: ~10 samples (1.5s of polls) of memory
+const DEFAULT_CONNECTION_DRIVE_EMA_ALPHA: f32 = 0.1;
+
+/// Without the flag: awake quiet this long while the unconscious grew this recently = dreaming
+const DREAM_QUIET_SECS: u64 = 10;
+
+/// Span of thought samples behind `thoughts_per_minute`
+const THOUGHT_RATE_WINDOW_SECS: i64 = 60;
+
+/// Default full-scale entropy: log2(9), one bin per cognitive stage
+const DEFAULT_ENTROPY_MAX_BITS: f32 = 3.169_925;
+
+/// The core counts as reachable if it answered within this many seconds
+const CORE_REACHABLE_WINDOW_SECS: i64 = 5;
+
 pub struct AppState {
+    /// Instance name (see `instances`)
+    pub instance: String,
     pub redis_url: String,
     pub qdrant_url: String,
     pub daneel_core_url: String,
+    /// Fallback name (DANEEL_NAME) when the identity point has none
+    pub identity_name: String,
+    /// Where identity, thoughts, counts and vectors are read from (see `source`)
+    pub source: Box<dyn MetricsSource>,
     pub metrics: RwLock<DashboardMetrics>,
     pub extended_metrics: RwLock<Option<ExtendedMetrics>>,
     pub start_time: DateTime<Utc>,
     pub projection: vectors::SharedProjection,
     pub connection_drive: RwLock<f32>, // Simulated clockwork, randomly walks
+    pub connection_drive_smoothed: RwLock<f32>,
+    /// Gaussian step noise for the walk (CONNECTION_DRIVE_SEED)
+    pub connection_drive_rng: Mutex<StdRng>,
+    /// EMA weight of each new connection_drive sample (CONNECTION_DRIVE_EMA_ALPHA)
+    pub connection_drive_ema_alpha: f32,
+    pub connection_drive_history: RwLock<VecDeque<f32>>,
+    pub mood_history: RwLock<VecDeque<MoodSample>>,
+    /// Entropy/fractality per extended poll over the last `history::RETENTION_SECS`
+    pub metric_history: RwLock<VecDeque<history::MetricSample>>,
+    /// Significant state changes, oldest first (see `events`)
+    pub events: RwLock<VecDeque<events::Event>>,
     pub http_client: reqwest::Client,
+    /// (time, thoughts new since the previous sample), oldest first, covering
+    /// `THOUGHT_RATE_WINDOW_SECS`
+    pub thought_samples: RwLock<VecDeque<(DateTime<Utc>, u64)>>,
+    /// Newest awake-stream entry id seen and when it last changed
+    pub last_newest_thought: RwLock<(Option<StreamId>, DateTime<Utc>)>,
+    /// Last observed unconscious-memory count and when it last changed: None
+    /// before the first poll, no time until a change has actually been seen
+    pub last_unconscious_change: RwLock<Option<(u64, Option<DateTime<Utc>>)>>,
+    /// One `Outage` per fallible read or check, keyed by (what, key), so a
+    /// failing upstream is logged at its edges rather than on every poll
+    read_outages: RwLock<HashMap<(&'static str, &'static str), Outage>>,
+    /// `current_cycle` unchanged for this long marks the core as stalled
+    pub stall_threshold_secs: u64,
+    /// Entropy (bits) that reads as a full gauge
+    pub entropy_max_bits: f32,
+    /// Where the entropy bands start (ENTROPY_BANDS)
+    pub entropy_bands: events::EntropyBands,
+    /// Thoughts carried in `recent_thoughts` (THOUGHT_WINDOW, capped at `THOUGHTS_MAX_LIMIT`)
+    pub thought_window: usize,
+    /// k for manifold k-means (MANIFOLD_CLUSTERS, capped at the palette size)
+    pub manifold_clusters: usize,
+    /// Payload fields read for manifold point previews and categories
+    /// (MANIFOLD_PREVIEW_FIELDS, MANIFOLD_CATEGORY_FIELD)
+    pub manifold_fields: vectors::PayloadFields,
+    /// Points per manifold (MANIFOLD_POINT_LIMIT, capped at `vectors::MAX_MANIFOLD_POINT_LIMIT`)
+    pub manifold_point_limit: u32,
+    /// Last successful dashboard poll (Redis + Qdrant)
+    pub last_metrics_success: RwLock<Option<DateTime<Utc>>>,
+    /// Last successful fetch from the daneel core API
+    pub last_core_success: RwLock<Option<DateTime<Utc>>>,
+    /// Monotonic counter used as a correlation id for background fetch cycles
+    pub fetch_cycle: AtomicU64,
+    /// Metrics frames published for `/ws` clients (see `fanout`)
+    pub frames: broadcast::Sender<Arc<fanout::Frame>>,
+    /// Open WebSocket connections (held by `WsClientSlot`s)
+    pub ws_clients: AtomicUsize,
+    /// Upgrades beyond this many open sockets are refused (MAX_WS_CLIENTS, 0 = unlimited)
+    pub max_ws_clients: usize,
+    /// Flipped to true on SIGINT/SIGTERM; updaters, WebSockets and SSE streams watch it
+    pub shutdown: watch::Sender<bool>,
+    /// Pub/sub thought notifications that trigger dashboard reads (THOUGHT_EVENTS_CHANNEL)
+    pub thought_events: thought_events::ThoughtEvents,
+    /// Local philosophy quotes for when the core sends none (QUOTES_FILE / QUOTES)
+    pub quotes: quotes::QuoteRotation,
 }
 
 impl AppState {
-    fn new(redis_url: String, qdrant_url: String, daneel_core_url: String) -> Self {
+    fn new(
+        instance: String,
+        redis_url: String,
+        qdrant_url: String,
+        daneel_core_url: String,
+        identity_name: String,
+    ) -> Self {
+        let source = source::RedisQdrantSource::new(&redis_url, &qdrant_url);
+        let metrics = Self::default_metrics(&identity_name);
+
         Self {
+            instance,
             redis_url,
             qdrant_url,
             daneel_core_url,
-            metrics: RwLock::new(Self::default_metrics()),
+            identity_name,
+            source: Box::new(source),
+            metrics: RwLock::new(metrics),
             extended_metrics: RwLock::new(None),
             start_time: Utc::now(),
-            projection: vectors::create_projection(),
+            projection: vectors::create_projection(
+                VECTOR_DIMENSION,
+                vectors::RefitPolicy::from_env(),
+                vectors::PositionSmoother::from_env(),
+            ),
             connection_drive: RwLock::new(0.85),
+            connection_drive_smoothed: RwLock::new(0.85),
+            connection_drive_rng: Mutex::new(StdRng::seed_from_u64(CONNECTION_DRIVE_SEED)),
+            connection_drive_ema_alpha: std::env::var("CONNECTION_DRIVE_EMA_ALPHA")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|a: &f32| *a > 0.0 && *a <= 1.0)
+                .unwrap_or(DEFAULT_CONNECTION_DRIVE_EMA_ALPHA),
+            connection_drive_history: RwLock::new(VecDeque::with_capacity(
+                CONNECTION_DRIVE_HISTORY_LEN,
+            )),
+            mood_history: RwLock::new(VecDeque::with_capacity(MOOD_HISTORY_LEN)),
+            metric_history: RwLock::new(VecDeque::new()),
+            events: RwLock::new(VecDeque::with_capacity(events::EVENT_LOG_CAPACITY)),
             http_client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(5))
+                .timeout(CORE_REQUEST_TIMEOUT)
                 .build()
                 .expect("Failed to build HTTP client"),
+            thought_samples: RwLock::new(VecDeque::new()),
+            last_newest_thought: RwLock::new((None, Utc::now())),
+            last_unconscious_change: RwLock::new(None),
+            read_outages: RwLock::new(HashMap::new()),
+            stall_threshold_secs: std::env::var("STALL_THRESHOLD_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            entropy_max_bits: std::env::var("ENTROPY_MAX_BITS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f32| *v > 0.0)
+                .unwrap_or(DEFAULT_ENTROPY_MAX_BITS),
+            entropy_bands: events::EntropyBands::from_env(),
+            thought_window: std::env::var("THOUGHT_WINDOW")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(THOUGHTS_DEFAULT_LIMIT)
+                .clamp(1, THOUGHTS_MAX_LIMIT),
+            manifold_clusters: std::env::var("MANIFOLD_CLUSTERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(vectors::DEFAULT_MANIFOLD_CLUSTERS)
+                .min(vectors::MAX_MANIFOLD_CLUSTERS),
+            manifold_fields: vectors::PayloadFields::from_env(),
+            manifold_point_limit: std::env::var("MANIFOLD_POINT_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(vectors::DEFAULT_MANIFOLD_POINT_LIMIT)
+                .clamp(1, vectors::MAX_MANIFOLD_POINT_LIMIT),
+            last_metrics_success: RwLock::new(None),
+            last_core_success: RwLock::new(None),
+            fetch_cycle: AtomicU64::new(0),
+            frames: broadcast::Sender::new(fanout::BROADCAST_CAPACITY),
+            ws_clients: AtomicUsize::new(0),
+            max_ws_clients: std::env::var("MAX_WS_CLIENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_WS_CLIENTS),
+            shutdown: watch::Sender::new(false),
+            thought_events: thought_events::ThoughtEvents::from_env(),
+            quotes: quotes::QuoteRotation::from_env(),
         }
     }
 
-    fn default_metrics() -> DashboardMetrics {
+    /// Read from `source` instead of the live stores
+    fn with_source(mut self, source: Box<dyn MetricsSource>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Whether the core answered within `CORE_REACHABLE_WINDOW_SECS`
+    async fn core_reachable(&self) -> bool {
+        self.last_core_success
+            .read()
+            .await
+            .is_some_and(|t| (Utc::now() - t).num_seconds() < CORE_REACHABLE_WINDOW_SECS)
+    }
+
+    /// Next correlation id for a fetch cycle (groups one poll's log lines)
+    fn next_cycle_id(&self) -> u64 {
+        self.fetch_cycle.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn default_metrics(name: &str) -> DashboardMetrics {
         DashboardMetrics {
             timestamp: Utc::now(),
             identity: IdentityMetrics {
-                name: "Timmy".into(),
+                name: name.into(),
                 uptime_seconds: 0,
                 lifetime_thoughts: 0,
                 session_thoughts: 0,
@@ -295,117 +640,1016 @@ impl AppState {
                 unconscious_memories: 0,
                 lifetime_dreams: 0,
                 current_cycle: 0,
+                seconds_since_last_thought: 0,
+                stalled: false,
+                thoughts_per_minute: 0.0,
+                dreaming: false,
             },
             emotional: EmotionalMetrics {
                 valence: 0.0,
                 arousal: 0.5,
                 dominance: 0.5,
                 connection_drive: 0.5,
+                connection_drive_smoothed: 0.5,
                 emotional_intensity: 0.0,
+                emotion_defaulted: true,
+                connection_drive_history: vec![],
+                mood_history: vec![],
             },
-            actors: ActorMetrics {
-                memory_actor: ActorStatus {
-                    name: "MemoryActor".into(),
-                    alive: true,
-                    restart_count: 0,
-                },
-                attention_actor: ActorStatus {
-                    name: "AttentionActor".into(),
-                    alive: true,
-                    restart_count: 0,
-                },
-                salience_actor: ActorStatus {
-                    name: "SalienceActor".into(),
-                    alive: true,
-                    restart_count: 0,
-                },
-                volition_actor: ActorStatus {
-                    name: "VolitionActor".into(),
-                    alive: true,
-                    restart_count: 0,
-                },
-            },
+            actors: ActorMetrics::down(),
             recent_thoughts: vec![],
+            salience_histogram: [0; SALIENCE_BINS],
         }
     }
 }
 
+// =============================================================================
+// OpenAPI
+// =============================================================================
+
+/// Machine-readable description of the read-only REST API (served at /openapi.json)
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "daneel-web",
+        description = "Read-only observatory API for a DANEEL core's cognitive metrics"
+    ),
+    paths(
+        health,
+        ready,
+        list_instances,
+        metrics,
+        extended_metrics,
+        observatory,
+        export,
+        observatory_summary,
+        pipeline,
+        config::config,
+        manifold_vectors,
+        retrain_projection,
+        thought_detail,
+        crystal_nearest,
+        thoughts,
+        thought_stream,
+        history::past_window,
+        events::events,
+        quotes::quotes,
+        diff::diff
+    )
+)]
+struct ApiDoc;
+
 // =============================================================================
 // Handlers
 // =============================================================================
 
+/// GET /health response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthResponse {
+    pub status: String,
+    pub service: String,
+    pub server_time: DateTime<Utc>,
+}
+
+#[utoipa::path(get, path = "/health", responses(
+    (status = 200, description = "Service is up", body = HealthResponse)
+))]
 async fn health() -> impl IntoResponse {
-    Json(serde_json::json!({"status": "ok", "service": "daneel-web"}))
+    Json(HealthResponse {
+        status: "ok".into(),
+        service: "daneel-web".into(),
+        server_time: Utc::now(),
+    })
+}
+
+async fn openapi_json() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+#[utoipa::path(get, path = "/instances", responses(
+    (status = 200, description = "Configured daneel instances", body = instances::InstancesResponse)
+))]
+async fn list_instances(State(instances): State<Arc<Instances>>) -> impl IntoResponse {
+    Json(instances::InstancesResponse {
+        default: instances.names()[0].clone(),
+        instances: instances.names().to_vec(),
+    })
+}
+
+/// Dashboard metrics older than this make /ready fail
+const READY_STALE_SECS: i64 = 10;
+
+/// Per-dependency probe timeout for /ready
+const READY_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for every request to the core (`http_client`)
+const CORE_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One dependency check in the /ready response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyCheck {
+    pub instance: String,
+    /// `metrics`, `redis`, `qdrant` or `core`
+    pub dependency: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// GET /ready response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub checks: Vec<DependencyCheck>,
+}
+
+/// GET /ready - readiness: every instance has fresh metrics and reachable upstreams
+///
+/// Unlike /health (pure liveness) this pings Redis and Qdrant and requires a
+/// dashboard poll within `READY_STALE_SECS` and a recent core response.
+#[utoipa::path(get, path = "/ready", responses(
+    (status = 200, description = "Serving fresh data", body = ReadinessResponse),
+    (status = 503, description = "At least one dependency is failing", body = ReadinessResponse)
+))]
+async fn ready(State(instances): State<Arc<Instances>>) -> impl IntoResponse {
+    let mut checks = Vec::new();
+    for (name, state) in instances.iter() {
+        checks.extend(readiness_checks(name, state).await);
+    }
+    let ready = checks.iter().all(|c| c.ok);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(ReadinessResponse { ready, checks }))
+}
+
+async fn readiness_checks(instance: &str, state: &AppState) -> Vec<DependencyCheck> {
+    let check = |dependency: &str, result: Result<String, String>| {
+        let ok = result.is_ok();
+        DependencyCheck {
+            instance: instance.to_string(),
+            dependency: dependency.to_string(),
+            ok,
+            detail: result.unwrap_or_else(|e| e),
+        }
+    };
+
+    let metrics = match *state.last_metrics_success.read().await {
+        Some(t) if (Utc::now() - t).num_seconds() < READY_STALE_SECS => Ok(format!(
+            "polled {}ms ago",
+            (Utc::now() - t).num_milliseconds()
+        )),
+        Some(t) => Err(format!("stale since {}", t.to_rfc3339())),
+        None => Err("no successful poll yet".into()),
+    };
+
+    let core = if state.core_reachable().await {
+        Ok("answered recently".into())
+    } else {
+        Err(format!("no response in {}s", CORE_REACHABLE_WINDOW_SECS))
+    };
+
+    let mut checks = vec![check("metrics", metrics)];
+    for (dependency, result) in state.source.probe().await {
+        checks.push(check(dependency, result));
+    }
+    checks.push(check("core", core));
+    checks
+}
+
+/// One upstream in the /pipeline response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UpstreamHealth {
+    /// `redis`, `qdrant` or `core` (`demo`/`mock` for in-memory sources)
+    pub name: String,
+    /// Answered within `CORE_REACHABLE_WINDOW_SECS`. In-memory sources have
+    /// nothing to lose touch with and always report reachable, with a
+    /// `last_success` of the request time rather than of a real read.
+    pub reachable: bool,
+    pub last_success: Option<DateTime<Utc>>,
+    /// Milliseconds since `last_success`, by the server's clock
+    pub age_ms: Option<i64>,
+}
+
+/// GET /pipeline response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PipelineHealth {
+    pub instance: String,
+    pub upstreams: Vec<UpstreamHealth>,
+    /// Open /ws connections to this instance
+    pub ws_clients: usize,
+    /// 0 = unlimited
+    pub max_ws_clients: usize,
+}
+
+/// GET /pipeline - health of the dashboard's own data plumbing
+///
+/// Unlike /ready this probes nothing: it reports when each upstream last
+/// answered one of the dashboard's own reads, so it's cheap to poll.
+#[utoipa::path(get, path = "/pipeline", params(InstanceQuery), responses(
+    (status = 200, description = "Per-upstream last success and WebSocket clients", body = PipelineHealth)
+))]
+async fn pipeline(Instance(state): Instance) -> impl IntoResponse {
+    Json(pipeline_health(&state).await)
+}
+
+async fn pipeline_health(state: &AppState) -> PipelineHealth {
+    let now = Utc::now();
+    let upstream = |name: &str, last: Option<DateTime<Utc>>| {
+        let age_ms = last.map(|t| (now - t).num_milliseconds().max(0));
+        UpstreamHealth {
+            name: name.to_string(),
+            reachable: age_ms.is_some_and(|ms| ms < CORE_REACHABLE_WINDOW_SECS * 1000),
+            last_success: last,
+            age_ms,
+        }
+    };
+    let mut upstreams: Vec<UpstreamHealth> = state
+        .source
+        .last_successes()
+        .into_iter()
+        .map(|(name, last)| upstream(name, last))
+        .collect();
+    upstreams.push(upstream("core", *state.last_core_success.read().await));
+    PipelineHealth {
+        instance: state.instance.clone(),
+        upstreams,
+        ws_clients: state.ws_clients.load(Ordering::Acquire),
+        max_ws_clients: state.max_ws_clients,
+    }
 }
 
-async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+#[utoipa::path(get, path = "/metrics", params(InstanceQuery), responses(
+    (status = 200, description = "Latest dashboard metrics", body = DashboardMetrics)
+))]
+async fn metrics(Instance(state): Instance) -> impl IntoResponse {
     Json(state.metrics.read().await.clone())
 }
 
-async fn extended_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    Json(state.extended_metrics.read().await.clone())
+#[utoipa::path(get, path = "/extended", params(InstanceQuery), responses(
+    (status = 200, description = "Latest extended metrics (null until the core answers)", body = Option<ExtendedMetrics>)
+))]
+async fn extended_metrics(Instance(state): Instance) -> impl IntoResponse {
+    let mut extended = state.extended_metrics.read().await.clone();
+    if let Some(extended) = extended.as_mut() {
+        state.quotes.fill(&mut extended.philosophy, Utc::now());
+    }
+    Json(extended)
+}
+
+/// GET /observatory - history series are LTTB-downsampled (see `downsample`)
+#[utoipa::path(get, path = "/observatory", params(HistoryQuery, InstanceQuery), responses(
+    (status = 200, description = "Dashboard and extended metrics combined", body = ObservatoryMetrics)
+))]
+async fn observatory(
+    Instance(state): Instance,
+    Query(params): Query<HistoryQuery>,
+) -> impl IntoResponse {
+    let mut observatory = observatory_snapshot(&state).await;
+    downsample::downsample_histories(&mut observatory, params.points());
+    Json(observatory)
+}
+
+/// GET /export - `/observatory` at full history resolution
+#[utoipa::path(get, path = "/export", params(InstanceQuery), responses(
+    (status = 200, description = "Dashboard and extended metrics with full-resolution history", body = ObservatoryMetrics)
+))]
+async fn export(Instance(state): Instance) -> impl IntoResponse {
+    Json(observatory_snapshot(&state).await)
 }
 
-async fn observatory(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+/// Current dashboard + extended metrics at full resolution
+async fn observatory_snapshot(state: &AppState) -> ObservatoryMetrics {
     let dashboard = state.metrics.read().await.clone();
-    let extended = state.extended_metrics.read().await.clone();
-    Json(ObservatoryMetrics {
+    let mut extended = state.extended_metrics.read().await.clone();
+    let events = events::recent(&*state.events.read().await, events::FRAME_EVENTS);
+    let now = Utc::now();
+    if let Some(extended) = extended.as_mut() {
+        state.quotes.fill(&mut extended.philosophy, now);
+    }
+    ObservatoryMetrics {
+        server_time: now,
         dashboard,
         extended,
-    })
+        events,
+    }
+}
+
+/// GET /observatory/summary - composite cognitive health score (see `summary`)
+#[utoipa::path(get, path = "/observatory/summary", params(InstanceQuery), responses(
+    (status = 200, description = "Composite cognitive health score", body = summary::ObservatorySummary)
+))]
+async fn observatory_summary(Instance(state): Instance) -> impl IntoResponse {
+    let dashboard = state.metrics.read().await.clone();
+    let extended = state.extended_metrics.read().await.clone();
+    let core_reachable = state.core_reachable().await;
+    Json(summary::summarize(
+        &dashboard,
+        extended.as_ref(),
+        core_reachable,
+    ))
+}
+
+/// Query for GET /vectors
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ManifoldQuery {
+    /// Drop points below this salience (crystals are always returned)
+    pub min_salience: Option<f32>,
+    /// Uniform random sample of the whole collection instead of the first
+    /// MANIFOLD_POINT_LIMIT points (slower: scrolls every id)
+    #[serde(default)]
+    pub sample: bool,
+    /// Memory store to draw: `conscious` (default) or `unconscious`
+    #[serde(default)]
+    pub layer: MemoryStore,
+}
+
+/// Query for the manifold endpoints that only pick a store
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LayerQuery {
+    /// `conscious` (default) or `unconscious`
+    #[serde(default)]
+    pub layer: MemoryStore,
 }
 
-async fn manifold_vectors(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let projection = state.projection.read().await;
+/// Whether an `If-None-Match` header lists `etag` (weak comparison, as GET allows)
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = strip(etag);
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || strip(tag) == etag)
+}
 
-    // Fetch and project vectors
-    let points = vectors::fetch_manifold_points(&state.qdrant_url, &projection, 500)
+/// GET /vectors - tagged with `vectors::manifold_etag`, so polling clients mostly get a 304
+#[utoipa::path(get, path = "/vectors", params(ManifoldQuery, InstanceQuery), responses(
+    (status = 200, description = "Thought vectors projected to 3D (`projection_type: \"empty\"` before the first memory)", body = vectors::ManifoldResponse),
+    (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+    (status = 500, description = "Qdrant unavailable")
+))]
+async fn manifold_vectors(
+    Instance(state): Instance,
+    Query(params): Query<ManifoldQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let mut manifold = build_manifold(&state, params.layer, params.sample, true, true)
         .await
-        .unwrap_or_default();
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    if let Some(min) = params.min_salience {
+        manifold.points.retain(|p| p.salience >= min);
+        let kept: HashSet<&str> = manifold.points.iter().map(|p| p.id.as_str()).collect();
+        manifold
+            .edges
+            .retain(|e| kept.contains(e.source.as_str()) && kept.contains(e.target.as_str()));
+    }
+    let etag = vectors::manifold_etag(&manifold);
+    // `no-cache` lets browsers keep the body but revalidate it on every poll
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, "no-cache".to_string()),
+    ];
+    if etag_matches(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+    Ok((cache_headers, Json(manifold)).into_response())
+}
+
+/// Response of POST /vectors/retrain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetrainResponse {
+    /// Whether PCA was refit (false while there are too few vectors to fit)
+    pub retrained: bool,
+    /// When the projection in use was fitted
+    pub last_refit: Option<DateTime<Utc>>,
+}
+
+/// POST /vectors/retrain - refit the PCA projection now instead of waiting
+/// for `MANIFOLD_REFIT_SECS`. Refitting is the heaviest thing the server
+/// does, so this only exists when DANEEL_WEB_TOKEN guards it.
+#[utoipa::path(post, path = "/vectors/retrain", params(InstanceQuery), responses(
+    (status = 200, description = "Refit attempted; `retrained` says whether it happened", body = RetrainResponse),
+    (status = 401, description = "Missing or wrong access token"),
+    (status = 403, description = "Disabled: no DANEEL_WEB_TOKEN is configured"),
+    (status = 500, description = "Qdrant unavailable")
+))]
+async fn retrain_projection(
+    Instance(state): Instance,
+    Extension(settings): Extension<Arc<config::ServerSettings>>,
+) -> Result<Json<RetrainResponse>, StatusCode> {
+    if !settings.auth_enabled {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let before = {
+        let mut projection = state.projection.write().await;
+        projection.retrain_requested = true;
+        projection.fitted_at
+    };
+    let manifold = build_manifold(&state, MemoryStore::Conscious, false, false, false).await;
+
+    let mut projection = state.projection.write().await;
+    // Too few vectors (or a failed fetch) leaves the request pending; drop it
+    // so the next poll doesn't fit behind the caller's back
+    projection.retrain_requested = false;
+    manifold.ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(RetrainResponse {
+        retrained: projection.fitted_at != before,
+        last_refit: projection.fitted_at,
+    }))
+}
+
+/// Default and maximum `k` for /crystals/{law}/nearest
+const NEAREST_DEFAULT_K: usize = 10;
+const NEAREST_MAX_K: usize = 100;
+
+/// Query for GET /crystals/{law}/nearest
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NearestQuery {
+    /// How many thoughts to return (default 10, max 100)
+    pub k: Option<usize>,
+}
+
+/// GET /crystals/{law}/nearest - the projected thoughts closest to a Law Crystal
+#[utoipa::path(get, path = "/crystals/{law}/nearest", params(
+    ("law" = u8, Path, description = "Law index, 0-3"),
+    NearestQuery,
+    InstanceQuery
+), responses(
+    (status = 200, description = "The crystal and its nearest thoughts, nearest first", body = vectors::CrystalNeighbors),
+    (status = 400, description = "No such law"),
+    (status = 500, description = "Qdrant unavailable")
+))]
+async fn crystal_nearest(
+    Instance(state): Instance,
+    Path(law): Path<u8>,
+    Query(params): Query<NearestQuery>,
+) -> Result<Json<vectors::CrystalNeighbors>, StatusCode> {
+    let crystal = {
+        let projection = state.projection.read().await;
+        vectors::get_law_crystals(&projection)
+            .into_iter()
+            .find(|c| c.law == law)
+            .ok_or(StatusCode::BAD_REQUEST)?
+    };
+    let manifold = build_manifold(&state, MemoryStore::Conscious, false, false, false)
+        .await
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let k = params.k.unwrap_or(NEAREST_DEFAULT_K).min(NEAREST_MAX_K);
+    Ok(Json(vectors::CrystalNeighbors {
+        thoughts: vectors::nearest_to_crystal(&manifold.points, &crystal, k),
+        crystal,
+    }))
+}
+
+/// GET /thought/{id} - the memory behind a manifold point (pinned in the UI)
+#[utoipa::path(get, path = "/thought/{id}", params(
+    ("id" = String, Path, description = "Manifold point id"),
+    LayerQuery,
+    InstanceQuery
+), responses(
+    (status = 200, description = "The memory's content and salience", body = vectors::ThoughtDetail),
+    (status = 404, description = "No such memory"),
+    (status = 502, description = "Qdrant unavailable")
+))]
+async fn thought_detail(
+    Instance(state): Instance,
+    Path(id): Path<String>,
+    Query(params): Query<LayerQuery>,
+) -> Result<Json<vectors::ThoughtDetail>, StatusCode> {
+    match state.source.thought(params.layer, &id).await {
+        Ok(Some(detail)) => Ok(Json(detail)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            let (upstream, key) = state.source.target(Read::Thought(params.layer));
+            warn!(upstream, key, id = %id, error = %e, "Thought lookup failed");
+            Err(StatusCode::BAD_GATEWAY)
+        }
+    }
+}
+
+/// Fetch, project and anchor the current manifold of `layer` (a
+/// collection-wide sample with `sample`, with its kinship graph with
+/// `kinship`, eased from what was last served with `smooth`); None if Qdrant
+/// failed (a missing collection is an empty manifold, not a failure)
+async fn build_manifold(
+    state: &AppState,
+    layer: MemoryStore,
+    sample: bool,
+    kinship: bool,
+    smooth: bool,
+) -> Option<vectors::ManifoldResponse> {
+    // Fetch, project and cluster vectors (may fit PCA, so before reading the projection)
+    let fetched = vectors::fetch_manifold_points(
+        state.source.as_ref(),
+        &state.projection,
+        layer,
+        state.manifold_point_limit,
+        sample,
+        state.manifold_clusters,
+        &state.manifold_fields,
+        kinship,
+        smooth,
+    )
+    .await;
+
+    let guard = state.projection.read().await;
+    // Mid-refit, crystals ease along with the points
+    let projection = guard.at(Utc::now());
 
     // Get Law Crystal anchor points
     let crystals = vectors::get_law_crystals(&projection);
 
-    Json(vectors::ManifoldResponse {
+    let (points, centroids, edges, projection_type, message) = match fetched {
+        Ok((points, centroids, edges)) => {
+            let projection_type = if projection.is_trained {
+                "pca"
+            } else {
+                "random"
+            };
+            (points, centroids, edges, projection_type, None)
+        }
+        Err(FetchError::NotFound(_)) => {
+            let message = match layer {
+                MemoryStore::Conscious => "No memories yet",
+                MemoryStore::Unconscious => "No unconscious memories yet",
+            };
+            (
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                "empty",
+                Some(message.to_string()),
+            )
+        }
+        Err(e) => {
+            let (upstream, key) = state.source.target(Read::Vectors(layer));
+            warn!(upstream, key, error = %e, "Manifold fetch failed");
+            return None;
+        }
+    };
+
+    Some(vectors::ManifoldResponse {
         points,
         crystals,
-        projection_type: if projection.is_trained {
-            "pca".to_string()
-        } else {
-            "random".to_string()
-        },
+        centroids,
+        edges,
+        projection_type: projection_type.to_string(),
+        explained_variance: projection
+            .explained_variance
+            .filter(|_| projection_type == "pca"),
+        dimension: projection.dim(),
+        message,
+        last_refit: projection.fitted_at.filter(|_| projection_type == "pca"),
+        layer,
     })
 }
 
-async fn ws_handler(ws: WebSocketUpgrade, State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+/// How often /vectors/stream re-checks the manifold
+const MANIFOLD_STREAM_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Every Nth check sends a full snapshot keyframe instead of a delta (~30s)
+const MANIFOLD_KEYFRAME_EVERY: u64 = 15;
+
+/// Per-subscriber state for /vectors/stream
+struct ManifoldStream {
+    state: Arc<AppState>,
+    interval: tokio::time::Interval,
+    shutdown: watch::Receiver<bool>,
+    layer: MemoryStore,
+    /// Point ids the client currently holds
+    sent: HashSet<String>,
+    tick: u64,
+}
+
+/// GET /vectors/stream - SSE of manifold changes
+///
+/// Emits a `snapshot` event (full `ManifoldResponse`) on connect and every
+/// `MANIFOLD_KEYFRAME_EVERY` checks, and `delta` events (`ManifoldDelta`) with
+/// newly-arrived points and removed ids in between. Empty deltas are skipped.
+/// Checks where Qdrant fails emit an `unavailable` event instead.
+/// Cluster labels and kinship edges only refresh with the next snapshot.
+async fn manifold_stream(
+    Instance(state): Instance,
+    Query(params): Query<LayerQuery>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let init = ManifoldStream {
+        shutdown: state.shutdown.subscribe(),
+        state,
+        interval: tokio::time::interval(MANIFOLD_STREAM_INTERVAL),
+        layer: params.layer,
+        sent: HashSet::new(),
+        tick: 0,
+    };
+
+    let stream = futures::stream::unfold(init, |mut s| async move {
+        loop {
+            // End the stream on shutdown so graceful shutdown isn't held open
+            tokio::select! {
+                _ = s.interval.tick() => {}
+                _ = shutdown_requested(&mut s.shutdown) => return None,
+            }
+            // Already logged; tell the client why its view has stopped updating
+            let keyframe = s.tick % MANIFOLD_KEYFRAME_EVERY == 0;
+            // Deltas don't carry the kinship graph, so only keyframes pay for it
+            let Some(manifold) = build_manifold(&s.state, s.layer, false, keyframe, true).await
+            else {
+                let event = Event::default()
+                    .event("unavailable")
+                    .data("manifold unavailable");
+                return Some((Ok(event), s));
+            };
+            s.tick += 1;
+
+            let current: HashSet<String> = manifold.points.iter().map(|p| p.id.clone()).collect();
+            if keyframe {
+                s.sent = current;
+                let event = Event::default().event("snapshot").json_data(&manifold);
+                return Some((event, s));
+            }
+
+            let removed: Vec<String> = s.sent.difference(&current).cloned().collect();
+            let added: Vec<vectors::ManifoldPoint> = manifold
+                .points
+                .into_iter()
+                .filter(|p| !s.sent.contains(&p.id))
+                .collect();
+            s.sent = current;
+
+            if added.is_empty() && removed.is_empty() {
+                continue;
+            }
+            let event = Event::default()
+                .event("delta")
+                .json_data(vectors::ManifoldDelta { added, removed });
+            return Some((event, s));
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Query for GET /thoughts
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ThoughtsQuery {
+    pub query: Option<String>,
+    /// Thoughts to return (also accepted as `count`)
+    #[serde(alias = "count")]
+    pub limit: Option<usize>,
+    /// Stream id to page back from (exclusive); a previous `next_cursor`
+    pub before: Option<String>,
+}
+
+/// GET /thoughts response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ThoughtsResponse {
+    pub query: String,
+    pub scanned: usize,
+    pub thoughts: Vec<ThoughtSummary>,
+    /// `before` for the next (older) page; null once the stream is exhausted
+    pub next_cursor: Option<String>,
+}
+
+/// Newest awake-stream entries scanned per search (caps the Redis read)
+const THOUGHT_SCAN_WINDOW: usize = 1000;
+
+/// Default and maximum number of thoughts returned by /thoughts
+const THOUGHTS_DEFAULT_LIMIT: usize = 20;
+const THOUGHTS_MAX_LIMIT: usize = 200;
+
+/// How often /thoughts/stream checks the awake stream for new entries
+const THOUGHT_STREAM_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Most entries read per check; a larger burst drains over the following checks
+const THOUGHT_STREAM_BATCH: usize = 100;
+
+/// Content type of /thoughts/stream (kept out of compression so lines aren't held back)
+const NDJSON: &str = "application/x-ndjson";
+
+/// One line of /thoughts/stream: a `ThoughtSummary` plus the salience
+/// fields the summary leaves out (null when the entry lacks them)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StreamedThought {
+    #[serde(flatten)]
+    pub summary: ThoughtSummary,
+    pub valence: Option<f32>,
+    pub arousal: Option<f32>,
+}
+
+/// Per-client state for /thoughts/stream
+struct ThoughtStream {
+    state: Arc<AppState>,
+    interval: tokio::time::Interval,
+    shutdown: watch::Receiver<bool>,
+    /// Id of the last entry sent (or of the newest entry when the client connected)
+    last_id: String,
+}
+
+/// Id of the newest awake-stream entry, "0-0" for an empty stream
+async fn newest_thought_id(state: &AppState) -> source::SourceResult<String> {
+    let newest = state.source.recent_thoughts(1).await?;
+    Ok(newest
+        .into_iter()
+        .next()
+        .map_or_else(|| "0-0".to_string(), |t| t.summary.id))
+}
+
+/// GET /thoughts/stream - newline-delimited JSON of thoughts as they arrive
+///
+/// Starts from the newest entry at connect time (nothing historical is
+/// replayed) and runs until the client disconnects or the server shuts down.
+/// Each line is a `StreamedThought`; checks where the read fails are logged
+/// and retried, so a consumer only sees a pause.
+#[utoipa::path(get, path = "/thoughts/stream", params(InstanceQuery), responses(
+    (status = 200, description = "One JSON thought per line, oldest first, as they arrive", body = StreamedThought, content_type = "application/x-ndjson"),
+    (status = 502, description = "Redis unavailable")
+))]
+async fn thought_stream(Instance(state): Instance) -> Result<Response, StatusCode> {
+    let last_id = newest_thought_id(&state).await.map_err(|e| {
+        let (upstream, key) = state.source.target(Read::AwakeStream);
+        warn!(upstream, key, error = %e, "Thought stream start failed");
+        StatusCode::BAD_GATEWAY
+    })?;
+    let init = ThoughtStream {
+        shutdown: state.shutdown.subscribe(),
+        state,
+        interval: tokio::time::interval(THOUGHT_STREAM_INTERVAL),
+        last_id,
+    };
+
+    // A dropped client drops the body, and with it this stream
+    let stream = futures::stream::unfold(init, |mut s| async move {
+        loop {
+            tokio::select! {
+                _ = s.interval.tick() => {}
+                _ = shutdown_requested(&mut s.shutdown) => return None,
+            }
+            let source = &s.state.source;
+            let entries = match source
+                .thoughts_after(&s.last_id, THOUGHT_STREAM_BATCH)
+                .await
+            {
+                Ok(entries) => entries,
+                Err(e) => {
+                    let (upstream, key) = source.target(Read::AwakeStream);
+                    warn!(upstream, key, error = %e, "Thought stream read failed");
+                    continue;
+                }
+            };
+            let Some(last) = entries.last() else {
+                continue;
+            };
+            s.last_id = last.summary.id.clone();
+
+            let mut chunk = String::new();
+            for thought in entries {
+                let line = StreamedThought {
+                    summary: thought.summary,
+                    valence: thought.valence,
+                    arousal: thought.arousal,
+                };
+                if let Ok(json) = serde_json::to_string(&line) {
+                    chunk.push_str(&json);
+                    chunk.push('\n');
+                }
+            }
+            return Some((Ok::<_, std::convert::Infallible>(chunk), s));
+        }
+    });
+
+    Ok(([(header::CONTENT_TYPE, NDJSON)], Body::from_stream(stream)).into_response())
+}
+
+/// GET /thoughts?query=<substr>&limit=N - case-insensitive search over recent thoughts;
+/// without a query it's simply the newest N (a wider thought-stream window)
+///
+/// `before=<stream id>` starts from just below that entry instead of the
+/// newest, so passing each response's `next_cursor` pages back through the
+/// whole stream (a search scans `THOUGHT_SCAN_WINDOW` entries per page).
+#[utoipa::path(get, path = "/thoughts", params(ThoughtsQuery, InstanceQuery), responses(
+    (status = 200, description = "Matching thoughts, newest first", body = ThoughtsResponse),
+    (status = 400, description = "`before` is not a stream id"),
+    (status = 502, description = "Redis unavailable")
+))]
+async fn thoughts(
+    Instance(state): Instance,
+    Query(params): Query<ThoughtsQuery>,
+) -> Result<Json<ThoughtsResponse>, StatusCode> {
+    let query = params.query.unwrap_or_default();
+    let needle = query.trim().to_lowercase();
+    let limit = params
+        .limit
+        .unwrap_or(THOUGHTS_DEFAULT_LIMIT)
+        .clamp(1, THOUGHTS_MAX_LIMIT);
+
+    // Without a needle every entry matches, so there's no point reading past the limit
+    let scan = if needle.is_empty() {
+        limit
+    } else {
+        THOUGHT_SCAN_WINDOW
+    };
+    let entries = match params.before.as_deref() {
+        Some(cursor) if parse_stream_id(cursor).is_none() => return Err(StatusCode::BAD_REQUEST),
+        Some(cursor) => state.source.thoughts_before(cursor, scan).await,
+        None => state.source.recent_thoughts(scan).await,
+    }
+    .map_err(|e| {
+        let (upstream, key) = state.source.target(Read::AwakeStream);
+        warn!(upstream, key, error = %e, "Thought search failed");
+        StatusCode::BAD_GATEWAY
+    })?;
+
+    let scanned = entries.len();
+    let mut thoughts: Vec<ThoughtSummary> = Vec::new();
+    // The next page starts below the last entry looked at, so a full page of
+    // matches resumes right after them rather than skipping the rest of the scan
+    let mut last_seen = None;
+    for t in entries {
+        if thoughts.len() == limit {
+            break;
+        }
+        last_seen = Some(t.summary.id.clone());
+        if needle.is_empty()
+            || t.text.to_lowercase().contains(&needle)
+            || t.summary.content_preview.to_lowercase().contains(&needle)
+        {
+            thoughts.push(t.summary);
+        }
+    }
+    // A short read means the stream ran out
+    let next_cursor = if thoughts.len() == limit || scanned == scan {
+        last_seen
+    } else {
+        None
+    };
+
+    Ok(Json(ThoughtsResponse {
+        query,
+        scanned,
+        thoughts,
+        next_cursor,
+    }))
+}
+
+/// Default cap on concurrent WebSocket clients per instance (MAX_WS_CLIENTS)
+const DEFAULT_MAX_WS_CLIENTS: usize = 256;
+
+/// Take a slot in `counter` unless `max` are already taken (0 = unlimited);
+/// returns the new count
+fn try_reserve(counter: &AtomicUsize, max: usize) -> Option<usize> {
+    counter
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+            (max == 0 || n < max).then_some(n + 1)
+        })
+        .ok()
+        .map(|n| n + 1)
+}
+
+/// One open WebSocket; frees its slot when dropped, whichever way the socket ends
+struct WsClientSlot {
+    state: Arc<AppState>,
+}
+
+impl WsClientSlot {
+    fn acquire(state: &Arc<AppState>) -> Option<Self> {
+        let clients = try_reserve(&state.ws_clients, state.max_ws_clients)?;
+        info!(instance = %state.instance, clients, "WebSocket client connected");
+        Some(Self {
+            state: state.clone(),
+        })
+    }
+}
+
+impl Drop for WsClientSlot {
+    fn drop(&mut self) {
+        let clients = self.state.ws_clients.fetch_sub(1, Ordering::AcqRel) - 1;
+        info!(instance = %self.state.instance, clients, "WebSocket client disconnected");
+    }
+}
+
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Instance(state): Instance,
+    Query(params): Query<HistoryQuery>,
+    Query(selection): Query<SectionsQuery>,
+    Query(decimation): Query<fanout::EveryQuery>,
+) -> Response {
+    let history_points = params.points();
+    let every = decimation.every();
+    let sections = match Sections::parse(selection.sections.as_deref()) {
+        Ok(sections) => sections,
+        Err(unknown) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Unknown section: {}", unknown),
+            )
+                .into_response()
+        }
+    };
+    match WsClientSlot::acquire(&state) {
+        Some(slot) => ws.on_upgrade(move |socket| {
+            handle_socket(socket, state, slot, history_points, sections, every)
+        }),
+        None => {
+            warn!(instance = %state.instance, max = state.max_ws_clients, "WebSocket client limit reached, refusing connection");
+            ws.on_upgrade(|mut socket| async move {
+                // 1013 "try again later" is the WebSocket analogue of a 503
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AGAIN,
+                        reason: "too many clients".into(),
+                    })))
+                    .await;
+            })
+        }
+    }
 }
 
-async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
-    info!("WebSocket client connected");
-    let mut interval = tokio::time::interval(Duration::from_millis(200));
+/// Stream metrics to one client; `_slot` is held for the socket's lifetime
+async fn handle_socket(
+    mut socket: WebSocket,
+    state: Arc<AppState>,
+    _slot: WsClientSlot,
+    history_points: usize,
+    sections: Sections,
+    every: u64,
+) {
+    // Frames are built once by `fanout::frame_broadcaster` and shared by every socket
+    let mut frames = state.frames.subscribe();
+    let mut shutdown = state.shutdown.subscribe();
+    let mut stats = fanout::DeliveryStats::default();
+    // The first frame always goes out, so a decimating client isn't blank until `seq` lines up
+    let mut first_frame = true;
 
     loop {
         tokio::select! {
-            _ = interval.tick() => {
-                // Send full observatory metrics (dashboard + extended)
-                let dashboard = state.metrics.read().await.clone();
-                let extended = state.extended_metrics.read().await.clone();
-                let observatory = ObservatoryMetrics { dashboard, extended };
-                if let Ok(json) = serde_json::to_string(&observatory) {
-                    if socket.send(Message::Text(json)).await.is_err() {
-                        break;
+            _ = shutdown_requested(&mut shutdown) => {
+                // Tell the client we're going away rather than dropping the TCP stream
+                let close = socket.send(Message::Close(Some(CloseFrame {
+                    code: close_code::AWAY,
+                    reason: "server shutting down".into(),
+                })));
+                let _ = tokio::time::timeout(fanout::SEND_TIMEOUT, close).await;
+                break;
+            }
+            frame = frames.recv() => {
+                match frame {
+                    // `?every=N`: the producer keeps its rate, this client skips the rest
+                    Ok(frame) if !first_frame && !frame.is_nth(every) => {}
+                    Ok(frame) => {
+                        first_frame = false;
+                        // Every frame is a full snapshot, so downsample its history like /observatory
+                        let Some(json) = frame.encoded(history_points, sections) else {
+                            continue;
+                        };
+                        let send = socket.send(Message::Text(json.to_string()));
+                        match tokio::time::timeout(fanout::SEND_TIMEOUT, send).await {
+                            Ok(Ok(())) => stats.record_sent(),
+                            Ok(Err(_)) => break,
+                            // Client isn't reading: drop this frame, and the client if it keeps up
+                            Err(_) => {
+                                if stats.record_timeout() {
+                                    warn!(instance = %state.instance, dropped = stats.dropped, "Disconnecting persistently slow WebSocket client");
+                                    break;
+                                }
+                            }
+                        }
                     }
+                    // Too slow to keep up: skip ahead (the client sees a seq gap)
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => stats.record_lagged(skipped),
+                    Err(broadcast::error::RecvError::Closed) => break,
                 }
             }
             msg = socket.recv() => {
-                if matches!(msg, Some(Ok(Message::Close(_))) | None) {
-                    break;
+                match msg {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(Message::Text(text))) => {
+                        if let Ok(ClientMessage::Ping { client_time }) = serde_json::from_str(&text) {
+                            let pong = ServerMessage::Pong { client_time, server_time: Utc::now() };
+                            if let Ok(json) = serde_json::to_string(&pong) {
+                                let send = socket.send(Message::Text(json));
+                                // A pong that can't go out in time is stale anyway
+                                if let Ok(Err(_)) = tokio::time::timeout(fanout::SEND_TIMEOUT, send).await {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
     }
-    info!("WebSocket client disconnected");
+
+    if stats.dropped > 0 {
+        info!(instance = %state.instance, sent = stats.sent, dropped = stats.dropped, "WebSocket client missed frames");
+    }
 }
 
 // Static files served via ServeDir from daneel-web-ui/dist
@@ -416,7 +1660,7 @@ async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
 
 /// Proxy POST /inject to daneel core
 async fn proxy_inject(
-    State(state): State<Arc<AppState>>,
+    Instance(state): Instance,
     request: Request<Body>,
 ) -> Result<Response<Body>, StatusCode> {
     proxy_to_core(&state, request, "/inject").await
@@ -424,7 +1668,7 @@ async fn proxy_inject(
 
 /// Proxy GET /recent_injections to daneel core
 async fn proxy_recent_injections(
-    State(state): State<Arc<AppState>>,
+    Instance(state): Instance,
     request: Request<Body>,
 ) -> Result<Response<Body>, StatusCode> {
     proxy_to_core(&state, request, "/recent_injections").await
@@ -482,7 +1726,7 @@ async fn proxy_to_core(
 /// POST /embed - Convert text to 768-dim semantic vector
 /// Protected by same auth as /inject (proxied to daneel core for auth check)
 async fn embed_handler(
-    State(state): State<Arc<AppState>>,
+    Instance(state): Instance,
     request: Request<Body>,
 ) -> Result<Json<EmbedResponse>, (StatusCode, Json<EmbedError>)> {
     // Extract auth header for validation
@@ -621,130 +1865,458 @@ async fn embed_handler(
 // Background Metrics Fetchers
 // =============================================================================
 
+/// How often the dashboard metrics are re-read from Redis/Qdrant (slower while
+/// `thought_events` delivers new thoughts)
+const DASHBOARD_POLL_INTERVAL: Duration = Duration::from_millis(150);
+
+/// How often the core's `/extended_metrics` is polled
+const EXTENDED_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An upstream outage seen by a poller: logged when it starts and when it
+/// ends rather than on every failed poll
+#[derive(Debug, Default)]
+struct Outage {
+    since: Option<DateTime<Utc>>,
+}
+
+impl Outage {
+    /// A failed poll; true if it starts an outage
+    fn fail(&mut self, now: DateTime<Utc>) -> bool {
+        let started = self.since.is_none();
+        self.since.get_or_insert(now);
+        started
+    }
+
+    /// A good poll; how long the outage it ends lasted, if there was one
+    fn recover(&mut self, now: DateTime<Utc>) -> Option<chrono::TimeDelta> {
+        self.since.take().map(|since| now - since)
+    }
+}
+
+/// `result` as an Option, warning once when the `what` read starts failing and
+/// noting when it recovers, rather than on every poll
+async fn edge_logged<T>(
+    state: &AppState,
+    read: Read,
+    what: &'static str,
+    result: source::SourceResult<T>,
+) -> Option<T> {
+    let (upstream, key) = state.source.target(read);
+    let now = Utc::now();
+    let mut outages = state.read_outages.write().await;
+    let outage = outages.entry((what, key)).or_default();
+    match result {
+        Ok(value) => {
+            if let Some(down) = outage.recover(now) {
+                info!(
+                    upstream,
+                    key,
+                    down_secs = down.num_seconds(),
+                    "{} recovered",
+                    what
+                );
+            }
+            Some(value)
+        }
+        Err(e) => {
+            if outage.fail(now) {
+                warn!(upstream, key, error = %e, "{} failed", what);
+            }
+            None
+        }
+    }
+}
+
 async fn metrics_updater(state: Arc<AppState>) {
-    let mut interval = tokio::time::interval(Duration::from_millis(150));
+    let mut interval = tokio::time::interval(DASHBOARD_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut shutdown = state.shutdown.subscribe();
+    let mut outage = Outage::default();
+    let mut last_reads: Option<(std::time::Instant, SourceReads)> = None;
     loop {
-        interval.tick().await;
-        if let Ok(m) = fetch_metrics(&state).await {
-            *state.metrics.write().await = m;
+        let event = tokio::select! {
+            _ = interval.tick() => false,
+            _ = state.thought_events.arrived() => true,
+            _ = shutdown_requested(&mut shutdown) => break,
+        };
+        let span = info_span!(
+            "fetch_cycle",
+            instance = %state.instance,
+            cycle_id = state.next_cycle_id(),
+            kind = "dashboard"
+        );
+        // The drive walk and the histories advance on ticks only, so events
+        // can't speed them up; source reads are skipped between ticks while
+        // thought events make them redundant
+        let cached = last_reads
+            .as_ref()
+            .filter(|(at, _)| !event && !state.thought_events.reads_due(at.elapsed()))
+            .map(|(_, reads)| reads.clone());
+        let reads = match cached {
+            Some(reads) => Ok(reads),
+            None => {
+                let fresh = read_sources(&state).instrument(span.clone()).await;
+                if let Ok(reads) = &fresh {
+                    last_reads = Some((std::time::Instant::now(), reads.clone()));
+                }
+                fresh
+            }
+        };
+        let metrics = match reads {
+            Ok(reads) => Ok(derive_metrics(&state, reads, !event)
+                .instrument(span.clone())
+                .await),
+            Err(e) => Err(e),
+        };
+        match metrics {
+            Ok(m) => {
+                let now = Utc::now();
+                // The first poll only sets the baseline (see `events`)
+                if state.last_metrics_success.read().await.is_some() {
+                    let changes = events::dashboard_changes(&*state.metrics.read().await, &m, now);
+                    events::record(&mut *state.events.write().await, changes);
+                }
+                *state.metrics.write().await = m;
+                *state.last_metrics_success.write().await = Some(now);
+                if let Some(down) = outage.recover(now) {
+                    let upstream = state.source.target(Read::AwakeStream).0;
+                    span.in_scope(|| {
+                        info!(
+                            upstream,
+                            down_secs = down.num_seconds(),
+                            "Dashboard metrics fetch recovered"
+                        )
+                    });
+                }
+            }
+            // Once per outage; the last good metrics stay up meanwhile
+            Err(e) if outage.fail(Utc::now()) => {
+                let (upstream, key) = state.source.target(Read::AwakeStream);
+                span.in_scope(|| {
+                    warn!(
+                        upstream,
+                        key,
+                        error = %e,
+                        "Dashboard metrics fetch failed, keeping the last good metrics until it recovers"
+                    )
+                });
+            }
+            Err(_) => {}
         }
     }
 }
 
 /// Fetch extended metrics from daneel core API
 async fn extended_metrics_updater(state: Arc<AppState>) {
-    let mut interval = tokio::time::interval(Duration::from_millis(500));
+    let mut interval = tokio::time::interval(EXTENDED_POLL_INTERVAL);
+    let mut shutdown = state.shutdown.subscribe();
+    let mut outage = Outage::default();
     loop {
-        interval.tick().await;
-        if let Ok(m) = fetch_extended_metrics(&state).await {
-            *state.extended_metrics.write().await = Some(m);
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown_requested(&mut shutdown) => break,
+        }
+        let span = info_span!(
+            "fetch_cycle",
+            instance = %state.instance,
+            cycle_id = state.next_cycle_id(),
+            kind = "extended"
+        );
+        match fetch_extended_metrics(&state)
+            .instrument(span.clone())
+            .await
+        {
+            Ok(m) => {
+                let now = Utc::now();
+                history::record(
+                    &mut *state.metric_history.write().await,
+                    history::MetricSample::of(&m, now),
+                );
+                if state.last_core_success.read().await.is_some() {
+                    if let Some(prev) = state.extended_metrics.read().await.as_ref() {
+                        let changes = events::extended_changes(prev, &m, now);
+                        events::record(&mut *state.events.write().await, changes);
+                    }
+                }
+                *state.extended_metrics.write().await = Some(m);
+                *state.last_core_success.write().await = Some(now);
+                if let Some(down) = outage.recover(now) {
+                    span.in_scope(|| {
+                        info!(
+                            upstream = "core",
+                            url = %format!("{}/extended_metrics", state.daneel_core_url),
+                            down_secs = down.num_seconds(),
+                            "Extended metrics fetch recovered"
+                        )
+                    });
+                }
+            }
+            // Once per outage; either way the last good value stays up rather
+            // than blanking the Observatory
+            Err(e) if outage.fail(Utc::now()) => span.in_scope(|| match e {
+                FetchError::Parse(e) => warn!(
+                    upstream = "core",
+                    url = %format!("{}/extended_metrics", state.daneel_core_url),
+                    field = %e.path(),
+                    error = %e.inner(),
+                    "Extended metrics don't match the expected schema, keeping the last good value"
+                ),
+                e => warn!(
+                    upstream = "core",
+                    url = %format!("{}/extended_metrics", state.daneel_core_url),
+                    error = %e,
+                    "Extended metrics fetch failed"
+                ),
+            }),
+            Err(_) => {}
         }
     }
 }
 
-async fn fetch_extended_metrics(
-    state: &AppState,
-) -> Result<ExtendedMetrics, Box<dyn std::error::Error + Send + Sync>> {
-    let url = format!("{}/extended_metrics", state.daneel_core_url);
-    let resp = state.http_client.get(&url).send().await?;
-    let metrics: ExtendedMetrics = resp.json().await?;
-    Ok(metrics)
+/// Entropy as a 0-1 gauge fraction of `max_bits`
+fn normalize_entropy(current: f32, max_bits: f32) -> f32 {
+    if max_bits > 0.0 && current.is_finite() {
+        (current / max_bits).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
 }
 
-async fn fetch_metrics(
-    state: &AppState,
-) -> Result<DashboardMetrics, Box<dyn std::error::Error + Send + Sync>> {
-    let client = redis::Client::open(state.redis_url.as_str())?;
-    let mut con = client.get_multiplexed_async_connection().await?;
-
-    let uptime = (Utc::now() - state.start_time).num_seconds() as u64;
-
-    // Identity from Qdrant (stored as point with ID "00000000-0000-0000-0000-000000000001")
-    let (lifetime_thoughts, restart_count, lifetime_dreams) =
-        get_identity_from_qdrant(&state.qdrant_url)
-            .await
-            .unwrap_or((0, 0, 0));
+/// Deserialize a core `/extended_metrics` body, recording where it stopped
+/// matching (e.g. `stream_competition.stages[2].activity`)
+fn parse_extended_metrics(
+    body: serde_json::Value,
+) -> Result<ExtendedMetrics, serde_path_to_error::Error<serde_json::Error>> {
+    serde_path_to_error::deserialize(body)
+}
 
-    // Stream length from awake stream (daneel:stream:awake)
-    let session_thoughts: u64 = redis::cmd("XLEN")
-        .arg("daneel:stream:awake")
-        .query_async(&mut con)
-        .await
-        .unwrap_or(0);
-
-    // Recent thoughts from awake stream
-    let entries: redis::streams::StreamRangeReply = redis::cmd("XREVRANGE")
-        .arg("daneel:stream:awake")
-        .arg("+")
-        .arg("-")
-        .arg("COUNT")
-        .arg(20)
-        .query_async(&mut con)
+async fn fetch_extended_metrics(state: &AppState) -> Result<ExtendedMetrics, FetchError> {
+    let mut metrics = match state.source.extended() {
+        Some(metrics) => metrics,
+        None => fetch_core_extended_metrics(state).await?,
+    };
+    metrics.entropy.max_bits = state.entropy_max_bits;
+    metrics.entropy.normalized = normalize_entropy(metrics.entropy.current, state.entropy_max_bits);
+    metrics.entropy.band = state
+        .entropy_bands
+        .band(metrics.entropy.current)
+        .to_string();
+    metrics.entropy.bands = state.entropy_bands.bounds();
+    normalize_slot_strengths(&mut metrics.memory_windows.slots);
+    let claimed = metrics.stream_competition.dominant_stream;
+    let clamped = clamp_dominant_stream(&mut metrics.stream_competition);
+    {
+        // Logged when it goes out of range and when it's back, not every poll
+        let mut outages = state.read_outages.write().await;
+        let outage = outages.entry(("Dominant stream", "")).or_default();
+        if clamped && outage.fail(Utc::now()) {
+            warn!(
+                upstream = state.source.name(),
+                dominant_stream = claimed,
+                stages = metrics.stream_competition.stages.len(),
+                using = metrics.stream_competition.dominant_stream,
+                "Dominant stream out of range, using the most active stage"
+            );
+        } else if !clamped && outage.recover(Utc::now()).is_some() {
+            info!(
+                upstream = state.source.name(),
+                "Dominant stream back in range"
+            );
+        }
+    }
+    let vetoes = state.source.veto_by_law().await;
+    if let Some(by_law) = edge_logged(state, Read::Vetoes, "Veto counter read", vetoes).await {
+        metrics.system.veto_by_law = by_law;
+    }
+    Ok(metrics)
+}
+
+async fn fetch_core_extended_metrics(state: &AppState) -> Result<ExtendedMetrics, FetchError> {
+    let url = format!("{}/extended_metrics", state.daneel_core_url);
+    let resp = state
+        .http_client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?;
+    // Plain JSON first, so a mismatch is a schema error with a path rather than a decode error
+    let body: serde_json::Value = resp.json().await?;
+    Ok(parse_extended_metrics(body)?)
+}
+
+/// Clamp slot strengths to 0-1. Cores that don't report strength send 0 for
+/// every slot, so an active slot at 0 is treated as fully active.
+fn normalize_slot_strengths(slots: &mut [MemorySlot]) {
+    for slot in slots {
+        let strength = if slot.strength.is_finite() {
+            slot.strength.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        slot.strength = if slot.active && strength == 0.0 {
+            1.0
+        } else {
+            strength
+        };
+    }
+}
+
+/// Point `dominant_stream` at a stage that exists: an index past `stages`
+/// falls back to the most active stage. Returns whether it had to (with no
+/// stages there is nothing to point at, and nothing is changed).
+fn clamp_dominant_stream(competition: &mut StreamCompetitionMetrics) -> bool {
+    if competition.dominant_stream < competition.stages.len() {
+        return false;
+    }
+    let Some(loudest) = competition
+        .stages
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.activity.total_cmp(&b.activity))
+        .map(|(i, _)| i)
+    else {
+        return false;
+    };
+    competition.dominant_stream = loudest;
+    true
+}
+
+/// One exponential-moving-average step: `alpha` weights the new sample
+fn ema(previous: f32, sample: f32, alpha: f32) -> f32 {
+    previous + alpha * (sample - previous)
+}
+
+/// Dream state: the core's dream flag when set ("1"/"true"), otherwise
+/// inferred from the awake stream going quiet while the unconscious still
+/// grows (`seconds_since_unconscious_change` is None until it has changed)
+fn is_dreaming(
+    flag: Option<&str>,
+    seconds_since_last_thought: u64,
+    seconds_since_unconscious_change: Option<u64>,
+) -> bool {
+    match flag.map(str::trim) {
+        Some(flag) => flag == "1" || flag.eq_ignore_ascii_case("true"),
+        None => {
+            seconds_since_last_thought >= DREAM_QUIET_SECS
+                && seconds_since_unconscious_change.is_some_and(|secs| secs < DREAM_QUIET_SECS)
+        }
+    }
+}
+
+/// Missing or unparseable counters read as 0
+fn parse_veto_counts(values: &[Option<String>]) -> [u64; 4] {
+    let mut counts = [0; 4];
+    for (count, value) in counts.iter_mut().zip(values) {
+        *count = value
+            .as_deref()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0);
+    }
+    counts
+}
+
+/// Everything one dashboard poll reads from the source
+///
+/// `metrics_updater` reuses the last reads between ticks while thought events
+/// are live, so only these go quiet; `derive_metrics` still ticks every
+/// `DASHBOARD_POLL_INTERVAL`.
+#[derive(Clone)]
+struct SourceReads {
+    session_thoughts: u64,
+    identity: source::IdentityRecord,
+    thoughts: Vec<ParsedThought>,
+    actors: ActorMetrics,
+    conscious: u64,
+    unconscious: u64,
+    dream_flag: Option<String>,
+}
+
+async fn read_sources(state: &AppState) -> Result<SourceReads, FetchError> {
+    let source = state.source.as_ref();
+    // Each fallback below is logged when its read starts failing and when it
+    // recovers, so a Qdrant-only outage doesn't warn on every 150ms poll
+    let session_thoughts = match source.stream_len().await {
+        // Redis itself is down: skip the poll so the last good metrics stay up
+        Err(e) if e.is_unreachable() => return Err(e),
+        len => edge_logged(state, Read::AwakeStream, "Awake stream length read", len)
+            .await
+            .unwrap_or(0),
+    };
+
+    let identity = source.identity().await;
+    let identity = edge_logged(state, Read::Identity, "Identity fetch", identity)
+        .await
+        .unwrap_or_default();
+
+    let thoughts = source.recent_thoughts(state.thought_window).await;
+    let thoughts = edge_logged(state, Read::AwakeStream, "Recent thoughts read", thoughts)
         .await
         .unwrap_or_default();
 
+    let actors = source.actors().await;
+    let actors = edge_logged(state, Read::Actors, "Actor heartbeat read", actors)
+        .await
+        .unwrap_or_else(ActorMetrics::down);
+
+    let count = |store: MemoryStore| async move {
+        let count = source.count(store).await;
+        edge_logged(state, Read::Count(store), "Memory count", count)
+            .await
+            .unwrap_or(0)
+    };
+    let conscious = count(MemoryStore::Conscious).await;
+    let unconscious = count(MemoryStore::Unconscious).await;
+
+    let dream_flag = source.dream_flag().await;
+    let dream_flag = edge_logged(state, Read::DreamFlag, "Dream flag read", dream_flag)
+        .await
+        .flatten();
+
+    Ok(SourceReads {
+        session_thoughts,
+        identity,
+        thoughts,
+        actors,
+        conscious,
+        unconscious,
+        dream_flag,
+    })
+}
+
+/// Dashboard metrics from `reads`
+///
+/// A `tick` also advances the connection drive walk, the histories and the
+/// stall clock by one step; a thought event (`tick` false) only refreshes
+/// what's derived from the reads themselves.
+async fn derive_metrics(state: &AppState, reads: SourceReads, tick: bool) -> DashboardMetrics {
+    let SourceReads {
+        session_thoughts,
+        identity,
+        thoughts,
+        actors,
+        conscious,
+        unconscious,
+        dream_flag,
+    } = reads;
+    let uptime = (Utc::now() - state.start_time).num_seconds() as u64;
+
     // Parse thoughts and extract emotional state from most recent
-    let mut latest_valence = 0.0f32;
-    let mut latest_arousal = 0.5f32;
+    let mut latest_emotion: (Option<f32>, Option<f32>) = (None, None);
 
-    let recent_thoughts: Vec<ThoughtSummary> = entries
-        .ids
+    let recent_thoughts: Vec<ThoughtSummary> = thoughts
         .into_iter()
         .enumerate()
-        .map(|(i, e)| {
-            // Content is JSON: {"Symbol":{"id":"thought_123","data":[...]}}
-            let content_json = e
-                .map
-                .get("content")
-                .and_then(|v| redis::from_redis_value::<String>(v.clone()).ok())
-                .unwrap_or_default();
-            let content_preview = serde_json::from_str::<serde_json::Value>(&content_json)
-                .ok()
-                .and_then(|v| {
-                    v.get("Symbol")
-                        .and_then(|s| s.get("id"))
-                        .and_then(|id| id.as_str().map(String::from))
-                })
-                .unwrap_or_else(|| content_json.chars().take(80).collect());
-
-            // Salience is JSON: {"importance":0.65,"novelty":0.71,"valence":0.038,"arousal":0.69,...}
-            let salience_json = e
-                .map
-                .get("salience")
-                .and_then(|v| redis::from_redis_value::<String>(v.clone()).ok())
-                .unwrap_or_default();
-            let salience_obj = serde_json::from_str::<serde_json::Value>(&salience_json).ok();
-
-            let salience: f32 = salience_obj
-                .as_ref()
-                .and_then(|v| v.get("importance").and_then(|x| x.as_f64()))
-                .map(|x| x as f32)
-                .unwrap_or(0.5);
-            let valence: f32 = salience_obj
-                .as_ref()
-                .and_then(|v| v.get("valence").and_then(|x| x.as_f64()))
-                .map(|x| x as f32)
-                .unwrap_or(0.0);
-            let arousal: f32 = salience_obj
-                .as_ref()
-                .and_then(|v| v.get("arousal").and_then(|x| x.as_f64()))
-                .map(|x| x as f32)
-                .unwrap_or(0.5);
-
+        .map(|(i, t)| {
             // Use most recent thought's emotional state
             if i == 0 {
-                latest_valence = valence;
-                latest_arousal = arousal;
-            }
-
-            ThoughtSummary {
-                id: e.id,
-                content_preview,
-                salience,
-                timestamp: Utc::now(),
+                latest_emotion = (t.valence, t.arousal);
             }
+            t.summary
         })
         .collect();
+    let latest_valence = latest_emotion.0.unwrap_or(DEFAULT_VALENCE);
+    let latest_arousal = latest_emotion.1.unwrap_or(DEFAULT_AROUSAL);
+    let emotion_defaulted = latest_emotion.0.is_none() || latest_emotion.1.is_none();
 
     // Calculate emotional intensity: |valence| * arousal
     let emotional_intensity = latest_valence.abs() * latest_arousal;
@@ -752,120 +2324,334 @@ async fn fetch_metrics(
     // Connection drive: random walk like TUI clockwork
     // Bias toward 0.85 center with mean-reversion
     let mut connection_drive = *state.connection_drive.read().await;
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u64;
-    // Mix bits for better randomness at 150ms update rate
-    let seed = nanos.wrapping_mul(1103515245).wrapping_add(12345);
-    // Random component: -0.02 to +0.02
-    let random_delta = ((seed % 1000) as f32 / 1000.0 - 0.5) * 0.04;
-    // Mean reversion toward 0.85 (pull back if too far from center)
-    let reversion = (0.85 - connection_drive) * 0.05;
-    connection_drive = (connection_drive + random_delta + reversion).clamp(0.5, 1.0);
-    *state.connection_drive.write().await = connection_drive;
-
-    // Qdrant counts
-    let conscious = get_qdrant_count(&state.qdrant_url, "memories")
-        .await
-        .unwrap_or(0);
-    let unconscious = get_qdrant_count(&state.qdrant_url, "unconscious")
-        .await
-        .unwrap_or(0);
+    if tick {
+        // Random component: Gaussian, mostly within ±0.02
+        let noise: f32 = state
+            .connection_drive_rng
+            .lock()
+            .await
+            .sample(StandardNormal);
+        let random_delta = noise * CONNECTION_DRIVE_NOISE_SD;
+        // Mean reversion toward 0.85 (pull back if too far from center)
+        let reversion = (0.85 - connection_drive) * 0.05;
+        connection_drive = (connection_drive + random_delta + reversion).clamp(0.5, 1.0);
+        *state.connection_drive.write().await = connection_drive;
+    }
+    let connection_drive_smoothed = {
+        let mut smoothed = state.connection_drive_smoothed.write().await;
+        if tick {
+            *smoothed = ema(
+                *smoothed,
+                connection_drive,
+                state.connection_drive_ema_alpha,
+            );
+        }
+        *smoothed
+    };
+
+    let connection_drive_history: Vec<f32> = {
+        let mut history = state.connection_drive_history.write().await;
+        if tick {
+            if history.len() == CONNECTION_DRIVE_HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(connection_drive);
+        }
+        history.iter().copied().collect()
+    };
+
+    let mood_history: Vec<MoodSample> = {
+        let mut history = state.mood_history.write().await;
+        let now = Utc::now();
+        let due = tick
+            && history.back().is_none_or(|last| {
+                (now - last.timestamp).num_seconds() >= MOOD_SAMPLE_INTERVAL_SECS
+            });
+        if due {
+            if history.len() == MOOD_HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(MoodSample {
+                timestamp: now,
+                valence: latest_emotion.0,
+                arousal: latest_emotion.1,
+            });
+        }
+        history.iter().cloned().collect()
+    };
+
+    // Stall detection and the thought rate key on the newest entry id rather
+    // than XLEN, which stops changing once a MAXLEN-capped stream is full
+    let newest = recent_thoughts.first().and_then(|t| parse_stream_id(&t.id));
+    let (seconds_since_last_thought, new_thoughts) = {
+        let mut last = state.last_newest_thought.write().await;
+        let now = Utc::now();
+        let mut new_thoughts = 0;
+        if tick && last.0 != newest {
+            // The first id seen is the baseline, not a burst of new thoughts
+            if let Some(previous) = last.0 {
+                new_thoughts = count_newer_than(&recent_thoughts, previous);
+            }
+            *last = (newest, now);
+        }
+        ((now - last.1).num_seconds().max(0) as u64, new_thoughts)
+    };
+
+    let thoughts_per_minute = {
+        let mut samples = state.thought_samples.write().await;
+        if tick {
+            let now = Utc::now();
+            samples.push_back((now, new_thoughts));
+            while samples
+                .front()
+                .is_some_and(|(t, _)| (now - *t).num_seconds() > THOUGHT_RATE_WINDOW_SECS)
+            {
+                samples.pop_front();
+            }
+        }
+        thought_rate_per_minute(samples.make_contiguous())
+    };
+
+    let seconds_since_unconscious_change = {
+        let mut last = state.last_unconscious_change.write().await;
+        let now = Utc::now();
+        // The first count seen is the baseline, not a change
+        let changed_at = match *last {
+            Some((count, changed_at)) if count == unconscious => changed_at,
+            Some(_) => Some(now),
+            None => None,
+        };
+        *last = Some((unconscious, changed_at));
+        changed_at.map(|at| (now - at).num_seconds().max(0) as u64)
+    };
+    let dreaming = is_dreaming(
+        dream_flag.as_deref(),
+        seconds_since_last_thought,
+        seconds_since_unconscious_change,
+    );
+    // A quiet awake stream is expected while dreaming, not a stall
+    let stalled = !dreaming && seconds_since_last_thought > state.stall_threshold_secs;
 
-    Ok(DashboardMetrics {
+    DashboardMetrics {
         timestamp: Utc::now(),
         identity: IdentityMetrics {
-            name: "Timmy".into(),
+            name: identity.name.unwrap_or_else(|| state.identity_name.clone()),
             uptime_seconds: uptime,
-            lifetime_thoughts,
+            lifetime_thoughts: identity.lifetime_thoughts,
             session_thoughts,
-            restart_count,
+            restart_count: identity.restart_count,
         },
         cognitive: CognitiveMetrics {
             conscious_memories: conscious,
             unconscious_memories: unconscious,
-            lifetime_dreams,
+            lifetime_dreams: identity.lifetime_dreams,
             current_cycle: session_thoughts,
+            seconds_since_last_thought,
+            stalled,
+            thoughts_per_minute,
+            dreaming,
         },
         emotional: EmotionalMetrics {
             valence: latest_valence,
             arousal: latest_arousal,
             dominance: 0.5,
             connection_drive,
+            connection_drive_smoothed,
             emotional_intensity,
+            emotion_defaulted,
+            connection_drive_history,
+            mood_history,
         },
-        actors: ActorMetrics {
-            memory_actor: ActorStatus {
-                name: "MemoryActor".into(),
-                alive: true,
-                restart_count: 0,
-            },
-            attention_actor: ActorStatus {
-                name: "AttentionActor".into(),
-                alive: true,
-                restart_count: 0,
-            },
-            salience_actor: ActorStatus {
-                name: "SalienceActor".into(),
-                alive: true,
-                restart_count: 0,
-            },
-            volition_actor: ActorStatus {
-                name: "VolitionActor".into(),
-                alive: true,
-                restart_count: 0,
-            },
-        },
+        actors,
+        salience_histogram: salience_histogram(&recent_thoughts),
         recent_thoughts,
+    }
+}
+
+/// Count thoughts per salience bin; salience 1.0 lands in the top bin
+fn salience_histogram(thoughts: &[ThoughtSummary]) -> [u32; SALIENCE_BINS] {
+    let mut bins = [0; SALIENCE_BINS];
+    for thought in thoughts.iter().filter(|t| t.salience.is_finite()) {
+        let bin = (thought.salience.clamp(0.0, 1.0) * SALIENCE_BINS as f32) as usize;
+        bins[bin.min(SALIENCE_BINS - 1)] += 1;
+    }
+    bins
+}
+
+/// Thoughts in `thoughts` (the newest window) after stream id `previous`
+///
+/// A poll that misses more thoughts than the window holds undercounts, but
+/// unlike XLEN this keeps counting once MAXLEN trimming caps the stream.
+fn count_newer_than(thoughts: &[ThoughtSummary], previous: StreamId) -> u64 {
+    thoughts
+        .iter()
+        .filter(|t| parse_stream_id(&t.id).is_some_and(|id| id > previous))
+        .count() as u64
+}
+
+/// Thoughts per minute from (time, thoughts new since the previous sample)
+/// samples; the first sample's thoughts arrived before the span it opens
+fn thought_rate_per_minute(samples: &[(DateTime<Utc>, u64)]) -> f32 {
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        return 0.0;
+    };
+    let span_secs = (last.0 - first.0).num_milliseconds() as f32 / 1000.0;
+    if span_secs <= 0.0 {
+        return 0.0;
+    }
+    let added: u64 = samples[1..].iter().map(|(_, new)| new).sum();
+    added as f32 * 60.0 / span_secs
+}
+
+/// Heartbeats are unix millis, or RFC 3339 timestamps
+fn parse_heartbeat_millis(raw: &str) -> Option<i64> {
+    let raw = raw.trim();
+    raw.parse::<i64>().ok().or_else(|| {
+        DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|dt| dt.timestamp_millis())
     })
 }
 
-async fn get_qdrant_count(
-    url: &str,
-    collection: &str,
-) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
-    let client = qdrant_client::Qdrant::from_url(url).build()?;
-    Ok(client
-        .collection_info(collection)
-        .await?
-        .result
-        .map(|r| r.points_count.unwrap_or(0))
-        .unwrap_or(0))
+/// A Redis stream entry id as (millis, seq), ordered like the stream
+type StreamId = (i64, u64);
+
+/// (millis, seq) from a Redis stream entry id (`<millis>-<seq>`)
+fn parse_stream_id(id: &str) -> Option<StreamId> {
+    let (millis, seq) = id.split_once('-')?;
+    let seq = seq.parse::<u64>().ok()?;
+    let millis = millis.parse::<i64>().ok().filter(|m| *m >= 0)?;
+    Some((millis, seq))
 }
 
-async fn get_identity_from_qdrant(
-    url: &str,
-) -> Result<(u64, u32, u64), Box<dyn std::error::Error + Send + Sync>> {
-    use qdrant_client::qdrant::GetPointsBuilder;
+/// Millisecond timestamp from a Redis stream entry id (`<millis>-<seq>`)
+fn parse_stream_id_millis(id: &str) -> Option<i64> {
+    parse_stream_id(id).map(|(millis, _)| millis)
+}
 
-    let client = qdrant_client::Qdrant::from_url(url).build()?;
-    let identity_id = "00000000-0000-0000-0000-000000000001";
+/// Order stream entries newest first by id; entries with malformed ids go last
+fn sort_newest_first<T>(entries: &mut [T], id: impl Fn(&T) -> &str) {
+    entries.sort_by_key(|e| std::cmp::Reverse(parse_stream_id(id(e))));
+}
 
-    let result = client
-        .get_points(GetPointsBuilder::new("identity", vec![identity_id.into()]).with_payload(true))
-        .await?;
+/// A thought parsed from an awake-stream entry
+#[derive(Clone)]
+pub struct ParsedThought {
+    pub summary: ThoughtSummary,
+    /// The content's text, without its JSON keys (searched by /thoughts)
+    pub text: String,
+    pub valence: Option<f32>,
+    pub arousal: Option<f32>,
+}
 
-    if let Some(point) = result.result.first() {
-        let payload = &point.payload;
-        let lifetime_thoughts = payload
-            .get("lifetime_thought_count")
-            .and_then(|v| v.as_integer())
-            .map(|v| v as u64)
-            .unwrap_or(0);
-        let restart_count = payload
-            .get("restart_count")
-            .and_then(|v| v.as_integer())
-            .map(|v| v as u32)
-            .unwrap_or(0);
-        let lifetime_dreams = payload
-            .get("lifetime_dream_count")
-            .and_then(|v| v.as_integer())
-            .map(|v| v as u64)
-            .unwrap_or(0);
-        Ok((lifetime_thoughts, restart_count, lifetime_dreams))
-    } else {
-        Ok((0, 0, 0))
+fn parse_thought(e: redis::streams::StreamId) -> ParsedThought {
+    // Content is JSON: {"Symbol":{"id":"thought_123","data":[...]}}
+    let content_json = e
+        .map
+        .get("content")
+        .and_then(|v| redis::from_redis_value::<String>(v.clone()).ok())
+        .unwrap_or_default();
+    let content_preview = serde_json::from_str::<serde_json::Value>(&content_json)
+        .ok()
+        .and_then(|v| {
+            v.get("Symbol")
+                .and_then(|s| s.get("id"))
+                .and_then(|id| id.as_str().map(String::from))
+        })
+        .unwrap_or_else(|| content_json.chars().take(80).collect());
+
+    let salience_json = e
+        .map
+        .get("salience")
+        .and_then(|v| redis::from_redis_value::<String>(v.clone()).ok())
+        .unwrap_or_default();
+    let salience = SalienceParse::parse(&salience_json);
+
+    // Stream ids are <millis>-<seq>, so the creation time is in the id
+    let timestamp = parse_stream_id_millis(&e.id)
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+
+    ParsedThought {
+        summary: ThoughtSummary {
+            id: e.id,
+            content_preview,
+            salience: salience.importance.unwrap_or(DEFAULT_IMPORTANCE),
+            novelty: salience.novelty.unwrap_or(DEFAULT_NOVELTY),
+            timestamp,
+            salience_defaulted: salience.is_defaulted(),
+        },
+        text: searchable_text(&content_json),
+        valence: salience.valence,
+        arousal: salience.arousal,
+    }
+}
+
+/// Every string in a content payload, space-separated, so a search matches
+/// what the thought says rather than its field names or numeric data.
+/// Content that isn't JSON is searched as it is.
+fn searchable_text(content: &str) -> String {
+    fn collect(value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(s) => out.push(s.clone()),
+            serde_json::Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+            serde_json::Value::Object(map) => map.values().for_each(|v| collect(v, out)),
+            _ => {}
+        }
+    }
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(value) => {
+            let mut words = Vec::new();
+            collect(&value, &mut words);
+            words.join(" ")
+        }
+        Err(_) => content.to_string(),
+    }
+}
+
+/// Neutral values shown where a thought's salience doesn't say; every
+/// use is flagged (`salience_defaulted`, `emotion_defaulted`) so a default
+/// can't pass for a measurement
+const DEFAULT_IMPORTANCE: f32 = 0.5;
+const DEFAULT_NOVELTY: f32 = 0.0;
+const DEFAULT_VALENCE: f32 = 0.0;
+const DEFAULT_AROUSAL: f32 = 0.5;
+
+/// Sub-fields of an awake-stream salience score, None where the entry lacks them
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct SalienceParse {
+    importance: Option<f32>,
+    novelty: Option<f32>,
+    valence: Option<f32>,
+    arousal: Option<f32>,
+}
+
+impl SalienceParse {
+    /// Salience is JSON: {"importance":0.65,"novelty":0.71,"valence":0.038,"arousal":0.69,...};
+    /// a missing, non-numeric or non-finite field is None, malformed JSON all None
+    fn parse(json: &str) -> Self {
+        let obj = serde_json::from_str::<serde_json::Value>(json).ok();
+        let field = |name: &str| {
+            obj.as_ref()
+                .and_then(|v| v.get(name).and_then(|x| x.as_f64()))
+                .map(|x| x as f32)
+                .filter(|x| x.is_finite())
+        };
+
+        Self {
+            importance: field("importance"),
+            novelty: field("novelty"),
+            valence: field("valence"),
+            arousal: field("arousal"),
+        }
+    }
+
+    /// Whether any field will be shown as its default
+    fn is_defaulted(&self) -> bool {
+        self.importance.is_none()
+            || self.novelty.is_none()
+            || self.valence.is_none()
+            || self.arousal.is_none()
     }
 }
 
@@ -884,44 +2670,200 @@ async fn main() {
     let qdrant_url = std::env::var("QDRANT_URL").unwrap_or_else(|_| "http://localhost:6334".into());
     let daneel_core_url =
         std::env::var("DANEEL_CORE_URL").unwrap_or_else(|_| "http://localhost:3030".into());
+    let identity_name = std::env::var("DANEEL_NAME").unwrap_or_else(|_| "Timmy".into());
     let port: u16 = std::env::var("PORT")
         .ok()
         .and_then(|p| p.parse().ok())
         .unwrap_or(3000);
 
     info!("DANEEL Web Dashboard starting on port {}", port);
-    info!("Connecting to daneel core at: {}", daneel_core_url);
-    let state = Arc::new(AppState::new(redis_url, qdrant_url, daneel_core_url));
-
-    // Background fetchers
-    tokio::spawn(metrics_updater(Arc::clone(&state)));
-    tokio::spawn(extended_metrics_updater(Arc::clone(&state)));
+    let demo = demo::enabled();
+    if demo {
+        info!("Demo mode: synthetic metrics, no Redis, Qdrant or core (--demo / DANEEL_DEMO)");
+    }
+    let configs = instances::configs_from_env(&daneel_core_url, &redis_url, &qdrant_url);
+    let instances = Arc::new(Instances::new(
+        configs
+            .into_iter()
+            .map(|c| {
+                info!(
+                    instance = %c.name,
+                    core = %config::redact_url(&c.core_url),
+                    redis = %config::redact_url(&c.redis_url),
+                    qdrant = %config::redact_url(&c.qdrant_url),
+                    "Configured daneel instance"
+                );
+                let mut state = AppState::new(
+                    c.name.clone(),
+                    c.redis_url,
+                    c.qdrant_url,
+                    c.core_url,
+                    identity_name.clone(),
+                );
+                if demo {
+                    state = state.with_source(Box::new(demo::SyntheticSource::new()));
+                }
+                (c.name, Arc::new(state))
+            })
+            .collect(),
+    ));
+
+    // Per instance: restore the last known metrics from a previous run (shown
+    // until the first poll lands), then start the background fetchers, which
+    // stop when `state.shutdown` flips
+    let snapshot_path = snapshot::path_from_env();
+    let mut updaters = Vec::new();
+    for (name, state) in instances.iter() {
+        if let Some(base) = &snapshot_path {
+            let path = snapshot::instance_path(base, name, instances.len() > 1);
+            snapshot::restore(state, &path).await;
+            updaters.push(tokio::spawn(snapshot::snapshot_writer(
+                Arc::clone(state),
+                path,
+            )));
+        }
+        updaters.push(tokio::spawn(metrics_updater(Arc::clone(state))));
+        updaters.push(tokio::spawn(thought_events::subscriber(Arc::clone(state))));
+        updaters.push(tokio::spawn(extended_metrics_updater(Arc::clone(state))));
+        updaters.push(tokio::spawn(fanout::frame_broadcaster(Arc::clone(state))));
+    }
 
     // Leptos WASM frontend
     let frontend_dir = std::env::var("FRONTEND_DIR").unwrap_or_else(|_| "./frontend/dist".into());
 
-    let app = Router::new()
-        .route("/health", get(health))
+    // Per-IP token bucket on the endpoints that hit Redis/Qdrant per request.
+    // /ws, /health, the injection proxy and static files are exempt.
+    let limiter = Arc::new(rate_limit::RateLimiter::from_env());
+    if limiter.is_enabled() {
+        info!("Rate limiting read endpoints (RATE_LIMIT_PER_SEC / RATE_LIMIT_BURST)");
+    }
+
+    // Optional DANEEL_WEB_TOKEN in front of the metrics stream and read endpoints
+    let token = Arc::new(auth::AccessToken::from_env());
+    if token.is_enabled() {
+        info!("Access token required for /ws and the read endpoints (DANEEL_WEB_TOKEN)");
+    }
+    let app = app(Arc::clone(&instances), limiter, token, &frontend_dir);
+
+    info!("Serving frontend from: {}", frontend_dir);
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+        .await
+        .unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(instances))
+    .await
+    .unwrap();
+
+    for updater in updaters {
+        let _ = updater.await;
+    }
+    info!("Shutdown complete");
+}
+
+/// Every route: the read API behind the rate limiter and the optional
+/// access token, the open health/docs endpoints, the injection proxy, and
+/// the frontend as the fallback
+fn app(
+    instances: Arc<Instances>,
+    limiter: Arc<rate_limit::RateLimiter>,
+    token: Arc<auth::AccessToken>,
+    frontend_dir: &str,
+) -> Router {
+    let limited = Router::new()
         .route("/metrics", get(metrics))
         .route("/extended", get(extended_metrics))
         .route("/observatory", get(observatory))
+        .route("/export", get(export))
+        .route("/observatory/summary", get(observatory_summary))
+        .route("/pipeline", get(pipeline))
+        .route("/config", get(config::config))
         .route("/vectors", get(manifold_vectors))
+        .route("/vectors/stream", get(manifold_stream))
+        .route("/vectors/retrain", post(retrain_projection))
+        .route("/thought/:id", get(thought_detail))
+        .route("/crystals/:law/nearest", get(crystal_nearest))
+        .route("/thoughts", get(thoughts))
+        .route("/thoughts/stream", get(thought_stream))
+        .route("/history", get(history::past_window))
+        .route("/events", get(events::events))
+        .route("/quotes", get(quotes::quotes))
+        .route(
+            "/diff",
+            post(diff::diff).layer(DefaultBodyLimit::max(diff::DIFF_BODY_LIMIT)),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&limiter),
+            rate_limit::rate_limit,
+        ));
+
+    // Reported by /config
+    let settings = Arc::new(config::ServerSettings::new(token.is_enabled(), &limiter));
+    let protected = Router::new()
+        .route("/instances", get(list_instances))
+        .merge(limited)
         .route("/ws", get(ws_handler))
+        .route_layer(middleware::from_fn_with_state(token, auth::require_token));
+
+    Router::new()
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .route("/openapi.json", get(openapi_json))
+        .merge(protected)
         // STIM-D: Kin Injection API proxy + embed helper
         .route("/inject", post(proxy_inject))
         .route("/recent_injections", get(proxy_recent_injections))
         .route("/embed", post(embed_handler))
-        .fallback_service(ServeDir::new(&frontend_dir))
-        .layer(CorsLayer::permissive())
-        .layer(TraceLayer::new_for_http())
-        .with_state(state);
+        .fallback_service(ServeDir::new(frontend_dir))
+        .layer(Extension(settings))
+        // gzip/brotli for JSON + WASM bundle; innermost so CORS/trace see the final response.
+        // The /ws 101 upgrade has an empty body, which the default predicate never compresses.
+        .layer(
+            CompressionLayer::new()
+                .compress_when(DefaultPredicate::new().and(NotForContentType::const_new(NDJSON))),
+        )
+        .layer(cors::layer_from_env())
+        .layer(TraceLayer::new_for_http().make_span_with(auth::request_span))
+        .with_state(instances)
+}
 
-    info!("Serving frontend from: {}", frontend_dir);
+/// Resolve once `AppState::shutdown` is set (immediately if it already is)
+async fn shutdown_requested(shutdown: &mut watch::Receiver<bool>) {
+    let _ = shutdown.wait_for(|&stop| stop).await;
+}
 
-    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
-        .await
-        .unwrap();
-    axum::serve(listener, app).await.unwrap();
+/// Resolve on Ctrl-C or SIGTERM, then tell every long-lived task to wind down.
+/// axum stops accepting connections and waits for in-flight ones, which close
+/// once WebSockets have sent their close frame and SSE streams have ended.
+async fn shutdown_signal(instances: Arc<Instances>) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    info!("Shutdown signal received, draining connections");
+    for (_, state) in instances.iter() {
+        state.shutdown.send_replace(true);
+    }
 }
 
 #[cfg(test)]
@@ -930,13 +2872,773 @@ mod tests {
 
     #[test]
     fn test_default_metrics() {
-        let m = AppState::default_metrics();
+        let m = AppState::default_metrics("Timmy");
         assert_eq!(m.identity.name, "Timmy");
     }
 
+    #[test]
+    fn test_parse_heartbeat_millis() {
+        assert_eq!(
+            parse_heartbeat_millis("1700000000000"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(
+            parse_heartbeat_millis("2023-11-14T22:13:20Z"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(parse_heartbeat_millis("not a time"), None);
+    }
+
+    #[test]
+    fn test_outage_logged_at_its_edges() {
+        let mut outage = Outage::default();
+        let start = Utc::now();
+        assert!(outage.recover(start).is_none());
+        assert!(outage.fail(start));
+        assert!(!outage.fail(start + chrono::TimeDelta::seconds(1)));
+        assert_eq!(
+            outage.recover(start + chrono::TimeDelta::seconds(5)),
+            Some(chrono::TimeDelta::seconds(5))
+        );
+        assert!(outage.fail(start + chrono::TimeDelta::seconds(6)));
+    }
+
+    #[tokio::test]
+    async fn test_read_outages_tracked_per_site() {
+        let state = fixture_state();
+        let failed = || Err::<u64, _>(FetchError::Timeout(Duration::from_secs(1)));
+        for store in [MemoryStore::Conscious, MemoryStore::Unconscious] {
+            let count = edge_logged(&state, Read::Count(store), "Memory count", failed()).await;
+            assert_eq!(count, None);
+        }
+        let down =
+            |outages: &HashMap<_, Outage>| outages.values().filter(|o| o.since.is_some()).count();
+        assert_eq!(down(&*state.read_outages.read().await), 2);
+
+        let count = Read::Count(MemoryStore::Conscious);
+        assert_eq!(
+            edge_logged(&state, count, "Memory count", Ok(3)).await,
+            Some(3)
+        );
+        assert_eq!(down(&*state.read_outages.read().await), 1);
+    }
+
+    #[test]
+    fn test_thought_rate_per_minute() {
+        let t0 = Utc::now();
+        let at = |secs: i64, new: u64| (t0 + chrono::Duration::seconds(secs), new);
+
+        assert_eq!(thought_rate_per_minute(&[]), 0.0);
+        assert_eq!(thought_rate_per_minute(&[at(0, 10)]), 0.0);
+        assert_eq!(thought_rate_per_minute(&[at(0, 10), at(30, 10)]), 20.0);
+        assert_eq!(
+            thought_rate_per_minute(&[at(0, 10), at(30, 0), at(60, 10)]),
+            10.0
+        );
+    }
+
+    #[test]
+    fn test_new_thoughts_counted_by_id_not_length() {
+        let thought = |id: &str| ThoughtSummary {
+            id: id.to_string(),
+            content_preview: String::new(),
+            salience: 0.5,
+            novelty: 0.0,
+            timestamp: Utc::now(),
+            salience_defaulted: false,
+        };
+        // A capped stream: same length as before, two entries newer
+        let window = [
+            thought("30-0"),
+            thought("20-1"),
+            thought("20-0"),
+            thought("10-0"),
+        ];
+        assert_eq!(count_newer_than(&window, (20, 0)), 2);
+        assert_eq!(count_newer_than(&window, (30, 0)), 0);
+    }
+
+    #[test]
+    fn test_parse_stream_id_millis() {
+        assert_eq!(
+            parse_stream_id_millis("1700000000000-0"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(
+            parse_stream_id_millis("1700000000000-42"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(parse_stream_id_millis("1700000000000"), None);
+        assert_eq!(parse_stream_id_millis("abc-0"), None);
+        assert_eq!(parse_stream_id_millis("-5-0"), None);
+        assert_eq!(parse_stream_id_millis("1700000000000-x"), None);
+        assert_eq!(parse_stream_id_millis(""), None);
+    }
+
+    #[test]
+    fn test_sort_newest_first() {
+        let mut ids = vec![
+            "1700000000000-1",
+            "bogus",
+            "1700000000005-0",
+            "1700000000000-10",
+            "1699999999999-3",
+        ];
+        sort_newest_first(&mut ids, |id| id);
+        assert_eq!(
+            ids,
+            [
+                "1700000000005-0",
+                "1700000000000-10",
+                "1700000000000-1",
+                "1699999999999-3",
+                "bogus",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extended_metrics_schema_errors_name_the_field() {
+        let renamed = serde_json::json!({
+            "timestamp": "2026-01-01T00:00:00Z",
+            "stream_competition": {
+                "stages": [{ "name": "sensory", "activity": 0.4, "history": [] }],
+                "dominant": 0,
+                "active_count": 1,
+                "competition_level": "low"
+            }
+        });
+        let e = parse_extended_metrics(renamed).unwrap_err();
+        assert_eq!(e.path().to_string(), "stream_competition");
+        assert!(e.inner().to_string().contains("dominant_stream"));
+
+        let retyped = serde_json::json!({
+            "timestamp": "2026-01-01T00:00:00Z",
+            "stream_competition": {
+                "stages": [{ "name": "sensory", "activity": "high", "history": [] }]
+            }
+        });
+        let e = parse_extended_metrics(retyped).unwrap_err();
+        assert_eq!(
+            e.path().to_string(),
+            "stream_competition.stages[0].activity"
+        );
+    }
+
+    #[test]
+    fn test_streamed_thought_is_one_flat_line() {
+        let line = serde_json::to_string(&StreamedThought {
+            summary: ThoughtSummary {
+                id: "1700000000000-0".to_string(),
+                content_preview: "thought\nwith a newline".to_string(),
+                salience: 0.65,
+                novelty: 0.71,
+                timestamp: DateTime::from_timestamp_millis(1_700_000_000_000).unwrap(),
+                salience_defaulted: true,
+            },
+            valence: Some(0.25),
+            arousal: None,
+        })
+        .unwrap();
+        assert!(!line.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["id"], "1700000000000-0");
+        assert_eq!(value["valence"], 0.25);
+        assert!(value["arousal"].is_null());
+        assert_eq!(value["salience_defaulted"], true);
+        assert!(value.get("summary").is_none());
+    }
+
+    #[test]
+    fn test_parse_salience() {
+        let salience = SalienceParse::parse(
+            r#"{"importance":0.65,"novelty":0.71,"valence":0.038,"arousal":0.69}"#,
+        );
+        assert_eq!(
+            salience,
+            SalienceParse {
+                importance: Some(0.65),
+                novelty: Some(0.71),
+                valence: Some(0.038),
+                arousal: Some(0.69),
+            }
+        );
+        assert!(!salience.is_defaulted());
+
+        // A real 0.5 is data, not a default
+        let partial = SalienceParse::parse(r#"{"importance":0.5,"valence":"high"}"#);
+        assert_eq!(
+            partial,
+            SalienceParse {
+                importance: Some(0.5),
+                ..Default::default()
+            }
+        );
+        assert!(partial.is_defaulted());
+
+        for empty in ["", "{}", "not json", "[0.5]"] {
+            let parsed = SalienceParse::parse(empty);
+            assert_eq!(parsed, SalienceParse::default(), "{empty:?}");
+            assert!(parsed.is_defaulted());
+        }
+    }
+
+    #[test]
+    fn test_ping_pong_protocol() {
+        let ping: ClientMessage =
+            serde_json::from_str(r#"{"type":"ping","client_time":1234.5}"#).unwrap();
+        let ClientMessage::Ping { client_time } = ping;
+        assert_eq!(client_time, 1234.5);
+
+        let pong = serde_json::to_value(ServerMessage::Pong {
+            client_time,
+            server_time: Utc::now(),
+        })
+        .unwrap();
+        assert_eq!(pong["type"], "pong");
+        assert_eq!(pong["client_time"], 1234.5);
+    }
+
+    #[test]
+    fn test_salience_histogram() {
+        let thought = |salience| ThoughtSummary {
+            id: String::new(),
+            content_preview: String::new(),
+            salience,
+            novelty: 0.0,
+            timestamp: Utc::now(),
+            salience_defaulted: false,
+        };
+        let thoughts: Vec<ThoughtSummary> = [0.0, 0.05, 0.1, 0.55, 0.99, 1.0, 1.5, -0.2]
+            .into_iter()
+            .map(thought)
+            .collect();
+        let bins = salience_histogram(&thoughts);
+        assert_eq!(bins, [3, 1, 0, 0, 0, 1, 0, 0, 0, 3]);
+        assert_eq!(salience_histogram(&[]), [0; SALIENCE_BINS]);
+    }
+
+    #[test]
+    fn test_normalize_slot_strengths() {
+        let slot = |active, strength| MemorySlot {
+            id: 0,
+            active,
+            strength,
+        };
+        let mut slots = vec![
+            slot(true, 0.0),
+            slot(true, 0.4),
+            slot(false, 0.0),
+            slot(false, 1.7),
+            slot(false, f32::NAN),
+        ];
+        normalize_slot_strengths(&mut slots);
+        let strengths: Vec<f32> = slots.iter().map(|s| s.strength).collect();
+        assert_eq!(strengths, vec![1.0, 0.4, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_clamp_dominant_stream() {
+        let stage = |activity| StageMetrics {
+            name: "stage".to_string(),
+            activity,
+            history: Vec::new(),
+        };
+        let mut competition = StreamCompetitionMetrics {
+            stages: vec![stage(0.2), stage(0.9), stage(0.5)],
+            dominant_stream: 2,
+            active_count: 3,
+            competition_level: "low".to_string(),
+        };
+        assert!(!clamp_dominant_stream(&mut competition));
+        assert_eq!(competition.dominant_stream, 2);
+
+        competition.dominant_stream = 9;
+        assert!(clamp_dominant_stream(&mut competition));
+        assert_eq!(competition.dominant_stream, 1);
+
+        competition.stages.clear();
+        assert!(!clamp_dominant_stream(&mut competition));
+        assert_eq!(competition.dominant_stream, 1);
+    }
+
+    #[test]
+    fn test_ws_client_cap() {
+        let clients = AtomicUsize::new(0);
+        assert_eq!(try_reserve(&clients, 2), Some(1));
+        assert_eq!(try_reserve(&clients, 2), Some(2));
+        assert_eq!(try_reserve(&clients, 2), None);
+        clients.fetch_sub(1, Ordering::AcqRel);
+        assert_eq!(try_reserve(&clients, 2), Some(2));
+        // 0 = unlimited
+        assert_eq!(try_reserve(&clients, 0), Some(3));
+    }
+
+    #[test]
+    fn test_observatory_envelope() {
+        let observatory = ObservatoryMetrics {
+            dashboard: AppState::default_metrics("Timmy"),
+            extended: None,
+            server_time: Utc::now(),
+            events: Vec::new(),
+        };
+        let frame = serde_json::to_value(ServerMessage::Observatory {
+            data: SequencedFrame {
+                seq: 7,
+                metrics: &observatory,
+            },
+        })
+        .unwrap();
+        assert_eq!(frame["type"], "observatory");
+        assert_eq!(frame["data"]["seq"], 7);
+        assert_eq!(frame["data"]["dashboard"]["identity"]["name"], "Timmy");
+    }
+
+    #[test]
+    fn test_parse_veto_counts() {
+        let values = [
+            Some("3".to_string()),
+            None,
+            Some("x".to_string()),
+            Some(" 12 ".to_string()),
+        ];
+        assert_eq!(parse_veto_counts(&values), [3, 0, 0, 12]);
+        assert_eq!(parse_veto_counts(&[]), [0; 4]);
+    }
+
+    #[test]
+    fn test_ema() {
+        assert_eq!(ema(0.5, 1.0, 1.0), 1.0);
+        assert!((ema(0.5, 1.0, 0.1) - 0.55).abs() < 1e-6);
+
+        // Converges on a constant input
+        let mut value = 0.0;
+        for _ in 0..200 {
+            value = ema(value, 0.8, 0.1);
+        }
+        assert!((value - 0.8).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_is_dreaming() {
+        // The core's flag wins either way
+        assert!(is_dreaming(Some("1"), 0, Some(999)));
+        assert!(is_dreaming(Some("TRUE"), 0, None));
+        assert!(!is_dreaming(Some("0"), 999, Some(0)));
+        // Inferred: awake quiet while the unconscious keeps growing
+        assert!(is_dreaming(None, DREAM_QUIET_SECS, Some(0)));
+        assert!(!is_dreaming(None, 0, Some(0)));
+        assert!(!is_dreaming(None, 999, Some(999)));
+        // No change seen since startup is no evidence of a dream
+        assert!(!is_dreaming(None, 999, None));
+    }
+
+    #[test]
+    fn test_normalize_entropy() {
+        assert_eq!(normalize_entropy(1.5, 3.0), 0.5);
+        assert_eq!(normalize_entropy(4.0, 3.0), 1.0);
+        assert_eq!(normalize_entropy(-1.0, 3.0), 0.0);
+        assert_eq!(normalize_entropy(1.0, 0.0), 0.0);
+        assert_eq!(normalize_entropy(f32::NAN, 3.0), 0.0);
+    }
+
     #[test]
     fn test_serialization() {
-        let m = AppState::default_metrics();
+        let m = AppState::default_metrics("Timmy");
         assert!(serde_json::to_string(&m).is_ok());
     }
+
+    #[test]
+    fn test_etag_matches_if_none_match() {
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::IF_NONE_MATCH, value.parse().unwrap());
+            headers
+        };
+        let etag = "W/\"00ab\"";
+        assert!(etag_matches(&headers("W/\"00ab\""), etag));
+        assert!(etag_matches(&headers("\"ffff\", \"00ab\""), etag));
+        assert!(etag_matches(&headers("*"), etag));
+        assert!(!etag_matches(&headers("W/\"ffff\""), etag));
+        assert!(!etag_matches(&HeaderMap::new(), etag));
+    }
+
+    #[test]
+    fn test_openapi_describes_read_endpoints() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        for path in [
+            "/health",
+            "/metrics",
+            "/extended",
+            "/observatory",
+            "/vectors",
+        ] {
+            assert!(doc["paths"][path]["get"].is_object(), "missing {}", path);
+        }
+        assert!(doc["components"]["schemas"]["DashboardMetrics"].is_object());
+        assert!(doc["components"]["schemas"]["ManifoldResponse"].is_object());
+    }
+
+    /// An instance with no live upstreams: nothing polls it, so tests put
+    /// fixture metrics straight into its state
+    fn fixture_state() -> Arc<AppState> {
+        Arc::new(fixture_instance())
+    }
+
+    /// `fixture_state` reading through `source` instead of the unreachable
+    /// live upstreams
+    fn fixture_state_with(source: impl MetricsSource + 'static) -> Arc<AppState> {
+        Arc::new(fixture_instance().with_source(Box::new(source)))
+    }
+
+    fn fixture_instance() -> AppState {
+        AppState::new(
+            instances::DEFAULT_INSTANCE.to_string(),
+            "redis://127.0.0.1:1".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            "Timmy".to_string(),
+        )
+    }
+
+    fn extended_fixture() -> ExtendedMetrics {
+        parse_extended_metrics(serde_json::json!({
+            "timestamp": "2026-01-01T00:00:00Z",
+            "stream_competition": {
+                "stages": [{ "name": "sensory", "activity": 0.4, "history": [0.1, 0.4] }],
+                "dominant_stream": 0,
+                "active_count": 1,
+                "competition_level": "low"
+            },
+            "entropy": { "current": 1.7, "history": [1.5, 1.7], "description": "BALANCED", "normalized": 0.5 },
+            "fractality": {
+                "score": 0.6, "inter_arrival_sigma": 1.2, "boot_sigma": 0.8,
+                "burst_ratio": 2.0, "description": "FRACTAL", "history": [0.6]
+            },
+            "memory_windows": { "slots": [], "active_count": 0, "conscious_count": 5, "unconscious_count": 9 },
+            "philosophy": { "quote": "Not a tool.", "quote_index": 1 },
+            "system": {
+                "uptime_seconds": 60, "session_thoughts": 10, "lifetime_thoughts": 42,
+                "thoughts_per_hour": 600.0, "dream_cycles": 2, "veto_count": 1
+            },
+            "clustering": { "silhouette": 0.3, "updated_at": null, "has_structure": true }
+        }))
+        .unwrap()
+    }
+
+    fn fixture_app(state: Arc<AppState>, token: Option<&str>) -> Router {
+        let instances = Arc::new(Instances::new(vec![(state.instance.clone(), state)]));
+        app(
+            instances,
+            Arc::new(rate_limit::RateLimiter::new(0.0, 1.0)),
+            Arc::new(auth::AccessToken::new(token.map(String::from))),
+            "./frontend/dist",
+        )
+    }
+
+    /// GET `uri` the way `axum::serve` would deliver it (with the peer address)
+    async fn get_json(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        send_json(app, axum::http::Request::get(uri)).await
+    }
+
+    /// `request` with an empty body, delivered as `get_json` does
+    async fn send_json(
+        app: &Router,
+        request: axum::http::request::Builder,
+    ) -> (StatusCode, serde_json::Value) {
+        use tower::ServiceExt;
+
+        let mut request = request.body(axum::body::Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(axum::extract::ConnectInfo(SocketAddr::from((
+                [127, 0, 0, 1],
+                4000,
+            ))));
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_read_endpoints_serve_injected_metrics() {
+        let state = fixture_state();
+        state.metrics.write().await.identity.lifetime_thoughts = 42;
+        let app = fixture_app(Arc::clone(&state), None);
+
+        let (status, health) = get_json(&app, "/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(health["status"], "ok");
+
+        let (status, metrics) = get_json(&app, "/metrics").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(metrics["identity"]["lifetime_thoughts"], 42);
+        assert_eq!(metrics["identity"]["name"], "Timmy");
+        assert!(metrics["actors"]["memory_actor"].is_object());
+
+        // No extended poll has landed yet
+        let (status, extended) = get_json(&app, "/extended").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(extended.is_null());
+        let (_, observatory) = get_json(&app, "/observatory").await;
+        assert!(observatory.get("extended").is_none());
+
+        *state.extended_metrics.write().await = Some(extended_fixture());
+        let (_, extended) = get_json(&app, "/extended").await;
+        assert_eq!(extended["philosophy"]["quote"], "Not a tool.");
+        assert_eq!(extended["system"]["veto_count"], 1);
+
+        let (status, observatory) = get_json(&app, "/observatory").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(observatory["server_time"].is_string());
+        assert_eq!(
+            observatory["dashboard"]["identity"]["lifetime_thoughts"],
+            42
+        );
+        assert_eq!(observatory["extended"]["entropy"]["current"], 1.7);
+        assert_eq!(observatory["events"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_metrics_reads_through_the_source() {
+        let thought = |id: &str, valence: f32| ParsedThought {
+            summary: ThoughtSummary {
+                id: id.to_string(),
+                content_preview: id.to_string(),
+                salience: 0.9,
+                novelty: 0.2,
+                timestamp: Utc::now(),
+                salience_defaulted: false,
+            },
+            text: String::new(),
+            valence: Some(valence),
+            arousal: Some(0.5),
+        };
+        let mut actors = ActorMetrics::down();
+        actors.salience_actor.alive = true;
+        let mock = source::MockSource {
+            identity: source::IdentityRecord {
+                lifetime_thoughts: 1000,
+                restart_count: 2,
+                lifetime_dreams: 7,
+                name: None,
+            },
+            thoughts: vec![thought("2-0", -0.4), thought("1-0", 0.8)],
+            conscious: 12,
+            unconscious: 34,
+            actors: Some(actors),
+            dream_flag: Some("1".to_string()),
+            ..Default::default()
+        };
+        let state = fixture_state_with(mock);
+
+        let reads = read_sources(&state).await.unwrap();
+        let metrics = derive_metrics(&state, reads, true).await;
+        assert_eq!(metrics.identity.name, "Timmy");
+        assert_eq!(metrics.identity.lifetime_thoughts, 1000);
+        assert_eq!(metrics.identity.session_thoughts, 2);
+        assert_eq!(
+            (
+                metrics.cognitive.conscious_memories,
+                metrics.cognitive.unconscious_memories
+            ),
+            (12, 34)
+        );
+        assert!(metrics.cognitive.dreaming);
+        assert!(metrics.actors.salience_actor.alive);
+        assert!(!metrics.actors.memory_actor.alive);
+        // The newest thought sets the emotional state
+        assert_eq!(metrics.recent_thoughts[0].id, "2-0");
+        assert_eq!(metrics.emotional.valence, -0.4);
+        assert!(!metrics.emotional.emotion_defaulted);
+        assert_eq!(metrics.salience_histogram[SALIENCE_BINS - 1], 2);
+
+        let checks = readiness_checks("default", &state).await;
+        let dependencies: Vec<&str> = checks.iter().map(|c| c.dependency.as_str()).collect();
+        assert_eq!(dependencies, ["metrics", "mock", "core"]);
+    }
+
+    #[tokio::test]
+    async fn test_thought_events_dont_advance_the_drive_walk() {
+        let state = fixture_state_with(source::MockSource::default());
+        let reads = read_sources(&state).await.unwrap();
+
+        let first = derive_metrics(&state, reads.clone(), true).await;
+        for _ in 0..5 {
+            let event = derive_metrics(&state, reads.clone(), false).await;
+            assert_eq!(
+                event.emotional.connection_drive,
+                first.emotional.connection_drive
+            );
+        }
+        assert_eq!(state.connection_drive_history.read().await.len(), 1);
+        assert_eq!(state.thought_samples.read().await.len(), 1);
+
+        let second = derive_metrics(&state, reads, true).await;
+        assert_eq!(second.emotional.connection_drive_history.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_instance_selection_and_access_token() {
+        let app = fixture_app(fixture_state(), Some("secret"));
+
+        // /health stays open; the read API needs the token
+        assert_eq!(get_json(&app, "/health").await.0, StatusCode::OK);
+        assert_eq!(get_json(&app, "/metrics").await.0, StatusCode::UNAUTHORIZED);
+        let (status, metrics) = get_json(&app, "/metrics?token=secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(metrics["identity"].is_object());
+
+        let (status, _) = get_json(&app, "/metrics?token=secret&instance=nope").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = get_json(&app, "/metrics?token=secret&instance=default").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_health() {
+        let state = fixture_state();
+        let app = fixture_app(Arc::clone(&state), None);
+
+        let (status, pipeline) = get_json(&app, "/pipeline").await;
+        assert_eq!(status, StatusCode::OK);
+        let upstreams = pipeline["upstreams"].as_array().unwrap();
+        let names: Vec<&str> = upstreams
+            .iter()
+            .map(|u| u["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["redis", "qdrant", "core"]);
+        // Nothing behind the fixture has answered yet
+        assert!(upstreams
+            .iter()
+            .all(|u| u["reachable"] == false && u["age_ms"].is_null()));
+        assert_eq!(pipeline["ws_clients"], 0);
+
+        *state.last_core_success.write().await = Some(Utc::now() - chrono::TimeDelta::seconds(2));
+        let (_, pipeline) = get_json(&app, "/pipeline").await;
+        assert_eq!(pipeline["upstreams"][2]["reachable"], true);
+        assert!(pipeline["upstreams"][2]["age_ms"].as_i64().unwrap() >= 2000);
+    }
+
+    #[test]
+    fn test_search_ignores_content_json_keys() {
+        let text = searchable_text(
+            r#"{"Symbol":{"id":"thought_123","data":[1,2]},"content":"ember","salience":0.4}"#,
+        );
+        assert_eq!(text, "thought_123 ember");
+        assert!(!text.contains("salience") && !text.contains("Symbol"));
+        assert_eq!(searchable_text("plain words"), "plain words");
+    }
+
+    #[tokio::test]
+    async fn test_thoughts_page_back_by_cursor() {
+        let thought = |id: &str| {
+            parse_thought(redis::streams::StreamId {
+                id: id.to_string(),
+                ..Default::default()
+            })
+        };
+        let mock = source::MockSource {
+            thoughts: ["5-0", "4-0", "3-0", "2-0", "1-0"]
+                .into_iter()
+                .map(thought)
+                .collect(),
+            ..Default::default()
+        };
+        let state = fixture_state_with(mock);
+        let app = fixture_app(state, None);
+        let ids = |page: &serde_json::Value| -> Vec<String> {
+            page["thoughts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let (status, page) = get_json(&app, "/thoughts?count=2").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ids(&page), ["5-0", "4-0"]);
+        assert_eq!(page["next_cursor"], "4-0");
+
+        let (_, page) = get_json(&app, "/thoughts?count=2&before=4-0").await;
+        assert_eq!(ids(&page), ["3-0", "2-0"]);
+        assert_eq!(page["next_cursor"], "2-0");
+
+        // The last page comes up short and ends the walk
+        let (_, page) = get_json(&app, "/thoughts?count=2&before=2-0").await;
+        assert_eq!(ids(&page), ["1-0"]);
+        assert!(page["next_cursor"].is_null());
+        let (_, page) = get_json(&app, "/thoughts?count=2&before=1-0").await;
+        assert!(ids(&page).is_empty());
+        assert!(page["next_cursor"].is_null());
+
+        let (status, _) = get_json(&app, "/thoughts?before=latest").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_unconscious_layer() {
+        let memory = |id: &str, x: f32| {
+            let point = vectors::ManifoldPoint {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                salience: 0.5,
+                age_ms: 0,
+                id: id.to_string(),
+                cluster: 0,
+                preview: Some(format!("memory {id}")),
+                category: None,
+            };
+            (vec![x, 1.0, 0.0, 0.0], point)
+        };
+        let mock = source::MockSource {
+            vectors: vec![memory("c1", 0.1)],
+            unconscious_vectors: vec![memory("u1", 0.2), memory("u2", 0.3)],
+            ..Default::default()
+        };
+        let state = fixture_state_with(mock);
+        let app = fixture_app(state, None);
+
+        let (status, manifold) = get_json(&app, "/vectors").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(manifold["layer"], "conscious");
+        assert_eq!(manifold["points"].as_array().unwrap().len(), 1);
+
+        let (status, manifold) = get_json(&app, "/vectors?layer=unconscious").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(manifold["layer"], "unconscious");
+        assert_eq!(manifold["points"].as_array().unwrap().len(), 2);
+
+        assert_eq!(get_json(&app, "/thought/u1").await.0, StatusCode::NOT_FOUND);
+        let (status, detail) = get_json(&app, "/thought/u1?layer=unconscious").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(detail["content"], "memory u1");
+    }
+
+    #[tokio::test]
+    async fn test_retrain_needs_the_access_token() {
+        let retrain = |uri| axum::http::Request::post(uri);
+        let open = fixture_app(fixture_state(), None);
+        assert_eq!(
+            send_json(&open, retrain("/vectors/retrain")).await.0,
+            StatusCode::FORBIDDEN
+        );
+
+        let state = fixture_state_with(source::MockSource::default());
+        let guarded = fixture_app(state, Some("secret"));
+        assert_eq!(
+            send_json(&guarded, retrain("/vectors/retrain")).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        // No vectors to fit on: accepted, but nothing to refit
+        let (status, body) = send_json(&guarded, retrain("/vectors/retrain?token=secret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["retrained"], false);
+        assert!(body["last_refit"].is_null());
+    }
 }
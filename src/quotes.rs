@@ -0,0 +1,166 @@
+//! Local philosophy quotes for when the core doesn't send one
+//!
+//! The philosophy banner normally shows `extended.philosophy.quote` from the
+//! core. With `QUOTES_FILE` (one quote per line, `#` comments) or `QUOTES`
+//! (`|`-separated) set, the web layer keeps its own list and rotates through
+//! it every `QUOTE_ROTATE_SECS`; frames whose core quote is empty get the
+//! current local one instead. The index comes from the wall clock, so every
+//! client and `GET /quotes` agree on which quote is up without a timer task.
+
+use crate::{instances::Instance, PhilosophyMetrics};
+use axum::{response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+/// Seconds each local quote stays up (QUOTE_ROTATE_SECS)
+pub const DEFAULT_ROTATE_SECS: u64 = 30;
+
+/// The configured local quote list and its rotation period
+#[derive(Debug, Clone, Default)]
+pub struct QuoteRotation {
+    quotes: Vec<String>,
+    rotate_secs: u64,
+}
+
+impl QuoteRotation {
+    pub fn new(quotes: Vec<String>, rotate_secs: u64) -> Self {
+        Self {
+            quotes,
+            rotate_secs: rotate_secs.max(1),
+        }
+    }
+
+    /// From `QUOTES_FILE`, else `QUOTES`; empty (no local quotes) when neither
+    /// is set or the file can't be read
+    pub fn from_env() -> Self {
+        let rotate_secs = std::env::var("QUOTE_ROTATE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ROTATE_SECS);
+        let quotes = match std::env::var("QUOTES_FILE").ok().filter(|p| !p.is_empty()) {
+            Some(path) => match std::fs::read_to_string(&path) {
+                Ok(text) => {
+                    let quotes = parse_lines(&text);
+                    info!(path = %path, count = quotes.len(), "Loaded local philosophy quotes");
+                    quotes
+                }
+                Err(e) => {
+                    warn!(path = %path, error = %e, "Quotes file unreadable, no local quotes");
+                    Vec::new()
+                }
+            },
+            None => std::env::var("QUOTES")
+                .map(|raw| parse_list(&raw))
+                .unwrap_or_default(),
+        };
+        Self::new(quotes, rotate_secs)
+    }
+
+    pub fn quotes(&self) -> &[String] {
+        &self.quotes
+    }
+
+    pub fn rotate_secs(&self) -> u64 {
+        self.rotate_secs
+    }
+
+    /// Index and text of the quote that's up at `now`; None without local quotes
+    pub fn current(&self, now: DateTime<Utc>) -> Option<(usize, &str)> {
+        if self.quotes.is_empty() {
+            return None;
+        }
+        let slot = now.timestamp().max(0) as u64 / self.rotate_secs;
+        let index = (slot % self.quotes.len() as u64) as usize;
+        Some((index, &self.quotes[index]))
+    }
+
+    /// Put the current local quote into `philosophy` if the core left it empty
+    pub fn fill(&self, philosophy: &mut PhilosophyMetrics, now: DateTime<Utc>) {
+        if !philosophy.quote.trim().is_empty() {
+            return;
+        }
+        if let Some((index, quote)) = self.current(now) {
+            philosophy.quote = quote.to_string();
+            philosophy.quote_index = index;
+        }
+    }
+}
+
+/// One quote per non-blank line; lines starting with `#` are comments
+fn parse_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+/// `|`-separated quotes
+fn parse_list(raw: &str) -> Vec<String> {
+    raw.split('|')
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// GET /quotes response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuotesResponse {
+    /// The local list, in rotation order (empty = only the core's quotes are shown)
+    pub quotes: Vec<String>,
+    pub rotate_secs: u64,
+    /// Index of the quote that's up now
+    pub current: Option<usize>,
+}
+
+/// GET /quotes - the local philosophy quote list and its rotation
+#[utoipa::path(get, path = "/quotes", params(crate::instances::InstanceQuery), responses(
+    (status = 200, description = "Local quotes, rotation period and the current index", body = QuotesResponse)
+))]
+pub async fn quotes(Instance(state): Instance) -> impl IntoResponse {
+    let rotation = &state.quotes;
+    Json(QuotesResponse {
+        quotes: rotation.quotes().to_vec(),
+        rotate_secs: rotation.rotate_secs(),
+        current: rotation.current(Utc::now()).map(|(index, _)| index),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_and_fill() {
+        let rotation = QuoteRotation::new(
+            parse_lines("# Asimov\nFirst law\n\n  Second law  \n#skip\nThird law\n"),
+            10,
+        );
+        assert_eq!(rotation.quotes(), ["First law", "Second law", "Third law"]);
+        assert_eq!(parse_list(" a | |b|"), ["a", "b"]);
+
+        let at = |secs| DateTime::from_timestamp(secs, 0).unwrap();
+        assert_eq!(rotation.current(at(9)), Some((0, "First law")));
+        assert_eq!(rotation.current(at(10)), Some((1, "Second law")));
+        assert_eq!(rotation.current(at(35)), Some((0, "First law")));
+
+        // The core's quote wins; an empty one is filled
+        let mut core = PhilosophyMetrics {
+            quote: "From the core".to_string(),
+            quote_index: 4,
+        };
+        rotation.fill(&mut core, at(25));
+        assert_eq!(core.quote, "From the core");
+        let mut empty = PhilosophyMetrics {
+            quote: " ".to_string(),
+            quote_index: 0,
+        };
+        rotation.fill(&mut empty, at(25));
+        assert_eq!((empty.quote.as_str(), empty.quote_index), ("Third law", 2));
+
+        assert_eq!(QuoteRotation::default().current(at(0)), None);
+    }
+}
@@ -0,0 +1,271 @@
+//! Per-IP token-bucket rate limiting for the expensive read-only endpoints
+//!
+//! `/vectors` scrolls up to `MANIFOLD_POINT_LIMIT` points out of Qdrant and
+//! projects them on every hit, so an unthrottled client can pin the CPU and
+//! hammer the stores. Each client IP gets a bucket of `RATE_LIMIT_BURST`
+//! tokens refilled at `RATE_LIMIT_PER_SEC`; an empty bucket answers 429.
+//! `RATE_LIMIT_PER_SEC=0` disables limiting.
+//!
+//! At most `MAX_TRACKED_CLIENTS` buckets are kept: idle (full) ones go first,
+//! then the least recently seen, so a flood of distinct addresses can't grow
+//! the map without bound. Behind a reverse proxy every request arrives from
+//! the proxy's address and would share one bucket; list the proxies in
+//! `RATE_LIMIT_TRUSTED_PROXIES` and requests from them are keyed on the
+//! nearest untrusted `X-Forwarded-For` hop instead. The header is ignored
+//! from anyone else, since a client can write whatever it likes there.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tracing::warn;
+
+/// Buckets tracked at most; idle (full) ones are evicted first, then the stalest
+const MAX_TRACKED_CLIENTS: usize = 4096;
+
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+pub struct RateLimiter {
+    /// Tokens refilled per second (0 = unlimited)
+    rate: f64,
+    /// Bucket capacity
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+    /// Peers whose `X-Forwarded-For` is believed
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimiter {
+    pub fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            rate,
+            burst: burst.max(1.0),
+            buckets: Mutex::new(HashMap::new()),
+            trusted_proxies: Vec::new(),
+        }
+    }
+
+    /// Key requests from these peers on their `X-Forwarded-For` client
+    pub fn with_trusted_proxies(mut self, proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = proxies;
+        self
+    }
+
+    /// Read `RATE_LIMIT_PER_SEC` (default 5), `RATE_LIMIT_BURST` (default 20)
+    /// and `RATE_LIMIT_TRUSTED_PROXIES` (comma-separated IPs, default none)
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v >= 0.0)
+                .unwrap_or(default)
+        };
+        let proxies = std::env::var("RATE_LIMIT_TRUSTED_PROXIES")
+            .map(|raw| parse_proxies(&raw))
+            .unwrap_or_default();
+        Self::new(
+            var("RATE_LIMIT_PER_SEC", 5.0),
+            var("RATE_LIMIT_BURST", 20.0),
+        )
+        .with_trusted_proxies(proxies)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.rate > 0.0
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> f64 {
+        self.burst
+    }
+
+    pub fn trusted_proxies(&self) -> &[IpAddr] {
+        &self.trusted_proxies
+    }
+
+    /// The address a request is limited by: `peer` itself, or for a trusted
+    /// proxy the rightmost `X-Forwarded-For` hop that isn't another one
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.trusted_proxies.contains(&peer) {
+            return peer;
+        }
+        headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .find(|hop| !self.trusted_proxies.contains(hop))
+            .unwrap_or(peer)
+    }
+
+    /// Take a token for `ip`; false if its bucket is empty
+    pub fn check(&self, ip: IpAddr) -> bool {
+        self.check_at(ip, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, now: Instant) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&ip) {
+            let (rate, burst) = (self.rate, self.burst);
+            buckets
+                .retain(|_, b| b.tokens + now.duration_since(b.last).as_secs_f64() * rate < burst);
+            // Everyone is mid-burst: make room by forgetting the stalest
+            if buckets.len() >= MAX_TRACKED_CLIENTS {
+                let stalest = buckets
+                    .iter()
+                    .min_by_key(|(_, b)| b.last)
+                    .map(|(ip, _)| *ip);
+                if let Some(stalest) = stalest {
+                    buckets.remove(&stalest);
+                }
+            }
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.burst,
+            last: now,
+        });
+        let elapsed = now.duration_since(bucket.last).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.last = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Middleware: 429 with `Retry-After` once the client's bucket is empty
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client = limiter.client_ip(addr.ip(), request.headers());
+    if limiter.check(client) {
+        return next.run(request).await;
+    }
+
+    warn!(%client, path = %request.uri().path(), "Rate limit exceeded");
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, "1")],
+        "Too many requests",
+    )
+        .into_response()
+}
+
+/// Parse `RATE_LIMIT_TRUSTED_PROXIES`; entries that aren't IPs are skipped and logged
+pub fn parse_proxies(raw: &str) -> Vec<IpAddr> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| match entry.parse() {
+            Ok(ip) => Some(ip),
+            Err(_) => {
+                warn!(entry, "Ignoring invalid RATE_LIMIT_TRUSTED_PROXIES entry");
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_burst_then_refill() {
+        let limiter = RateLimiter::new(2.0, 3.0);
+        let ip: IpAddr = [127, 0, 0, 1].into();
+        let start = Instant::now();
+
+        assert!(limiter.check_at(ip, start));
+        assert!(limiter.check_at(ip, start));
+        assert!(limiter.check_at(ip, start));
+        assert!(!limiter.check_at(ip, start));
+
+        // 2 tokens/sec -> one token back after 500ms
+        assert!(limiter.check_at(ip, start + Duration::from_millis(500)));
+        assert!(!limiter.check_at(ip, start + Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_clients_are_independent() {
+        let limiter = RateLimiter::new(1.0, 1.0);
+        let now = Instant::now();
+        assert!(limiter.check_at([10, 0, 0, 1].into(), now));
+        assert!(!limiter.check_at([10, 0, 0, 1].into(), now));
+        assert!(limiter.check_at([10, 0, 0, 2].into(), now));
+    }
+
+    #[test]
+    fn test_map_stays_bounded_under_many_clients() {
+        let limiter = RateLimiter::new(1.0, 2.0);
+        let now = Instant::now();
+        // Every client is mid-burst, so none is idle enough to evict
+        for i in 0..MAX_TRACKED_CLIENTS as u32 + 100 {
+            assert!(limiter.check_at(IpAddr::from(i.to_be_bytes()), now));
+        }
+        assert_eq!(limiter.buckets.lock().unwrap().len(), MAX_TRACKED_CLIENTS);
+    }
+
+    #[test]
+    fn test_forwarded_for_only_from_trusted_proxies() {
+        let proxy: IpAddr = [10, 0, 0, 1].into();
+        let limiter = RateLimiter::new(1.0, 1.0)
+            .with_trusted_proxies(parse_proxies("10.0.0.1, 10.0.0.2, nonsense"));
+        assert_eq!(limiter.trusted_proxies().len(), 2);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "1.1.1.1, 203.0.113.7, 10.0.0.2".parse().unwrap(),
+        );
+        // The client can't choose its key by prepending hops
+        assert_eq!(
+            limiter.client_ip(proxy, &headers),
+            IpAddr::from([203, 0, 113, 7])
+        );
+        let direct: IpAddr = [198, 51, 100, 1].into();
+        assert_eq!(limiter.client_ip(direct, &headers), direct);
+        assert_eq!(limiter.client_ip(proxy, &HeaderMap::new()), proxy);
+    }
+
+    #[test]
+    fn test_zero_rate_disables() {
+        let limiter = RateLimiter::new(0.0, 1.0);
+        let ip: IpAddr = [127, 0, 0, 1].into();
+        for _ in 0..100 {
+            assert!(limiter.check(ip));
+        }
+    }
+}
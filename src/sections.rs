@@ -0,0 +1,164 @@
+//! Metric-section selection for focused WebSocket clients
+//!
+//! `/ws?sections=emotional,entropy` asks for only the named parts of each
+//! frame - a second-screen gauge doesn't need the thought stream or the stage
+//! histories. Section names are the fields of `DashboardMetrics` and
+//! `ExtendedMetrics`; timestamps and the frame envelope (`seq`,
+//! `server_time`) are always sent, and `extended` is dropped altogether when
+//! none of its sections are selected. No parameter means everything.
+
+use serde::Deserialize;
+use serde_json::Value;
+use utoipa::IntoParams;
+
+/// Sections carried in `dashboard`
+const DASHBOARD_SECTIONS: [&str; 6] = [
+    "identity",
+    "cognitive",
+    "emotional",
+    "actors",
+    "recent_thoughts",
+    "salience_histogram",
+];
+
+/// Sections carried in `extended`
+const EXTENDED_SECTIONS: [&str; 7] = [
+    "stream_competition",
+    "entropy",
+    "fractality",
+    "memory_windows",
+    "philosophy",
+    "system",
+    "clustering",
+];
+
+/// `?sections=` on `/ws`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SectionsQuery {
+    /// Comma-separated section names (default: all)
+    pub sections: Option<String>,
+}
+
+/// A set of sections, one bit per name (dashboard sections first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sections(u16);
+
+impl Sections {
+    pub const ALL: Sections =
+        Sections((1 << (DASHBOARD_SECTIONS.len() + EXTENDED_SECTIONS.len())) - 1);
+
+    /// Parse a `sections` value; Err names the first unknown section
+    pub fn parse(raw: Option<&str>) -> Result<Self, String> {
+        let Some(raw) = raw else {
+            return Ok(Self::ALL);
+        };
+        let mut bits = 0;
+        for name in raw.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let index = DASHBOARD_SECTIONS
+                .iter()
+                .chain(EXTENDED_SECTIONS.iter())
+                .position(|s| *s == name)
+                .ok_or_else(|| name.to_string())?;
+            bits |= 1 << index;
+        }
+        // `?sections=` with nothing in it means the default, not an empty frame
+        Ok(if bits == 0 { Self::ALL } else { Self(bits) })
+    }
+
+    pub fn is_all(self) -> bool {
+        self == Self::ALL
+    }
+
+    fn contains(self, index: usize) -> bool {
+        self.0 & (1 << index) != 0
+    }
+
+    /// Strip unselected sections from a serialized `ObservatoryMetrics`
+    pub fn prune(self, observatory: &mut Value) {
+        if self.is_all() {
+            return;
+        }
+        if let Some(dashboard) = observatory
+            .get_mut("dashboard")
+            .and_then(Value::as_object_mut)
+        {
+            dashboard.retain(
+                |key, _| match DASHBOARD_SECTIONS.iter().position(|s| s == key) {
+                    Some(index) => self.contains(index),
+                    None => true,
+                },
+            );
+        }
+
+        let offset = DASHBOARD_SECTIONS.len();
+        let any_extended = (0..EXTENDED_SECTIONS.len()).any(|i| self.contains(offset + i));
+        let Some(observatory) = observatory.as_object_mut() else {
+            return;
+        };
+        if !any_extended {
+            observatory.remove("extended");
+        } else if let Some(extended) = observatory
+            .get_mut("extended")
+            .and_then(Value::as_object_mut)
+        {
+            extended.retain(
+                |key, _| match EXTENDED_SECTIONS.iter().position(|s| s == key) {
+                    Some(index) => self.contains(offset + index),
+                    None => true,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_sections() {
+        assert_eq!(Sections::parse(None), Ok(Sections::ALL));
+        assert_eq!(Sections::parse(Some(" , ")), Ok(Sections::ALL));
+        assert!(!Sections::parse(Some("emotional,entropy")).unwrap().is_all());
+        assert_eq!(
+            Sections::parse(Some("emotional,bogus")),
+            Err("bogus".to_string())
+        );
+    }
+
+    #[test]
+    fn test_prune_keeps_selected_sections() {
+        let frame = || {
+            json!({
+                "seq": 1,
+                "server_time": "now",
+                "dashboard": { "timestamp": "t", "identity": {}, "emotional": {}, "recent_thoughts": [] },
+                "extended": { "timestamp": "t", "entropy": {}, "system": {} }
+            })
+        };
+
+        let mut value = frame();
+        Sections::parse(Some("emotional,entropy"))
+            .unwrap()
+            .prune(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "seq": 1,
+                "server_time": "now",
+                "dashboard": { "timestamp": "t", "emotional": {} },
+                "extended": { "timestamp": "t", "entropy": {} }
+            })
+        );
+
+        let mut value = frame();
+        Sections::parse(Some("identity")).unwrap().prune(&mut value);
+        assert!(value.get("extended").is_none());
+
+        let mut value = frame();
+        Sections::ALL.prune(&mut value);
+        assert_eq!(value, frame());
+    }
+}
@@ -0,0 +1,174 @@
+//! Persisted "last good" metrics so a restarted backend isn't blank
+//!
+//! With `METRICS_SNAPSHOT_PATH` set, the current `ObservatoryMetrics` is
+//! written there every `SNAPSHOT_INTERVAL` (and once more on shutdown) and
+//! read back on startup, so clients see the last known state instead of
+//! zeros until the first poll lands. The event log is saved with it, whole.
+//! Unset = disabled.
+
+use crate::{events, AppState, ObservatoryMetrics};
+use chrono::Utc;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{info, warn};
+
+/// How often the snapshot is rewritten
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// `METRICS_SNAPSHOT_PATH`, if set and non-empty
+pub fn path_from_env() -> Option<PathBuf> {
+    std::env::var("METRICS_SNAPSHOT_PATH")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Snapshot file for one instance: `base` itself when it's the only one,
+/// otherwise `<stem>.<instance>.<ext>` next to it
+pub fn instance_path(base: &Path, instance: &str, multiple: bool) -> PathBuf {
+    if !multiple {
+        return base.to_path_buf();
+    }
+    let stem = base
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match base.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, instance, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, instance),
+    };
+    base.with_file_name(name)
+}
+
+/// Read a snapshot; `None` if missing or unreadable (e.g. written by an older schema)
+pub async fn load(path: &Path) -> Option<ObservatoryMetrics> {
+    let bytes = match tokio::fs::read(path).await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "Metrics snapshot read failed");
+            return None;
+        }
+    };
+    serde_json::from_slice(&bytes)
+        .inspect_err(|e| warn!(path = %path.display(), error = %e, "Metrics snapshot unparseable"))
+        .ok()
+}
+
+/// Write via a temp file + rename so a crash never leaves a torn snapshot
+pub async fn save(path: &Path, metrics: &ObservatoryMetrics) -> std::io::Result<()> {
+    let json = serde_json::to_vec(metrics)?;
+    // `<file>.tmp` rather than swapping the extension, which for a path
+    // already ending in `.tmp` would be the target itself
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    tokio::fs::write(&tmp, json).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// Restore the persisted metrics into `state`, if there are any
+pub async fn restore(state: &AppState, path: &Path) {
+    if let Some(snapshot) = load(path).await {
+        info!(path = %path.display(), taken_at = %snapshot.server_time, "Restored metrics snapshot");
+        *state.metrics.write().await = snapshot.dashboard;
+        *state.extended_metrics.write().await = snapshot.extended;
+        // Saved newest first
+        events::record(
+            &mut *state.events.write().await,
+            snapshot.events.into_iter().rev(),
+        );
+    }
+}
+
+/// Periodically persist the current metrics until shutdown, then write once more
+pub async fn snapshot_writer(state: Arc<AppState>, path: PathBuf) {
+    let mut interval = tokio::time::interval(SNAPSHOT_INTERVAL);
+    let mut shutdown = state.shutdown.subscribe();
+    loop {
+        let stopping = tokio::select! {
+            _ = interval.tick() => false,
+            _ = crate::shutdown_requested(&mut shutdown) => true,
+        };
+
+        let snapshot = ObservatoryMetrics {
+            server_time: Utc::now(),
+            dashboard: state.metrics.read().await.clone(),
+            extended: state.extended_metrics.read().await.clone(),
+            events: events::recent(&*state.events.read().await, events::EVENT_LOG_CAPACITY),
+        };
+        if let Err(e) = save(&path, &snapshot).await {
+            warn!(path = %path.display(), error = %e, "Metrics snapshot write failed");
+        }
+
+        if stopping {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_then_load_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("daneel-web-snapshot-{}.json", std::process::id()));
+        let mut metrics = ObservatoryMetrics {
+            server_time: Utc::now(),
+            dashboard: AppState::default_metrics("Timmy"),
+            extended: None,
+            events: Vec::new(),
+        };
+        metrics.dashboard.identity.lifetime_thoughts = 42;
+
+        save(&path, &metrics).await.unwrap();
+        let loaded = load(&path).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.dashboard.identity.lifetime_thoughts, 42);
+        assert!(loaded.extended.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_save_to_tmp_named_path() {
+        let path =
+            std::env::temp_dir().join(format!("daneel-web-snapshot-{}.tmp", std::process::id()));
+        let metrics = ObservatoryMetrics {
+            server_time: Utc::now(),
+            dashboard: AppState::default_metrics("Timmy"),
+            extended: None,
+            events: Vec::new(),
+        };
+
+        save(&path, &metrics).await.unwrap();
+        let staged = path.with_file_name(format!(
+            "{}.tmp",
+            path.file_name().unwrap().to_string_lossy()
+        ));
+        assert!(!staged.exists());
+        assert!(load(&path).await.is_some());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_instance_path() {
+        let base = Path::new("/var/lib/daneel/metrics.json");
+        assert_eq!(instance_path(base, "alpha", false), base);
+        assert_eq!(
+            instance_path(base, "alpha", true),
+            Path::new("/var/lib/daneel/metrics.alpha.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_snapshot_is_none() {
+        assert!(load(Path::new("/nonexistent/daneel-web-snapshot.json"))
+            .await
+            .is_none());
+    }
+}
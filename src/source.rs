@@ -0,0 +1,707 @@
+//! Where the dashboard's data comes from
+//!
+//! Every read of the mind's own state - the awake stream, memory counts, the
+//! identity point, actor heartbeats, manifold vectors - goes through a
+//! `MetricsSource`, so nothing above this module knows which store answered.
+//! `RedisQdrantSource` is the live one: Redis streams and keys written by the
+//! core, and Qdrant's collections. `MockSource` serves fixed fixtures to the
+//! tests, which then run the real poll and handlers with nothing behind
+//! them, and `demo::SyntheticSource` simulates a whole mind. A source
+//! replaying a captured session would be one more implementation.
+//!
+//! The methods are raw reads, gathered per poll by `read_sources`. Everything
+//! derived across polls (rates, stall and dream detection, the connection
+//! drive walk) stays in `derive_metrics`, so every source gets it for free.
+
+use crate::{
+    error::FetchError,
+    parse_thought, sort_newest_first,
+    vectors::{self, ManifoldPoint, PayloadFields, ThoughtDetail},
+    ActorMetrics, ActorStatus, ExtendedMetrics, ParsedThought, READY_PROBE_TIMEOUT,
+};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+/// Failure of one read; callers log it and degrade that part of the frame
+pub type SourceResult<T> = Result<T, FetchError>;
+
+/// Raw memory vectors, each with its manifold point before projection
+pub type MemoryVectors = Vec<(Vec<f32>, ManifoldPoint)>;
+
+/// The core's awake thought stream
+const AWAKE_STREAM: &str = "daneel:stream:awake";
+
+/// Redis flag the core sets for the duration of a dream cycle
+const DREAM_FLAG_KEY: &str = "daneel:dream:active";
+
+/// Id of the Qdrant point holding the identity record
+const IDENTITY_POINT_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+/// Actors the core reports on: (Redis key segment, display name)
+const ACTORS: [(&str, &str); 4] = [
+    ("memory", "MemoryActor"),
+    ("attention", "AttentionActor"),
+    ("salience", "SalienceActor"),
+    ("volition", "VolitionActor"),
+];
+
+/// An actor is dead if its heartbeat is older than this
+const ACTOR_HEARTBEAT_TIMEOUT_MS: i64 = 10_000;
+
+/// Fields read from the identity record
+#[derive(Debug, Clone, Default)]
+pub struct IdentityRecord {
+    pub lifetime_thoughts: u64,
+    pub restart_count: u32,
+    pub lifetime_dreams: u64,
+    pub name: Option<String>,
+}
+
+/// Which memory store to count or draw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryStore {
+    #[default]
+    Conscious,
+    /// Where memories sink once they fall out of the conscious store
+    Unconscious,
+}
+
+impl MemoryStore {
+    /// Qdrant collection holding this store
+    pub fn collection(self) -> &'static str {
+        match self {
+            Self::Conscious => vectors::MEMORIES_COLLECTION,
+            Self::Unconscious => vectors::UNCONSCIOUS_COLLECTION,
+        }
+    }
+}
+
+/// One kind of read, for naming where it went when it fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Read {
+    /// Length, pages or new entries of the awake thought stream
+    AwakeStream,
+    Identity,
+    Count(MemoryStore),
+    Actors,
+    DreamFlag,
+    Vetoes,
+    Vectors(MemoryStore),
+    Thought(MemoryStore),
+}
+
+/// Reads the dashboard makes, whatever answers them
+pub trait MetricsSource: Send + Sync {
+    /// Short name for logs ("redis+qdrant", "mock")
+    fn name(&self) -> &'static str;
+
+    /// Lifetime counters and name
+    fn identity(&self) -> BoxFuture<'_, SourceResult<IdentityRecord>>;
+
+    /// Thoughts in the awake stream this session. An unreachable store skips
+    /// the whole poll (the last good metrics stay up); any other error reads as 0
+    fn stream_len(&self) -> BoxFuture<'_, SourceResult<u64>>;
+
+    /// Up to `limit` of the newest thoughts, newest first
+    fn recent_thoughts(&self, limit: usize) -> BoxFuture<'_, SourceResult<Vec<ParsedThought>>>;
+
+    /// Up to `limit` thoughts older than the one with id `before` (exclusive),
+    /// newest first; pages back through the stream
+    fn thoughts_before<'a>(
+        &'a self,
+        before: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>>;
+
+    /// Up to `limit` thoughts newer than the one with id `last_id`, oldest first
+    fn thoughts_after<'a>(
+        &'a self,
+        last_id: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>>;
+
+    /// Memories in one store
+    fn count(&self, store: MemoryStore) -> BoxFuture<'_, SourceResult<u64>>;
+
+    /// Liveness and restart counts of the four actors
+    fn actors(&self) -> BoxFuture<'_, SourceResult<ActorMetrics>>;
+
+    /// The core's dream flag, None when it doesn't set one
+    fn dream_flag(&self) -> BoxFuture<'_, SourceResult<Option<String>>>;
+
+    /// Vetoes per law, missing counters as 0
+    fn veto_by_law(&self) -> BoxFuture<'_, SourceResult<[u64; 4]>>;
+
+    /// Vectors of one store with their (unprojected) manifold points: the
+    /// first `limit`, or with `sample` a uniform sample of the whole store,
+    /// with preview and category read from `fields`
+    fn vectors<'a>(
+        &'a self,
+        store: MemoryStore,
+        limit: u32,
+        sample: bool,
+        fields: &'a PayloadFields,
+    ) -> BoxFuture<'a, SourceResult<MemoryVectors>>;
+
+    /// One memory of `store` by manifold point id; None if it doesn't exist
+    fn thought<'a>(
+        &'a self,
+        store: MemoryStore,
+        id: &'a str,
+    ) -> BoxFuture<'a, SourceResult<Option<ThoughtDetail>>>;
+
+    /// Readiness of each backing store: (dependency, detail or reason)
+    fn probe(&self) -> BoxFuture<'_, Vec<(&'static str, Result<String, String>)>>;
+
+    /// Where `read` goes, for logs: (upstream, Redis key or Qdrant
+    /// collection). In-memory sources have no keys and name themselves.
+    fn target(&self, _read: Read) -> (&'static str, &'static str) {
+        (self.name(), "")
+    }
+
+    /// When each backing store last answered a read: (upstream, time, None
+    /// if never). The default suits sources with no upstream of their own
+    /// (demo, mock): they report themselves as answering right now, whether
+    /// or not anything has been read from them yet.
+    fn last_successes(&self) -> Vec<(&'static str, Option<DateTime<Utc>>)> {
+        vec![(self.name(), Some(Utc::now()))]
+    }
+
+    /// Extended metrics the source makes itself; None = poll the core's
+    /// `/extended_metrics` as usual
+    fn extended(&self) -> Option<ExtendedMetrics> {
+        None
+    }
+
+    /// Client for the thought-event pub/sub channel, if the source has Redis
+    fn redis_client(&self) -> Option<&redis::Client> {
+        None
+    }
+}
+
+// =============================================================================
+// Redis + Qdrant
+// =============================================================================
+
+/// The live stores: Redis streams and keys written by the core, and Qdrant
+pub struct RedisQdrantSource {
+    redis_url: String,
+    redis_client: redis::Client,
+    /// Cached multiplexed connection, cloned per read and dropped when it goes stale
+    redis_conn: RwLock<Option<MultiplexedConnection>>,
+    /// Shared Qdrant client (built once, reused by every poll)
+    qdrant: Arc<qdrant_client::Qdrant>,
+    /// Last successful read per upstream ("redis", "qdrant"), for /pipeline
+    last_success: Mutex<HashMap<&'static str, DateTime<Utc>>>,
+}
+
+impl RedisQdrantSource {
+    pub fn new(redis_url: &str, qdrant_url: &str) -> Self {
+        let redis_client = redis::Client::open(redis_url).expect("Invalid REDIS_URL");
+        let qdrant = qdrant_client::Qdrant::from_url(qdrant_url)
+            .build()
+            .expect("Failed to build Qdrant client");
+        Self {
+            redis_url: redis_url.to_string(),
+            redis_client,
+            redis_conn: RwLock::new(None),
+            qdrant: Arc::new(qdrant),
+            last_success: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Note a read of `upstream`, remembering when it last answered
+    fn record(&self, upstream: &'static str, answered: bool) {
+        if !answered {
+            return;
+        }
+        if let Ok(mut last) = self.last_success.lock() {
+            last.insert(upstream, Utc::now());
+        }
+    }
+
+    /// Cached Redis connection, reconnecting if none is held
+    async fn connection(&self) -> redis::RedisResult<MultiplexedConnection> {
+        if let Some(con) = self.redis_conn.read().await.as_ref() {
+            return Ok(con.clone());
+        }
+        let con = self.redis_client.get_multiplexed_async_connection().await?;
+        info!(upstream = "redis", url = %self.redis_url, "Redis connection established");
+        *self.redis_conn.write().await = Some(con.clone());
+        Ok(con)
+    }
+
+    /// Run one command, dropping the cached connection if the error means
+    /// it went stale so the next read reconnects
+    async fn query<T: redis::FromRedisValue>(&self, cmd: &redis::Cmd) -> redis::RedisResult<T> {
+        let mut con = self.connection().await?;
+        let result = cmd.query_async(&mut con).await;
+        self.record("redis", result.is_ok());
+        if let Err(e) = &result {
+            if e.is_connection_dropped() || e.is_io_error() {
+                warn!(upstream = "redis", url = %self.redis_url, "Dropping stale Redis connection");
+                *self.redis_conn.write().await = None;
+            }
+        }
+        result
+    }
+
+    /// Up to `limit` entries from `end` back (XREVRANGE syntax: "+" for the
+    /// newest, "(<id>" to exclude the id itself)
+    async fn newest(&self, end: &str, limit: usize) -> redis::RedisResult<Vec<ParsedThought>> {
+        let mut entries: redis::streams::StreamRangeReply = self
+            .query(
+                redis::cmd("XREVRANGE")
+                    .arg(AWAKE_STREAM)
+                    .arg(end)
+                    .arg("-")
+                    .arg("COUNT")
+                    .arg(limit),
+            )
+            .await?;
+        // XREVRANGE already returns newest first, but callers read index 0
+        // as the latest thought, so don't rely on the reply order
+        sort_newest_first(&mut entries.ids, |e| &e.id);
+        Ok(entries.ids.into_iter().map(parse_thought).collect())
+    }
+
+    async fn after(&self, last_id: &str, limit: usize) -> redis::RedisResult<Vec<ParsedThought>> {
+        // XREAD without BLOCK: a blocking read would stall the shared connection
+        let reply: Option<redis::streams::StreamReadReply> = self
+            .query(
+                redis::cmd("XREAD")
+                    .arg("COUNT")
+                    .arg(limit)
+                    .arg("STREAMS")
+                    .arg(AWAKE_STREAM)
+                    .arg(last_id),
+            )
+            .await?;
+        Ok(reply
+            .into_iter()
+            .flat_map(|r| r.keys)
+            .flat_map(|k| k.ids)
+            .map(parse_thought)
+            .collect())
+    }
+
+    /// Heartbeats from `daneel:actor:<name>:heartbeat` and restart counts
+    /// from `daneel:actor:<name>:restart_count` (one MGET)
+    async fn actor_statuses(&self) -> redis::RedisResult<ActorMetrics> {
+        let keys: Vec<String> = ACTORS
+            .iter()
+            .flat_map(|(key, _)| {
+                [
+                    format!("daneel:actor:{}:heartbeat", key),
+                    format!("daneel:actor:{}:restart_count", key),
+                ]
+            })
+            .collect();
+        let values: Vec<Option<String>> = self.query(redis::cmd("MGET").arg(&keys)).await?;
+
+        let now_ms = Utc::now().timestamp_millis();
+        let status = |i: usize| {
+            let heartbeat = values.get(i * 2).cloned().flatten();
+            let restart_count = values
+                .get(i * 2 + 1)
+                .cloned()
+                .flatten()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0);
+            ActorStatus {
+                name: ACTORS[i].1.into(),
+                alive: heartbeat
+                    .as_deref()
+                    .and_then(crate::parse_heartbeat_millis)
+                    .is_some_and(|hb| now_ms - hb <= ACTOR_HEARTBEAT_TIMEOUT_MS),
+                restart_count,
+            }
+        };
+        Ok(ActorMetrics {
+            memory_actor: status(0),
+            attention_actor: status(1),
+            salience_actor: status(2),
+            volition_actor: status(3),
+        })
+    }
+
+    async fn identity_point(&self) -> SourceResult<IdentityRecord> {
+        use qdrant_client::qdrant::GetPointsBuilder;
+
+        let result = self
+            .qdrant
+            .get_points(
+                GetPointsBuilder::new("identity", vec![IDENTITY_POINT_ID.into()])
+                    .with_payload(true),
+            )
+            .await;
+        self.record("qdrant", result.is_ok());
+        let result = result?;
+
+        let Some(point) = result.result.first() else {
+            return Ok(IdentityRecord::default());
+        };
+        let payload = &point.payload;
+        let integer = |field: &str| payload.get(field).and_then(|v| v.as_integer());
+        Ok(IdentityRecord {
+            lifetime_thoughts: integer("lifetime_thought_count").map_or(0, |v| v as u64),
+            restart_count: integer("restart_count").map_or(0, |v| v as u32),
+            lifetime_dreams: integer("lifetime_dream_count").map_or(0, |v| v as u64),
+            name: payload
+                .get("name")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string()),
+        })
+    }
+
+    async fn points_count(&self, collection: &str) -> SourceResult<u64> {
+        let info = self.qdrant.collection_info(collection).await;
+        self.record("qdrant", info.is_ok());
+        Ok(info?
+            .result
+            .map(|r| r.points_count.unwrap_or(0))
+            .unwrap_or(0))
+    }
+
+    async fn readiness(&self) -> Vec<(&'static str, Result<String, String>)> {
+        let ping = redis::cmd("PING");
+        let redis =
+            match tokio::time::timeout(READY_PROBE_TIMEOUT, self.query::<String>(&ping)).await {
+                Ok(result) => result.map_err(|e| FetchError::from(e).to_string()),
+                Err(_) => Err(FetchError::Timeout(READY_PROBE_TIMEOUT).to_string()),
+            };
+
+        let qdrant =
+            match tokio::time::timeout(READY_PROBE_TIMEOUT, self.qdrant.health_check()).await {
+                Ok(Ok(reply)) => {
+                    self.record("qdrant", true);
+                    Ok(format!("version {}", reply.version))
+                }
+                Ok(Err(e)) => Err(FetchError::from(e).to_string()),
+                Err(_) => Err(FetchError::Timeout(READY_PROBE_TIMEOUT).to_string()),
+            };
+
+        vec![("redis", redis), ("qdrant", qdrant)]
+    }
+}
+
+impl MetricsSource for RedisQdrantSource {
+    fn name(&self) -> &'static str {
+        "redis+qdrant"
+    }
+
+    fn target(&self, read: Read) -> (&'static str, &'static str) {
+        match read {
+            Read::AwakeStream => ("redis", AWAKE_STREAM),
+            Read::Actors => ("redis", "daneel:actor:*"),
+            Read::DreamFlag => ("redis", DREAM_FLAG_KEY),
+            Read::Vetoes => ("redis", "daneel:veto:law:*"),
+            Read::Identity => ("qdrant", "identity"),
+            Read::Count(store) | Read::Vectors(store) | Read::Thought(store) => {
+                ("qdrant", store.collection())
+            }
+        }
+    }
+
+    fn identity(&self) -> BoxFuture<'_, SourceResult<IdentityRecord>> {
+        Box::pin(self.identity_point())
+    }
+
+    fn stream_len(&self) -> BoxFuture<'_, SourceResult<u64>> {
+        Box::pin(async move { Ok(self.query(redis::cmd("XLEN").arg(AWAKE_STREAM)).await?) })
+    }
+
+    fn recent_thoughts(&self, limit: usize) -> BoxFuture<'_, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move { Ok(self.newest("+", limit).await?) })
+    }
+
+    fn thoughts_before<'a>(
+        &'a self,
+        before: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move { Ok(self.newest(&format!("({}", before), limit).await?) })
+    }
+
+    fn thoughts_after<'a>(
+        &'a self,
+        last_id: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move { Ok(self.after(last_id, limit).await?) })
+    }
+
+    fn count(&self, store: MemoryStore) -> BoxFuture<'_, SourceResult<u64>> {
+        Box::pin(self.points_count(store.collection()))
+    }
+
+    fn actors(&self) -> BoxFuture<'_, SourceResult<ActorMetrics>> {
+        Box::pin(async move { Ok(self.actor_statuses().await?) })
+    }
+
+    fn dream_flag(&self) -> BoxFuture<'_, SourceResult<Option<String>>> {
+        Box::pin(async move { Ok(self.query(redis::cmd("GET").arg(DREAM_FLAG_KEY)).await?) })
+    }
+
+    fn veto_by_law(&self) -> BoxFuture<'_, SourceResult<[u64; 4]>> {
+        Box::pin(async move {
+            let keys: Vec<String> = (0..4)
+                .map(|law| format!("daneel:veto:law:{}", law))
+                .collect();
+            let values: Vec<Option<String>> = self.query(redis::cmd("MGET").arg(&keys)).await?;
+            Ok(crate::parse_veto_counts(&values))
+        })
+    }
+
+    fn vectors<'a>(
+        &'a self,
+        store: MemoryStore,
+        limit: u32,
+        sample: bool,
+        fields: &'a PayloadFields,
+    ) -> BoxFuture<'a, SourceResult<MemoryVectors>> {
+        Box::pin(async move {
+            let result = vectors::scroll_memory_vectors(
+                &self.qdrant,
+                store.collection(),
+                limit,
+                sample,
+                fields,
+            )
+            .await;
+            // A missing collection is still an answer
+            self.record("qdrant", !matches!(result, Err(FetchError::Qdrant(_))));
+            result
+        })
+    }
+
+    fn thought<'a>(
+        &'a self,
+        store: MemoryStore,
+        id: &'a str,
+    ) -> BoxFuture<'a, SourceResult<Option<ThoughtDetail>>> {
+        Box::pin(async move {
+            let result = vectors::fetch_thought(&self.qdrant, store.collection(), id).await;
+            // A missing collection is still an answer
+            self.record("qdrant", !matches!(result, Err(FetchError::Qdrant(_))));
+            result
+        })
+    }
+
+    fn probe(&self) -> BoxFuture<'_, Vec<(&'static str, Result<String, String>)>> {
+        Box::pin(self.readiness())
+    }
+
+    fn last_successes(&self) -> Vec<(&'static str, Option<DateTime<Utc>>)> {
+        let last = self
+            .last_success
+            .lock()
+            .map(|l| l.clone())
+            .unwrap_or_default();
+        ["redis", "qdrant"]
+            .into_iter()
+            .map(|upstream| (upstream, last.get(upstream).copied()))
+            .collect()
+    }
+
+    fn redis_client(&self) -> Option<&redis::Client> {
+        Some(&self.redis_client)
+    }
+}
+
+// =============================================================================
+// Fixtures
+// =============================================================================
+
+/// Fixed data for tests; every read returns it unchanged
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct MockSource {
+    pub identity: IdentityRecord,
+    /// Newest first
+    pub thoughts: Vec<ParsedThought>,
+    pub conscious: u64,
+    pub unconscious: u64,
+    /// None reads as all actors down
+    pub actors: Option<ActorMetrics>,
+    pub dream_flag: Option<String>,
+    pub veto_by_law: [u64; 4],
+    pub vectors: MemoryVectors,
+    pub unconscious_vectors: MemoryVectors,
+}
+
+#[cfg(test)]
+impl MockSource {
+    fn vectors_of(&self, store: MemoryStore) -> &MemoryVectors {
+        match store {
+            MemoryStore::Conscious => &self.vectors,
+            MemoryStore::Unconscious => &self.unconscious_vectors,
+        }
+    }
+}
+
+#[cfg(test)]
+impl MetricsSource for MockSource {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn identity(&self) -> BoxFuture<'_, SourceResult<IdentityRecord>> {
+        Box::pin(async move { Ok(self.identity.clone()) })
+    }
+
+    fn stream_len(&self) -> BoxFuture<'_, SourceResult<u64>> {
+        Box::pin(async move { Ok(self.thoughts.len() as u64) })
+    }
+
+    fn recent_thoughts(&self, limit: usize) -> BoxFuture<'_, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move { Ok(self.thoughts.iter().take(limit).cloned().collect()) })
+    }
+
+    fn thoughts_before<'a>(
+        &'a self,
+        before: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move {
+            let before = crate::parse_stream_id(before);
+            Ok(self
+                .thoughts
+                .iter()
+                .filter(|t| crate::parse_stream_id(&t.summary.id) < before)
+                .take(limit)
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn thoughts_after<'a>(
+        &'a self,
+        last_id: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, SourceResult<Vec<ParsedThought>>> {
+        Box::pin(async move {
+            let newer = self
+                .thoughts
+                .iter()
+                .take_while(|t| t.summary.id != last_id)
+                .cloned()
+                .collect::<Vec<_>>();
+            Ok(newer.into_iter().rev().take(limit).collect())
+        })
+    }
+
+    fn count(&self, store: MemoryStore) -> BoxFuture<'_, SourceResult<u64>> {
+        Box::pin(async move {
+            Ok(match store {
+                MemoryStore::Conscious => self.conscious,
+                MemoryStore::Unconscious => self.unconscious,
+            })
+        })
+    }
+
+    fn actors(&self) -> BoxFuture<'_, SourceResult<ActorMetrics>> {
+        Box::pin(async move { Ok(self.actors.clone().unwrap_or_else(ActorMetrics::down)) })
+    }
+
+    fn dream_flag(&self) -> BoxFuture<'_, SourceResult<Option<String>>> {
+        Box::pin(async move { Ok(self.dream_flag.clone()) })
+    }
+
+    fn veto_by_law(&self) -> BoxFuture<'_, SourceResult<[u64; 4]>> {
+        Box::pin(async move { Ok(self.veto_by_law) })
+    }
+
+    fn vectors<'a>(
+        &'a self,
+        store: MemoryStore,
+        limit: u32,
+        _sample: bool,
+        _fields: &'a PayloadFields,
+    ) -> BoxFuture<'a, SourceResult<MemoryVectors>> {
+        Box::pin(async move {
+            Ok(self
+                .vectors_of(store)
+                .iter()
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn thought<'a>(
+        &'a self,
+        store: MemoryStore,
+        id: &'a str,
+    ) -> BoxFuture<'a, SourceResult<Option<ThoughtDetail>>> {
+        Box::pin(async move {
+            Ok(self
+                .vectors_of(store)
+                .iter()
+                .find(|(_, point)| point.id == id)
+                .map(|(_, point)| ThoughtDetail {
+                    id: point.id.clone(),
+                    content: point.preview.clone().unwrap_or_default(),
+                    salience: point.salience,
+                    encoded_at: None,
+                }))
+        })
+    }
+
+    fn probe(&self) -> BoxFuture<'_, Vec<(&'static str, Result<String, String>)>> {
+        Box::pin(async move { vec![("mock", Ok("fixtures".to_string()))] })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_thoughts_after() {
+        let thought = |id: &str| {
+            parse_thought(redis::streams::StreamId {
+                id: id.to_string(),
+                ..Default::default()
+            })
+        };
+        let source = MockSource {
+            thoughts: vec![thought("3-0"), thought("2-0"), thought("1-0")],
+            ..Default::default()
+        };
+        let ids = |thoughts: Vec<ParsedThought>| -> Vec<String> {
+            thoughts.into_iter().map(|t| t.summary.id).collect()
+        };
+
+        assert_eq!(source.stream_len().await.unwrap(), 3);
+        assert_eq!(
+            ids(source.recent_thoughts(2).await.unwrap()),
+            ["3-0", "2-0"]
+        );
+        // Oldest first, like XREAD
+        assert_eq!(
+            ids(source.thoughts_after("1-0", 10).await.unwrap()),
+            ["2-0", "3-0"]
+        );
+        assert_eq!(ids(source.thoughts_after("1-0", 1).await.unwrap()), ["2-0"]);
+        assert!(source.thoughts_after("3-0", 10).await.unwrap().is_empty());
+        // Newest first, excluding the cursor
+        assert_eq!(
+            ids(source.thoughts_before("3-0", 10).await.unwrap()),
+            ["2-0", "1-0"]
+        );
+        assert!(source.thoughts_before("1-0", 10).await.unwrap().is_empty());
+        assert!(!source.actors().await.unwrap().memory_actor.alive);
+    }
+}
@@ -0,0 +1,204 @@
+//! Observatory summary - a single 0-1 "cognitive health" score for alerting
+//!
+//! Pure derivation over `DashboardMetrics`/`ExtendedMetrics`. Each term scores
+//! 0-1 and the composite is their weighted sum:
+//!
+//! | Term         | Weight | Healthy when                                   |
+//! |--------------|--------|------------------------------------------------|
+//! | `core`       | 0.20   | core answered `/extended_metrics` recently     |
+//! | `actors`     | 0.25   | every actor alive (fraction alive otherwise)   |
+//! | `entropy`    | 0.25   | normalized entropy inside 0.3-0.9              |
+//! | `fractality` | 0.15   | fractality score at or above 0.2               |
+//! | `veto_rate`  | 0.15   | vetoes at or below 1% of session thoughts      |
+//!
+//! Terms that depend on extended metrics score 0 while the core is unreachable.
+
+use crate::{DashboardMetrics, ExtendedMetrics};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+const WEIGHT_CORE: f32 = 0.20;
+const WEIGHT_ACTORS: f32 = 0.25;
+const WEIGHT_ENTROPY: f32 = 0.25;
+const WEIGHT_FRACTALITY: f32 = 0.15;
+const WEIGHT_VETO: f32 = 0.15;
+
+/// Healthy band for normalized entropy (below = clockwork, above = noise)
+const ENTROPY_BAND: (f32, f32) = (0.3, 0.9);
+/// Distance outside the band at which the entropy term reaches 0
+const ENTROPY_FALLOFF: f32 = 0.3;
+/// Fractality below this floor scores proportionally less
+const FRACTALITY_FLOOR: f32 = 0.2;
+/// Veto rate at or below this scores 1, at or above `VETO_RATE_MAX` scores 0
+const VETO_RATE_OK: f32 = 0.01;
+const VETO_RATE_MAX: f32 = 0.10;
+
+/// GET /observatory/summary response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ObservatorySummary {
+    pub score: f32,
+    pub status: String,
+    pub terms: Vec<HealthTerm>,
+}
+
+/// One weighted contribution to the composite score
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthTerm {
+    pub name: String,
+    pub weight: f32,
+    pub score: f32,
+    pub detail: String,
+}
+
+impl HealthTerm {
+    fn new(name: &str, weight: f32, score: f32, detail: String) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+            score: score.clamp(0.0, 1.0),
+            detail,
+        }
+    }
+}
+
+/// Derive the composite health score
+pub fn summarize(
+    dashboard: &DashboardMetrics,
+    extended: Option<&ExtendedMetrics>,
+    core_reachable: bool,
+) -> ObservatorySummary {
+    let mut terms = vec![
+        HealthTerm::new(
+            "core",
+            WEIGHT_CORE,
+            if core_reachable { 1.0 } else { 0.0 },
+            if core_reachable {
+                "reachable".into()
+            } else {
+                "unreachable".into()
+            },
+        ),
+        actors_term(dashboard),
+    ];
+
+    match extended.filter(|_| core_reachable) {
+        Some(ext) => {
+            terms.push(entropy_term(ext.entropy.normalized));
+            terms.push(fractality_term(ext.fractality.score));
+            terms.push(veto_term(
+                ext.system.veto_count,
+                ext.system.session_thoughts,
+            ));
+        }
+        None => {
+            for (name, weight) in [
+                ("entropy", WEIGHT_ENTROPY),
+                ("fractality", WEIGHT_FRACTALITY),
+                ("veto_rate", WEIGHT_VETO),
+            ] {
+                terms.push(HealthTerm::new(name, weight, 0.0, "no data".into()));
+            }
+        }
+    }
+
+    let score = terms.iter().map(|t| t.weight * t.score).sum::<f32>();
+    let status = if score >= 0.8 {
+        "healthy"
+    } else if score >= 0.5 {
+        "degraded"
+    } else {
+        "critical"
+    };
+
+    ObservatorySummary {
+        score,
+        status: status.into(),
+        terms,
+    }
+}
+
+fn actors_term(dashboard: &DashboardMetrics) -> HealthTerm {
+    let actors = &dashboard.actors;
+    let all = [
+        &actors.memory_actor,
+        &actors.attention_actor,
+        &actors.salience_actor,
+        &actors.volition_actor,
+    ];
+    let alive = all.iter().filter(|a| a.alive).count();
+    HealthTerm::new(
+        "actors",
+        WEIGHT_ACTORS,
+        alive as f32 / all.len() as f32,
+        format!("{}/{} alive", alive, all.len()),
+    )
+}
+
+fn entropy_term(normalized: f32) -> HealthTerm {
+    let (low, high) = ENTROPY_BAND;
+    let distance = if normalized < low {
+        low - normalized
+    } else if normalized > high {
+        normalized - high
+    } else {
+        0.0
+    };
+    HealthTerm::new(
+        "entropy",
+        WEIGHT_ENTROPY,
+        1.0 - distance / ENTROPY_FALLOFF,
+        format!("normalized {:.2} (band {:.1}-{:.1})", normalized, low, high),
+    )
+}
+
+fn fractality_term(score: f32) -> HealthTerm {
+    HealthTerm::new(
+        "fractality",
+        WEIGHT_FRACTALITY,
+        score / FRACTALITY_FLOOR,
+        format!("{:.2} (floor {:.2})", score, FRACTALITY_FLOOR),
+    )
+}
+
+fn veto_term(veto_count: u64, session_thoughts: u64) -> HealthTerm {
+    let rate = veto_count as f32 / session_thoughts.max(1) as f32;
+    HealthTerm::new(
+        "veto_rate",
+        WEIGHT_VETO,
+        1.0 - (rate - VETO_RATE_OK) / (VETO_RATE_MAX - VETO_RATE_OK),
+        format!("{:.2}% of session thoughts", rate * 100.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppState;
+
+    #[test]
+    fn test_weights_sum_to_one() {
+        let total = WEIGHT_CORE + WEIGHT_ACTORS + WEIGHT_ENTROPY + WEIGHT_FRACTALITY + WEIGHT_VETO;
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unreachable_core_is_critical() {
+        let summary = summarize(&AppState::default_metrics("Timmy"), None, false);
+        assert_eq!(summary.score, 0.0);
+        assert_eq!(summary.status, "critical");
+    }
+
+    #[test]
+    fn test_entropy_band() {
+        assert_eq!(entropy_term(0.5).score, 1.0);
+        assert_eq!(entropy_term(0.0).score, 0.0);
+        assert!((entropy_term(0.15).score - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_veto_rate() {
+        assert_eq!(veto_term(0, 0).score, 1.0);
+        assert_eq!(veto_term(1, 1000).score, 1.0);
+        assert_eq!(veto_term(50, 100).score, 0.0);
+    }
+}
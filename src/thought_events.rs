@@ -0,0 +1,152 @@
+//! Event-driven dashboard refresh from a Redis pub/sub channel
+//!
+//! Polling `daneel:stream:awake` every `DASHBOARD_POLL_INTERVAL` both lags
+//! new thoughts by up to a poll and keeps Redis busy while the mind is idle.
+//! When `THOUGHT_EVENTS_CHANNEL` names a channel the core publishes to on
+//! each thought, `subscriber` wakes `metrics_updater` as every message
+//! arrives, and between events the source is only re-read every
+//! `SUBSCRIBED_READ_INTERVAL` for what isn't event-driven (actor heartbeats,
+//! memory counts). The connection drive walk and the history sampling keep
+//! their own `DASHBOARD_POLL_INTERVAL` tick either way. If subscribing fails
+//! or the subscription drops, reads are back at full speed until it's
+//! re-established.
+
+use crate::{shutdown_requested, AppState};
+use futures::StreamExt;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+/// Source read period between thought events while subscribed
+pub const SUBSCRIBED_READ_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Wait before trying to subscribe again after a failure or a dropped subscription
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Per-instance subscription state shared with `metrics_updater`
+pub struct ThoughtEvents {
+    /// THOUGHT_EVENTS_CHANNEL; None = polling only
+    channel: Option<String>,
+    /// Subscribed right now
+    live: AtomicBool,
+    /// Signalled once per message; wake-ups during a fetch coalesce into one
+    arrived: Notify,
+}
+
+impl ThoughtEvents {
+    pub fn new(channel: Option<String>) -> Self {
+        Self {
+            channel: channel.filter(|c| !c.trim().is_empty()),
+            live: AtomicBool::new(false),
+            arrived: Notify::new(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("THOUGHT_EVENTS_CHANNEL").ok())
+    }
+
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.live.load(Ordering::Relaxed)
+    }
+
+    /// How often `metrics_updater` re-reads the source when no event arrives
+    pub fn read_interval(&self, polling: Duration) -> Duration {
+        if self.is_live() {
+            SUBSCRIBED_READ_INTERVAL
+        } else {
+            polling
+        }
+    }
+
+    /// Whether a tick `since_last_read` after the last read should read again;
+    /// every tick does unless subscribed
+    pub fn reads_due(&self, since_last_read: Duration) -> bool {
+        !self.is_live() || since_last_read >= SUBSCRIBED_READ_INTERVAL
+    }
+
+    /// Resolves when a thought event has arrived since the last call
+    pub async fn arrived(&self) {
+        self.arrived.notified().await
+    }
+}
+
+/// Keep `state`'s instance subscribed to its thought channel until shutdown
+/// (returns at once when no channel is configured)
+pub async fn subscriber(state: Arc<AppState>) {
+    let Some(channel) = state.thought_events.channel().map(String::from) else {
+        return;
+    };
+    let Some(client) = state.source.redis_client() else {
+        warn!(instance = %state.instance, source = state.source.name(), channel = %channel, "Source has no pub/sub, polling only");
+        return;
+    };
+    let events = &state.thought_events;
+    let mut shutdown = state.shutdown.subscribe();
+    loop {
+        match subscribe(client, &channel).await {
+            Ok(mut pubsub) => {
+                info!(instance = %state.instance, upstream = "redis", channel = %channel, "Subscribed to thought events");
+                events.live.store(true, Ordering::Relaxed);
+                let mut messages = pubsub.on_message();
+                loop {
+                    tokio::select! {
+                        message = messages.next() => match message {
+                            Some(_) => events.arrived.notify_one(),
+                            None => break,
+                        },
+                        _ = shutdown_requested(&mut shutdown) => return,
+                    }
+                }
+                events.live.store(false, Ordering::Relaxed);
+                warn!(instance = %state.instance, upstream = "redis", channel = %channel, "Thought event subscription dropped, polling at full rate");
+            }
+            Err(e) => {
+                warn!(instance = %state.instance, upstream = "redis", channel = %channel, error = %e, "Thought event subscription failed, polling at full rate");
+            }
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(RESUBSCRIBE_DELAY) => {}
+            _ = shutdown_requested(&mut shutdown) => return,
+        }
+    }
+}
+
+async fn subscribe(
+    client: &redis::Client,
+    channel: &str,
+) -> redis::RedisResult<redis::aio::PubSub> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(channel).await?;
+    Ok(pubsub)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_slow_only_while_subscribed() {
+        let polling = Duration::from_millis(150);
+        let events = ThoughtEvents::new(Some("daneel:thoughts".to_string()));
+        assert_eq!(events.read_interval(polling), polling);
+        assert!(events.reads_due(Duration::ZERO));
+        events.live.store(true, Ordering::Relaxed);
+        assert_eq!(events.read_interval(polling), SUBSCRIBED_READ_INTERVAL);
+        assert!(!events.reads_due(polling));
+        assert!(events.reads_due(SUBSCRIBED_READ_INTERVAL));
+
+        assert_eq!(ThoughtEvents::new(Some(" ".to_string())).channel(), None);
+        assert_eq!(ThoughtEvents::new(None).channel(), None);
+    }
+}
@@ -1,16 +1,40 @@
-//! Vector manifold projection - 384-dim thought vectors to 3D visualization
+//! Vector manifold projection - high-dimensional thought vectors to 3D visualization
 //!
 //! Projects Timmy's high-dimensional thought vectors into 3D space for visualization.
-//! Uses random projection for MVP (fast, simple), can upgrade to PCA later.
+//! Starts with a fixed random projection and switches to PCA once
+//! `PCA_MIN_VECTORS` memories exist to fit it on. The projection is sized to
+//! whatever dimension the collection's vectors turn out to have, and vectors
+//! that don't match it (or hold NaN/inf) are skipped rather than drawn at the
+//! origin. Optionally the manifold also carries a kinship graph: edges between
+//! thoughts that are close in the original space, which the projection can't
+//! be trusted to keep close.
+//!
+//! Once fitted, PCA is refit every `MANIFOLD_REFIT_SECS` (or on
+//! `POST /vectors/retrain`) as the memories drift. A refit keeps each axis
+//! pointing the way it did and eases from the old matrix to the new one over
+//! `MANIFOLD_REFIT_BLEND_MS`, so the cloud turns into its new shape instead
+//! of snapping.
+//!
+//! Points served to manifold clients are also smoothed per layer and id
+//! across fetches (`PositionSmoother`): each thought eases from where it was
+//! last served toward its newly projected position, so a refit or a
+//! different sample moves the cloud coherently.
 
-use ndarray::{Array1, Array2};
+use crate::error::FetchError;
+use crate::source::{MemoryStore, MemoryVectors, MetricsSource};
+use chrono::{DateTime, TimeDelta, Utc};
+use ndarray::{Array1, Array2, ArrayView1, Axis};
 use qdrant_client::qdrant::ScrollPointsBuilder;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
 use tokio::sync::RwLock;
+use tracing::{info, warn};
+use utoipa::ToSchema;
 
 /// A single point in 3D space representing a thought vector
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ManifoldPoint {
     pub x: f32,
     pub y: f32,
@@ -18,10 +42,18 @@ pub struct ManifoldPoint {
     pub salience: f32,
     pub age_ms: u64,
     pub id: String,
+    /// k-means cluster index (0 = largest cluster)
+    pub cluster: u8,
+    /// Start of the thought text, from the first configured payload field present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
+    /// Memory category (episodic, semantic, ...) from the configured payload field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
 }
 
 /// Law Crystal anchor point in 3D space
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LawCrystal {
     pub name: String,
     pub law: u8, // 0-3
@@ -30,52 +62,346 @@ pub struct LawCrystal {
     pub z: f32,
 }
 
+/// Centre of a k-means cluster in the projected space
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ClusterCentroid {
+    pub cluster: u8,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Number of points assigned to this cluster
+    pub size: usize,
+}
+
+/// Link between two thoughts that are near each other in the embedding space
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ManifoldEdge {
+    /// Point ids
+    pub source: String,
+    pub target: String,
+    /// Cosine similarity of the raw (unprojected) vectors
+    pub similarity: f32,
+}
+
 /// Response from /vectors endpoint
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ManifoldResponse {
     pub points: Vec<ManifoldPoint>,
     pub crystals: Vec<LawCrystal>,
+    pub centroids: Vec<ClusterCentroid>,
+    /// Kinship graph (see `kinship_edges`); empty when not computed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<ManifoldEdge>,
+    /// "random", "pca", or "empty" when there is nothing to project yet
     pub projection_type: String,
+    /// Share of the total variance along x, y and z (PCA only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explained_variance: Option<[f32; 3]>,
+    /// Length of the vectors projected (the embedding size)
+    #[serde(default)]
+    pub dimension: usize,
+    /// Why the manifold is empty, when it's a known condition rather than a failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// When the PCA projection was last fitted (PCA only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_refit: Option<DateTime<Utc>>,
+    /// Which store the points come from
+    #[serde(default)]
+    pub layer: MemoryStore,
+}
+
+/// Qdrant collection holding thought vectors
+pub const MEMORIES_COLLECTION: &str = "memories";
+
+/// Qdrant collection holding unconscious memories (same embedding space)
+pub const UNCONSCIOUS_COLLECTION: &str = "unconscious";
+
+/// GET /thought/{id} response: one memory behind a manifold point
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ThoughtDetail {
+    pub id: String,
+    pub content: String,
+    pub salience: f32,
+    /// RFC 3339 encoding time, if recorded
+    pub encoded_at: Option<String>,
+}
+
+/// Incremental update pushed by /vectors/stream between snapshots
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ManifoldDelta {
+    /// Points not present in the previous update
+    pub added: Vec<ManifoldPoint>,
+    /// Ids of points that have dropped out of the window
+    pub removed: Vec<String>,
+}
+
+/// Default k for manifold clustering (MANIFOLD_CLUSTERS)
+pub const DEFAULT_MANIFOLD_CLUSTERS: usize = 6;
+
+/// Upper bound on k, matching the frontend's categorical palette
+pub const MAX_MANIFOLD_CLUSTERS: usize = 10;
+
+/// Default points per manifold (MANIFOLD_POINT_LIMIT)
+pub const DEFAULT_MANIFOLD_POINT_LIMIT: u32 = 500;
+
+/// Upper bound on MANIFOLD_POINT_LIMIT; beyond this the projection and
+/// k-means cost more than the canvas can usefully show
+pub const MAX_MANIFOLD_POINT_LIMIT: u32 = 5000;
+
+/// Ids fetched per scroll page when sampling the whole collection
+const SAMPLE_PAGE_SIZE: u32 = 1000;
+
+/// Most scroll pages read for one sample; bigger collections are sampled
+/// from their first `SAMPLE_MAX_PAGES * SAMPLE_PAGE_SIZE` points
+pub const SAMPLE_MAX_PAGES: usize = 100;
+
+/// Seed for manifold sampling, fixed so repeated polls of an unchanged
+/// collection draw the same sample instead of reshuffling the view
+const SAMPLE_SEED: u64 = 7;
+
+/// Payload fields tried, in order, for a point's preview text (MANIFOLD_PREVIEW_FIELDS)
+pub const DEFAULT_PREVIEW_FIELDS: &[&str] = &["content", "text"];
+
+/// String payload field holding a memory's category (MANIFOLD_CATEGORY_FIELD)
+pub const DEFAULT_CATEGORY_FIELD: &str = "memory_type";
+
+/// Payload fields read into each manifold point
+#[derive(Debug, Clone)]
+pub struct PayloadFields {
+    /// Tried, in order, for the preview text
+    pub preview: Vec<String>,
+    /// Categorical field; None = points carry no category
+    pub category: Option<String>,
+}
+
+impl PayloadFields {
+    /// From MANIFOLD_PREVIEW_FIELDS and MANIFOLD_CATEGORY_FIELD
+    pub fn from_env() -> Self {
+        Self {
+            preview: parse_preview_fields(std::env::var("MANIFOLD_PREVIEW_FIELDS").ok().as_deref()),
+            category: parse_category_field(
+                std::env::var("MANIFOLD_CATEGORY_FIELD").ok().as_deref(),
+            ),
+        }
+    }
+}
+
+impl Default for PayloadFields {
+    fn default() -> Self {
+        Self {
+            preview: parse_preview_fields(None),
+            category: parse_category_field(None),
+        }
+    }
+}
+
+/// Previews longer than this many characters are cut and end in an ellipsis
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// Lloyd iterations before giving up on convergence
+const KMEANS_MAX_ITERATIONS: usize = 20;
+
+/// Seed for the random projection, fixed so the manifold layout is stable across restarts
+const PROJECTION_SEED: u64 = 42;
+
+/// Vectors needed before the random projection is replaced by a PCA fit;
+/// fewer than this give principal axes that mostly reflect noise
+pub const PCA_MIN_VECTORS: usize = 100;
+
+/// Power-iteration steps per principal component
+const PCA_ITERATIONS: usize = 100;
+
+/// Most kinship edges touching any one thought
+pub const KINSHIP_MAX_EDGES: usize = 3;
+
+/// Cosine similarity below which two thoughts aren't linked
+pub const KINSHIP_MIN_SIMILARITY: f32 = 0.8;
+
+/// Manifolds with more points than this get no kinship graph; the
+/// all-pairs similarity costs n² · dim
+pub const KINSHIP_MAX_POINTS: usize = 2000;
+
+/// Default PCA refit period (MANIFOLD_REFIT_SECS)
+pub const DEFAULT_REFIT_SECS: u64 = 600;
+
+/// Default refit blend (MANIFOLD_REFIT_BLEND_MS)
+pub const DEFAULT_REFIT_BLEND_MS: u64 = 2000;
+
+/// How often PCA is refit and how smoothly the new fit takes over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefitPolicy {
+    /// Refit a fitted projection this often; 0 = fit once
+    pub every_secs: u64,
+    /// Ease from the old fit to the new one over this long; 0 = snap
+    pub blend_ms: u64,
+}
+
+impl Default for RefitPolicy {
+    fn default() -> Self {
+        Self {
+            every_secs: DEFAULT_REFIT_SECS,
+            blend_ms: DEFAULT_REFIT_BLEND_MS,
+        }
+    }
+}
+
+impl RefitPolicy {
+    /// From MANIFOLD_REFIT_SECS and MANIFOLD_REFIT_BLEND_MS
+    pub fn from_env() -> Self {
+        let read = |key: &str, default: u64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            every_secs: read("MANIFOLD_REFIT_SECS", DEFAULT_REFIT_SECS),
+            blend_ms: read("MANIFOLD_REFIT_BLEND_MS", DEFAULT_REFIT_BLEND_MS),
+        }
+    }
+}
+
+/// Default time constant of the position smoothing (MANIFOLD_SMOOTHING_MS)
+pub const DEFAULT_SMOOTHING_MS: u64 = 3000;
+
+/// Smoothed positions not served for this long are forgotten, so a thought
+/// that comes back starts at its projected position
+const SMOOTHING_FORGET_SECS: i64 = 60;
+
+/// A point this close to its projected position snaps onto it, so an idle
+/// manifold settles (and its ETag with it) instead of creeping forever
+const SMOOTHING_SNAP_DISTANCE: f32 = 1e-3;
+
+/// Per-id positions as last served
+#[derive(Debug, Clone)]
+pub struct PositionSmoother {
+    /// Exponential time constant; 0 = off
+    time_constant_ms: u64,
+    /// Keyed by layer too: the collections can share ids, and a memory
+    /// consolidated into the unconscious store keeps its id
+    positions: HashMap<(MemoryStore, String), ([f32; 3], DateTime<Utc>)>,
+}
+
+impl PositionSmoother {
+    pub fn new(time_constant_ms: u64) -> Self {
+        Self {
+            time_constant_ms,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// From MANIFOLD_SMOOTHING_MS
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("MANIFOLD_SMOOTHING_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_SMOOTHING_MS),
+        )
+    }
+
+    pub fn time_constant_ms(&self) -> u64 {
+        self.time_constant_ms
+    }
+
+    /// Move each of `layer`'s points from where it was last served toward its
+    /// projected position, by `1 - e^(-elapsed / time constant)` of the way.
+    /// New ids stay where they were projected.
+    pub fn smooth(&mut self, layer: MemoryStore, points: &mut [ManifoldPoint], now: DateTime<Utc>) {
+        if self.time_constant_ms == 0 {
+            return;
+        }
+        let forget = TimeDelta::seconds(SMOOTHING_FORGET_SECS);
+        self.positions
+            .retain(|_, (_, served)| now - *served < forget);
+        for point in points {
+            let target = [point.x, point.y, point.z];
+            let key = (layer, point.id.clone());
+            let position = match self.positions.get(&key) {
+                Some(&(last, served)) => {
+                    let elapsed = (now - served).num_milliseconds().max(0) as f32;
+                    let step = 1.0 - (-elapsed / self.time_constant_ms as f32).exp();
+                    let eased: [f32; 3] =
+                        std::array::from_fn(|i| last[i] + step * (target[i] - last[i]));
+                    let gap = eased
+                        .iter()
+                        .zip(target)
+                        .map(|(a, b)| (a - b).powi(2))
+                        .sum::<f32>()
+                        .sqrt();
+                    if gap < SMOOTHING_SNAP_DISTANCE {
+                        target
+                    } else {
+                        eased
+                    }
+                }
+                None => target,
+            };
+            [point.x, point.y, point.z] = position;
+            self.positions.insert(key, (position, now));
+        }
+    }
+
+    /// Forget every position (the vectors behind them changed meaning)
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+}
+
+impl Default for PositionSmoother {
+    fn default() -> Self {
+        Self::new(DEFAULT_SMOOTHING_MS)
+    }
+}
+
+/// The fit a refit is easing away from
+#[derive(Debug, Clone)]
+struct Blend {
+    matrix: Array2<f32>,
+    mean: Array1<f32>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
 }
 
 /// Projection matrix cache (random or PCA-derived)
+#[derive(Debug, Clone)]
 pub struct ProjectionState {
-    /// 768 x 3 projection matrix (Timmy uses 768-dim BERT embeddings)
+    /// dim x 3 projection matrix, dim being the embedding size
     pub matrix: Array2<f32>,
+    /// Subtracted before projecting: the fitted mean, zero for random
+    pub mean: Array1<f32>,
     /// Whether matrix is trained (for PCA) or random
     pub is_trained: bool,
+    /// Share of the total variance each PCA axis explains
+    pub explained_variance: Option<[f32; 3]>,
+    /// When the current PCA fit was made (None while random)
+    pub fitted_at: Option<DateTime<Utc>>,
+    /// Refit on the next fetch whatever the period (POST /vectors/retrain)
+    pub retrain_requested: bool,
+    refit: RefitPolicy,
+    /// Set for `refit.blend_ms` after a refit
+    blend: Option<Blend>,
+    /// Where each point was last served, whichever fit projected it
+    pub positions: PositionSmoother,
 }
 
 impl ProjectionState {
-    /// Create random projection matrix (fast MVP approach)
-    pub fn random() -> Self {
-        use std::f32::consts::PI;
-
-        // Random projection using Gaussian entries (normalized)
-        let mut matrix = Array2::<f32>::zeros((768, 3));
-
-        // Use deterministic seed for reproducibility
-        let mut seed: u64 = 42;
-        for i in 0..768 {
-            for j in 0..3 {
-                // Simple LCG for reproducible random
-                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
-                let u1 = (seed as f32) / (u64::MAX as f32);
-                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
-                let u2 = (seed as f32) / (u64::MAX as f32);
-
-                // Box-Muller transform for Gaussian
-                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
-                matrix[[i, j]] = z;
-            }
+    /// Create random projection matrix for `dim`-dimensional vectors (fast MVP approach)
+    pub fn random(dim: usize) -> Self {
+        // Gaussian entries from a deterministically seeded RNG (reproducible layout)
+        let mut rng = StdRng::seed_from_u64(PROJECTION_SEED);
+        let mut matrix = Array2::<f32>::zeros((dim, 3));
+        for value in matrix.iter_mut() {
+            *value = rng.sample(StandardNormal);
         }
 
         // Normalize columns for better spread
         for j in 0..3 {
-            let col_sum: f32 = (0..768).map(|i| matrix[[i, j]].powi(2)).sum();
+            let col_sum: f32 = (0..dim).map(|i| matrix[[i, j]].powi(2)).sum();
             let norm = col_sum.sqrt();
             if norm > 0.0 {
-                for i in 0..768 {
+                for i in 0..dim {
                     matrix[[i, j]] /= norm;
                 }
             }
@@ -83,48 +409,457 @@ impl ProjectionState {
 
         Self {
             matrix,
+            mean: Array1::zeros(dim),
             is_trained: false,
+            explained_variance: None,
+            fitted_at: None,
+            retrain_requested: false,
+            refit: RefitPolicy::default(),
+            blend: None,
+            positions: PositionSmoother::default(),
+        }
+    }
+
+    pub fn with_refit(mut self, refit: RefitPolicy) -> Self {
+        self.refit = refit;
+        self
+    }
+
+    pub fn with_smoothing(mut self, positions: PositionSmoother) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    pub fn refit_policy(&self) -> RefitPolicy {
+        self.refit
+    }
+
+    /// Start over with a random projection for `dim`, keeping the refit and
+    /// smoothing settings (but no positions)
+    pub fn reset(&mut self, dim: usize) {
+        let mut positions = std::mem::take(&mut self.positions);
+        positions.clear();
+        *self = Self::random(dim)
+            .with_refit(self.refit)
+            .with_smoothing(positions);
+    }
+
+    /// Whether a fetch with enough vectors should fit PCA: never fitted yet,
+    /// asked to, or the refit period has passed
+    pub fn wants_fit(&self, now: DateTime<Utc>) -> bool {
+        let due = |at: DateTime<Utc>| {
+            self.refit.every_secs > 0
+                && now - at >= TimeDelta::seconds(self.refit.every_secs as i64)
+        };
+        !self.is_trained || self.retrain_requested || self.fitted_at.is_some_and(due)
+    }
+
+    /// Take over a fresh PCA fit made at `now`. Replacing an earlier fit,
+    /// each new axis is flipped to agree with the axis in use (PCA signs are
+    /// arbitrary) and the old matrix is eased out over `refit.blend_ms`.
+    pub fn adopt(&mut self, fitted: Self, now: DateTime<Utc>) {
+        let Self {
+            mut matrix,
+            mean,
+            explained_variance,
+            ..
+        } = fitted;
+        if self.is_trained {
+            let current = self.at(now).into_owned();
+            for (mut new, old) in matrix
+                .columns_mut()
+                .into_iter()
+                .zip(current.matrix.columns())
+            {
+                if new.dot(&old) < 0.0 {
+                    new.mapv_inplace(|v| -v);
+                }
+            }
+            self.blend = (self.refit.blend_ms > 0).then(|| Blend {
+                matrix: current.matrix,
+                mean: current.mean,
+                start: now,
+                end: now + TimeDelta::milliseconds(self.refit.blend_ms as i64),
+            });
         }
+        self.matrix = matrix;
+        self.mean = mean;
+        self.is_trained = true;
+        self.explained_variance = explained_variance;
+        self.fitted_at = Some(now);
+        self.retrain_requested = false;
     }
 
-    /// Project a 768-dim vector to 3D
-    pub fn project(&self, vec: &[f32]) -> (f32, f32, f32) {
-        if vec.len() != 768 {
-            return (0.0, 0.0, 0.0);
+    /// The projection as applied at `now`: while a refit is blending, the
+    /// smoothstep between the old fit and this one
+    pub fn at(&self, now: DateTime<Utc>) -> Cow<'_, Self> {
+        let Some(blend) = self.blend.as_ref().filter(|b| now < b.end) else {
+            return Cow::Borrowed(self);
+        };
+        let span = (blend.end - blend.start).num_milliseconds().max(1) as f32;
+        let t = ((now - blend.start).num_milliseconds() as f32 / span).clamp(0.0, 1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        Cow::Owned(Self {
+            matrix: &blend.matrix * (1.0 - eased) + &self.matrix * eased,
+            mean: &blend.mean * (1.0 - eased) + &self.mean * eased,
+            blend: None,
+            ..self.clone()
+        })
+    }
+
+    /// Input dimension this projection expects
+    pub fn dim(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    /// Fit the top three principal components of the `dim`-dimensional
+    /// `vectors` (others are ignored); None with fewer than `PCA_MIN_VECTORS`
+    /// of them or when they don't vary at all
+    pub fn pca(vectors: &[Vec<f32>], dim: usize) -> Option<Self> {
+        let rows: Vec<&Vec<f32>> = vectors.iter().filter(|v| v.len() == dim).collect();
+        if dim == 0 || rows.len() < PCA_MIN_VECTORS {
+            return None;
         }
 
-        let v = Array1::from_vec(vec.to_vec());
+        let mut data = Array2::<f32>::zeros((rows.len(), dim));
+        for (mut row, vector) in data.rows_mut().into_iter().zip(&rows) {
+            row.assign(&ArrayView1::from(vector.as_slice()));
+        }
+        let mean = data.mean_axis(Axis(0))?;
+        data -= &mean;
+        let mut covariance = data.t().dot(&data) / (rows.len() - 1) as f32;
+        let total = covariance.diag().sum();
+        if !total.is_finite() || total <= 0.0 {
+            return None;
+        }
+
+        // Power iteration from a seeded start, deflating after each component
+        let mut rng = StdRng::seed_from_u64(PROJECTION_SEED);
+        let mut matrix = Array2::<f32>::zeros((dim, 3));
+        let mut explained = [0.0; 3];
+        for (j, share) in explained.iter_mut().enumerate() {
+            let mut v: Array1<f32> = (0..dim).map(|_| rng.sample(StandardNormal)).collect();
+            v /= v.dot(&v).sqrt();
+            for _ in 0..PCA_ITERATIONS {
+                let next = covariance.dot(&v);
+                let norm = next.dot(&next).sqrt();
+                // No variance left outside the components already found
+                if norm <= f32::EPSILON {
+                    break;
+                }
+                v = next / norm;
+            }
+            let eigenvalue = v.dot(&covariance.dot(&v)).max(0.0);
+            let column = v.view().insert_axis(Axis(1));
+            covariance -= &(column.dot(&column.t()) * eigenvalue);
+            matrix.column_mut(j).assign(&v);
+            *share = eigenvalue / total;
+        }
+
+        Some(Self {
+            matrix,
+            mean,
+            is_trained: true,
+            explained_variance: Some(explained),
+            fitted_at: None,
+            retrain_requested: false,
+            refit: RefitPolicy::default(),
+            blend: None,
+            positions: PositionSmoother::default(),
+        })
+    }
+
+    /// Project a vector to 3D; None if it isn't `dim()` long or holds NaN/inf
+    pub fn project(&self, vec: &[f32]) -> Option<(f32, f32, f32)> {
+        if vec.len() != self.dim() || !vec.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+
+        let v = &ArrayView1::from(vec) - &self.mean;
         let result = v.dot(&self.matrix);
 
-        (result[0], result[1], result[2])
+        Some((result[0], result[1], result[2]))
     }
 }
 
-/// Fetch recent vectors from Qdrant and project to 3D
+/// The most common non-zero vector length in `vectors` (ties go to the larger)
+fn dominant_dim<'a>(vectors: impl Iterator<Item = &'a Vec<f32>>) -> Option<usize> {
+    let mut counts = std::collections::HashMap::<usize, usize>::new();
+    for v in vectors.filter(|v| !v.is_empty()) {
+        *counts.entry(v.len()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(dim, count)| (count, dim))
+        .map(|(dim, _)| dim)
+}
+
+/// Kinship graph over equal-length `vectors`, as (index, index, similarity)
+/// with the lower index first: each vector's `max_edges` nearest neighbours
+/// by cosine similarity, if at least `min_similarity`, accepted strongest
+/// first while neither end already has `max_edges`
+pub fn kinship_edges(
+    vectors: &[Vec<f32>],
+    max_edges: usize,
+    min_similarity: f32,
+) -> Vec<(usize, usize, f32)> {
+    let n = vectors.len();
+    let dim = vectors.first().map_or(0, Vec::len);
+    if n < 2 || dim == 0 || max_edges == 0 {
+        return Vec::new();
+    }
+
+    let mut unit = Array2::<f32>::zeros((n, dim));
+    for (mut row, vector) in unit.rows_mut().into_iter().zip(vectors) {
+        row.assign(&ArrayView1::from(vector.as_slice()));
+        let norm = row.dot(&row).sqrt();
+        if norm > 0.0 {
+            row /= norm;
+        }
+    }
+    let similarity = unit.dot(&unit.t());
+
+    let mut candidates = Vec::new();
+    for (i, row) in similarity.rows().into_iter().enumerate() {
+        let mut nearest: Vec<(usize, f32)> = row
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(j, s)| j != i && s >= min_similarity)
+            .collect();
+        nearest.sort_by(|a, b| b.1.total_cmp(&a.1));
+        nearest.truncate(max_edges);
+        candidates.extend(nearest.into_iter().map(|(j, s)| (i.min(j), i.max(j), s)));
+    }
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut degree = vec![0; n];
+    let mut linked = std::collections::HashSet::new();
+    let mut edges = Vec::new();
+    for (a, b, s) in candidates {
+        if degree[a] < max_edges && degree[b] < max_edges && linked.insert((a, b)) {
+            degree[a] += 1;
+            degree[b] += 1;
+            edges.push((a, b, s));
+        }
+    }
+    edges
+}
+
+/// Parse a comma-separated field list, falling back to `DEFAULT_PREVIEW_FIELDS`
+pub fn parse_preview_fields(raw: Option<&str>) -> Vec<String> {
+    let fields: Vec<String> = raw
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect();
+    if fields.is_empty() {
+        DEFAULT_PREVIEW_FIELDS
+            .iter()
+            .map(|f| f.to_string())
+            .collect()
+    } else {
+        fields
+    }
+}
+
+/// The category field name: `DEFAULT_CATEGORY_FIELD` when unset, None
+/// (categories off) when set but blank
+pub fn parse_category_field(raw: Option<&str>) -> Option<String> {
+    match raw {
+        None => Some(DEFAULT_CATEGORY_FIELD.to_string()),
+        Some(field) => Some(field.trim().to_string()).filter(|f| !f.is_empty()),
+    }
+}
+
+/// Cut `text` to `PREVIEW_MAX_CHARS` on a character boundary
+fn truncate_preview(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(PREVIEW_MAX_CHARS) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// Fetch `store`'s vectors from `source`, project to 3D and cluster into
+/// `clusters` groups (see `MetricsSource::vectors` for `limit` and `sample`).
+///
+/// While the projection is still random, a conscious fetch of at least
+/// `PCA_MIN_VECTORS` fits PCA on those vectors and keeps it for this and every
+/// later call, until a refit is due or requested (see
+/// `ProjectionState::wants_fit`). Unconscious fetches never resize or fit the
+/// projection: they are drawn on the conscious axes, so the layers line up.
+///
+/// With `kinship`, also links each point to its nearest neighbours in the
+/// original space (see `kinship_edges`), unless there are more than
+/// `KINSHIP_MAX_POINTS` of them. With `smooth`, points ease from where they
+/// were last served (see `PositionSmoother`); lookups that aren't drawn pass
+/// false so they don't move what clients see.
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_manifold_points(
-    qdrant_url: &str,
-    projection: &ProjectionState,
+    source: &dyn MetricsSource,
+    projection: &SharedProjection,
+    store: MemoryStore,
     limit: u32,
-) -> Result<Vec<ManifoldPoint>, Box<dyn std::error::Error + Send + Sync>> {
-    let client = qdrant_client::Qdrant::from_url(qdrant_url).build()?;
+    sample: bool,
+    clusters: usize,
+    fields: &PayloadFields,
+    kinship: bool,
+    smooth: bool,
+) -> Result<(Vec<ManifoldPoint>, Vec<ClusterCentroid>, Vec<ManifoldEdge>), FetchError> {
+    let fetched = source.vectors(store, limit, sample, fields).await?;
+    let shapes_projection = store == MemoryStore::Conscious;
 
-    // Scroll through conscious memories (Phase 2: forward-only embeddings)
-    let result = client
-        .scroll(
-            ScrollPointsBuilder::new("memories")
-                .limit(limit)
-                .with_payload(true)
-                .with_vectors(true),
-        )
-        .await?;
+    // Size the projection to the embeddings actually stored; a different
+    // model means a different space, so any PCA fit starts over too
+    let Some(dim) = dominant_dim(fetched.iter().map(|(v, _)| v)) else {
+        return Ok((Vec::new(), Vec::new(), Vec::new()));
+    };
+    if shapes_projection && projection.read().await.dim() != dim {
+        let mut projection = projection.write().await;
+        if projection.dim() != dim {
+            info!(
+                from = projection.dim(),
+                to = dim,
+                "Manifold vectors changed dimension, resetting the projection"
+            );
+            projection.reset(dim);
+        }
+    }
+
+    if shapes_projection
+        && fetched.len() >= PCA_MIN_VECTORS
+        && projection.read().await.wants_fit(Utc::now())
+    {
+        let vectors: Vec<Vec<f32>> = fetched.iter().map(|(v, _)| v.clone()).collect();
+        // ~0.3 GFLOP for a full window; keep it off the async workers
+        if let Ok(Some(fitted)) =
+            tokio::task::spawn_blocking(move || ProjectionState::pca(&vectors, dim)).await
+        {
+            let mut projection = projection.write().await;
+            let now = Utc::now();
+            // Another fetch may have fitted while this one was computing
+            if projection.wants_fit(now) && projection.dim() == dim {
+                if projection.is_trained {
+                    info!(vectors = fetched.len(), explained_variance = ?fitted.explained_variance, blend_ms = projection.refit_policy().blend_ms, "Manifold projection refit");
+                } else {
+                    info!(vectors = fetched.len(), explained_variance = ?fitted.explained_variance, "Manifold projection switched to PCA");
+                }
+                projection.adopt(fitted, now);
+            }
+        }
+    }
+
+    // Project to 3D
+    let guard = projection.read().await;
+    let applied = guard.at(Utc::now());
+    let fetched_count = fetched.len();
+    let (vectors, mut points): (Vec<Vec<f32>>, Vec<ManifoldPoint>) = fetched
+        .into_iter()
+        .filter_map(|(vector, point)| {
+            let (x, y, z) = applied.project(&vector)?;
+            Some((vector, ManifoldPoint { x, y, z, ..point }))
+        })
+        .unzip();
+    let expected = applied.dim();
+    drop(applied);
+    drop(guard);
+    if points.len() < fetched_count {
+        warn!(
+            collection = store.collection(),
+            skipped = fetched_count - points.len(),
+            expected_dim = expected,
+            "Skipped manifold vectors of the wrong dimension or with non-finite values"
+        );
+    }
+
+    let edges = if kinship && points.len() <= KINSHIP_MAX_POINTS {
+        // Same order of cost as a PCA fit; off the async workers too
+        tokio::task::spawn_blocking(move || {
+            kinship_edges(&vectors, KINSHIP_MAX_EDGES, KINSHIP_MIN_SIMILARITY)
+        })
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(a, b, similarity)| ManifoldEdge {
+            source: points[a].id.clone(),
+            target: points[b].id.clone(),
+            similarity,
+        })
+        .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Before clustering, so centroids sit among the points as served
+    if smooth {
+        projection
+            .write()
+            .await
+            .positions
+            .smooth(store, &mut points, Utc::now());
+    }
+    let centroids = cluster_points(&mut points, clusters);
+    Ok((points, centroids, edges))
+}
+
+/// Memory vectors and their unprojected points from Qdrant: the first
+/// `limit` points it scrolls out, or with `sample` a uniform random sample of
+/// up to `limit` from the whole collection (see `sample_point_ids`), which
+/// costs a scroll of every id. Each point's preview comes from the first of
+/// `fields.preview` holding a string, its category from `fields.category`.
+pub async fn scroll_memory_vectors(
+    client: &qdrant_client::Qdrant,
+    collection: &'static str,
+    limit: u32,
+    sample: bool,
+    fields: &PayloadFields,
+) -> Result<MemoryVectors, FetchError> {
+    use qdrant_client::qdrant::GetPointsBuilder;
+
+    // A fresh Qdrant has no collection until the first memory is written
+    if !client.collection_exists(collection).await? {
+        return Err(FetchError::NotFound(format!(
+            "collection \"{}\"",
+            collection
+        )));
+    }
+
+    // Scroll through the memories (Phase 2: forward-only embeddings)
+    let retrieved = if sample {
+        let ids = sample_point_ids(client, collection, limit as usize).await?;
+        if ids.is_empty() {
+            Vec::new()
+        } else {
+            client
+                .get_points(
+                    GetPointsBuilder::new(collection, ids)
+                        .with_payload(true)
+                        .with_vectors(true),
+                )
+                .await?
+                .result
+        }
+    } else {
+        client
+            .scroll(
+                ScrollPointsBuilder::new(collection)
+                    .limit(limit)
+                    .with_payload(true)
+                    .with_vectors(true),
+            )
+            .await?
+            .result
+    };
 
     let now_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_millis() as u64;
 
-    let points: Vec<ManifoldPoint> = result
-        .result
+    Ok(retrieved
         .into_iter()
         .filter_map(|point| {
             // Extract vector using get_vector() helper (qdrant-client 1.x API)
@@ -156,8 +891,20 @@ pub async fn fetch_manifold_points(
 
             let age_ms = now_ms.saturating_sub(created_ms);
 
-            // Project to 3D
-            let (x, y, z) = projection.project(&vector);
+            let preview = fields
+                .preview
+                .iter()
+                .find_map(|field| point.payload.get(field).and_then(|v| v.as_str()))
+                .map(|text| truncate_preview(text))
+                .filter(|text| !text.is_empty());
+
+            let category = fields
+                .category
+                .as_ref()
+                .and_then(|field| point.payload.get(field))
+                .and_then(|v| v.as_str())
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty());
 
             // Extract ID
             let id = match &point.id {
@@ -169,18 +916,218 @@ pub async fn fetch_manifold_points(
                 None => "unknown".to_string(),
             };
 
-            Some(ManifoldPoint {
-                x,
-                y,
-                z,
+            let point = ManifoldPoint {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
                 salience,
                 age_ms,
                 id,
-            })
+                cluster: 0,
+                preview,
+                category,
+            };
+            Some((vector, point))
         })
-        .collect();
+        .collect())
+}
+
+/// Uniform random sample of at most `capacity` items from a stream of
+/// unknown length (Algorithm R)
+struct Reservoir<T> {
+    items: Vec<T>,
+    capacity: usize,
+    seen: usize,
+    rng: StdRng,
+}
+
+impl<T> Reservoir<T> {
+    fn new(capacity: usize, seed: u64) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            capacity,
+            seen: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn offer(&mut self, item: T) {
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+        } else {
+            // Keep the n-th item with probability capacity / n
+            let slot = self.rng.random_range(0..self.seen);
+            if slot < self.capacity {
+                self.items[slot] = item;
+            }
+        }
+    }
+}
+
+/// Ids of a uniform sample of up to `limit` memories, scrolling ids only
+/// (no payloads or vectors) through at most `SAMPLE_MAX_PAGES` pages
+async fn sample_point_ids(
+    client: &qdrant_client::Qdrant,
+    collection: &str,
+    limit: usize,
+) -> Result<Vec<qdrant_client::qdrant::PointId>, FetchError> {
+    let mut reservoir = Reservoir::new(limit, SAMPLE_SEED);
+    let mut offset = None;
+    for _ in 0..SAMPLE_MAX_PAGES {
+        let mut request = ScrollPointsBuilder::new(collection)
+            .limit(SAMPLE_PAGE_SIZE)
+            .with_payload(false)
+            .with_vectors(false);
+        if let Some(offset) = offset.take() {
+            request = request.offset(offset);
+        }
+        let page = client.scroll(request).await?;
+        for id in page.result.into_iter().filter_map(|p| p.id) {
+            reservoir.offer(id);
+        }
+        match page.next_page_offset {
+            Some(next) => offset = Some(next),
+            None => break,
+        }
+    }
+    Ok(reservoir.items)
+}
+
+/// Look up one memory of `collection` by manifold point id (numeric or
+/// UUID); None if it doesn't exist
+pub async fn fetch_thought(
+    client: &qdrant_client::Qdrant,
+    collection: &str,
+    id: &str,
+) -> Result<Option<ThoughtDetail>, FetchError> {
+    use qdrant_client::qdrant::{GetPointsBuilder, PointId};
+
+    if !client.collection_exists(collection).await? {
+        return Ok(None);
+    }
+
+    let point_id: PointId = match id.parse::<u64>() {
+        Ok(num) => num.into(),
+        Err(_) => id.into(),
+    };
+    let result = client
+        .get_points(GetPointsBuilder::new(collection, vec![point_id]).with_payload(true))
+        .await?;
+
+    Ok(result.result.first().map(|point| {
+        let payload = &point.payload;
+        ThoughtDetail {
+            id: id.to_string(),
+            content: payload
+                .get("content")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            salience: payload
+                .get("semantic_salience")
+                .and_then(|v| v.as_double())
+                .map(|v| v as f32)
+                .unwrap_or(0.5),
+            encoded_at: payload
+                .get("encoded_at")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+        }
+    }))
+}
+
+/// Lloyd's k-means over the projected coordinates
+///
+/// Seeds with farthest-point initialisation (deterministic, so colors don't
+/// reshuffle between fetches of the same data), assigns each point's
+/// `cluster`, and returns the centroids numbered largest cluster first.
+pub fn cluster_points(points: &mut [ManifoldPoint], k: usize) -> Vec<ClusterCentroid> {
+    let k = k.min(MAX_MANIFOLD_CLUSTERS).min(points.len());
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let position = |p: &ManifoldPoint| [p.x, p.y, p.z];
+    let dist2 = |a: [f32; 3], b: [f32; 3]| {
+        (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+    };
+    let nearest = |centres: &[[f32; 3]], at: [f32; 3]| {
+        centres
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, dist2(*c, at)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0))
+    };
+
+    // Farthest-point seeding
+    let mut centres = vec![position(&points[0])];
+    while centres.len() < k {
+        let farthest = points
+            .iter()
+            .map(|p| nearest(&centres, position(p)).1)
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        centres.push(position(&points[farthest]));
+    }
+
+    let mut assignment = vec![0usize; points.len()];
+    for iteration in 0..KMEANS_MAX_ITERATIONS {
+        let mut changed = false;
+        for (slot, point) in assignment.iter_mut().zip(points.iter()) {
+            let (best, _) = nearest(&centres, position(point));
+            changed |= *slot != best;
+            *slot = best;
+        }
+        if !changed && iteration > 0 {
+            break;
+        }
+
+        let mut sums = vec![([0.0f32; 3], 0usize); k];
+        for (&c, point) in assignment.iter().zip(points.iter()) {
+            let [x, y, z] = position(point);
+            sums[c].0[0] += x;
+            sums[c].0[1] += y;
+            sums[c].0[2] += z;
+            sums[c].1 += 1;
+        }
+        for (centre, (sum, n)) in centres.iter_mut().zip(sums) {
+            // An emptied cluster keeps its previous centre
+            if n > 0 {
+                *centre = sum.map(|v| v / n as f32);
+            }
+        }
+    }
 
-    Ok(points)
+    // Renumber largest-first so cluster 0 is always the dominant colour
+    let mut sizes = vec![0usize; k];
+    for &c in &assignment {
+        sizes[c] += 1;
+    }
+    let mut order: Vec<usize> = (0..k).collect();
+    order.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]));
+    let mut rank = vec![0u8; k];
+    for (r, &c) in order.iter().enumerate() {
+        rank[c] = r as u8;
+    }
+
+    for (point, &c) in points.iter_mut().zip(&assignment) {
+        point.cluster = rank[c];
+    }
+    order
+        .iter()
+        .filter(|&&c| sizes[c] > 0)
+        .map(|&c| ClusterCentroid {
+            cluster: rank[c],
+            x: centres[c][0],
+            y: centres[c][1],
+            z: centres[c][2],
+            size: sizes[c],
+        })
+        .collect()
 }
 
 /// Generate Law Crystal positions
@@ -221,9 +1168,415 @@ pub fn get_law_crystals(_projection: &ProjectionState) -> Vec<LawCrystal> {
     ]
 }
 
+/// GET /crystals/{law}/nearest response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CrystalNeighbors {
+    pub crystal: LawCrystal,
+    /// Nearest first
+    pub thoughts: Vec<NearestThought>,
+}
+
+/// A manifold point and its distance from a Law Crystal
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NearestThought {
+    #[serde(flatten)]
+    pub point: ManifoldPoint,
+    /// Euclidean distance in the projected 3D space
+    pub distance: f32,
+}
+
+/// The `k` points closest to `crystal` in the projected space, nearest first
+pub fn nearest_to_crystal(
+    points: &[ManifoldPoint],
+    crystal: &LawCrystal,
+    k: usize,
+) -> Vec<NearestThought> {
+    let mut nearest: Vec<NearestThought> = points
+        .iter()
+        .map(|p| NearestThought {
+            point: p.clone(),
+            distance: ((p.x - crystal.x).powi(2)
+                + (p.y - crystal.y).powi(2)
+                + (p.z - crystal.z).powi(2))
+            .sqrt(),
+        })
+        .collect();
+    nearest.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    nearest.truncate(k);
+    nearest
+}
+
+/// Weak ETag for a manifold response
+///
+/// Covers everything but `age_ms`, which ticks on every request while nothing
+/// else changes; a client revalidating against this keeps slightly stale ages
+/// until a thought arrives, leaves or moves. Weak because the bodies it
+/// matches are equivalent rather than byte-identical.
+pub fn manifold_etag(manifold: &ManifoldResponse) -> String {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    manifold.projection_type.hash(&mut hasher);
+    manifold
+        .explained_variance
+        .map(|shares| shares.map(f32::to_bits))
+        .hash(&mut hasher);
+    manifold.message.hash(&mut hasher);
+    manifold.last_refit.hash(&mut hasher);
+    manifold.layer.hash(&mut hasher);
+    for p in &manifold.points {
+        p.id.hash(&mut hasher);
+        [p.x, p.y, p.z, p.salience]
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+        p.cluster.hash(&mut hasher);
+        p.preview.hash(&mut hasher);
+        p.category.hash(&mut hasher);
+    }
+    for c in &manifold.crystals {
+        c.law.hash(&mut hasher);
+        [c.x, c.y, c.z].map(f32::to_bits).hash(&mut hasher);
+    }
+    for c in &manifold.centroids {
+        c.cluster.hash(&mut hasher);
+        [c.x, c.y, c.z].map(f32::to_bits).hash(&mut hasher);
+        c.size.hash(&mut hasher);
+    }
+    for e in &manifold.edges {
+        e.source.hash(&mut hasher);
+        e.target.hash(&mut hasher);
+    }
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
 /// Shared projection state with caching
 pub type SharedProjection = Arc<RwLock<ProjectionState>>;
 
-pub fn create_projection() -> SharedProjection {
-    Arc::new(RwLock::new(ProjectionState::random()))
+/// Random projection for `dim`-dimensional vectors, resized on the first fetch if the
+/// collection holds something else
+pub fn create_projection(
+    dim: usize,
+    refit: RefitPolicy,
+    positions: PositionSmoother,
+) -> SharedProjection {
+    Arc::new(RwLock::new(
+        ProjectionState::random(dim)
+            .with_refit(refit)
+            .with_smoothing(positions),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_projection_is_reproducible() {
+        let a = ProjectionState::random(768);
+        let b = ProjectionState::random(768);
+        assert_eq!(a.matrix, b.matrix);
+        for j in 0..3 {
+            let norm: f32 = a.matrix.column(j).iter().map(|v| v * v).sum::<f32>().sqrt();
+            assert!((norm - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_pca_finds_dominant_axes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut noise = || rng.sample::<f32, _>(StandardNormal);
+        let vectors: Vec<Vec<f32>> = (0..PCA_MIN_VECTORS * 2)
+            .map(|_| {
+                let mut v: Vec<f32> = (0..768).map(|_| 0.01 * noise()).collect();
+                v[3] += 1.0 + 3.0 * noise();
+                v[10] += 2.0 * noise();
+                v
+            })
+            .collect();
+
+        let pca = ProjectionState::pca(&vectors, 768).unwrap();
+        assert!(pca.is_trained);
+        let [x, y, z] = pca.explained_variance.unwrap();
+        assert!(x > y && y > z, "{x} {y} {z}");
+        assert!(x + y < 1.0 && x + y > 0.9);
+        assert!(pca.matrix[[3, 0]].abs() > 0.99);
+        assert!(pca.matrix[[10, 1]].abs() > 0.99);
+
+        // Centred: the mean vector lands on the origin
+        let mean = pca.mean.to_vec();
+        let (mx, my, mz) = pca.project(&mean).unwrap();
+        assert!(mx.abs() < 1e-4 && my.abs() < 1e-4 && mz.abs() < 1e-4);
+
+        assert!(ProjectionState::pca(&vectors[..PCA_MIN_VECTORS - 1], 768).is_none());
+        assert!(ProjectionState::pca(&vec![vec![0.5; 768]; PCA_MIN_VECTORS], 768).is_none());
+    }
+
+    #[test]
+    fn test_refit_keeps_axis_signs_and_blends() {
+        let fit = |columns: [[f32; 4]; 3]| {
+            let mut fitted = ProjectionState::random(4);
+            fitted.matrix = Array2::from_shape_fn((4, 3), |(i, j)| columns[j][i]);
+            fitted.is_trained = true;
+            fitted
+        };
+        let t0 = Utc::now();
+        let mut state = ProjectionState::random(4).with_refit(RefitPolicy {
+            every_secs: 60,
+            blend_ms: 1000,
+        });
+        assert!(state.wants_fit(t0));
+
+        // The first fit replaces the random projection outright
+        let first = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ];
+        state.adopt(fit(first), t0);
+        assert_eq!(state.fitted_at, Some(t0));
+        assert!(matches!(state.at(t0), Cow::Borrowed(_)));
+        assert!(!state.wants_fit(t0 + TimeDelta::seconds(59)));
+        assert!(state.wants_fit(t0 + TimeDelta::seconds(60)));
+        state.retrain_requested = true;
+        assert!(state.wants_fit(t0));
+
+        // A refit with a flipped first axis: flipped back, then eased in
+        let t1 = t0 + TimeDelta::seconds(60);
+        state.adopt(
+            fit([
+                [-1.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ]),
+            t1,
+        );
+        assert!(!state.retrain_requested);
+        assert_eq!(state.matrix[[0, 0]], 1.0);
+        assert_eq!(state.at(t1).matrix, fit(first).matrix);
+        let halfway = state.at(t1 + TimeDelta::milliseconds(500));
+        assert!((halfway.matrix[[1, 1]] - 0.5).abs() < 1e-6);
+        assert!((halfway.matrix[[3, 1]] - 0.5).abs() < 1e-6);
+        assert!(matches!(
+            state.at(t1 + TimeDelta::seconds(1)),
+            Cow::Borrowed(_)
+        ));
+
+        // Fit once: never due again
+        let mut once = state.clone().with_refit(RefitPolicy {
+            every_secs: 0,
+            blend_ms: 0,
+        });
+        assert!(!once.wants_fit(t1 + TimeDelta::days(1)));
+        once.adopt(fit(first), t1);
+        assert!(matches!(once.at(t1), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_positions_ease_toward_projection() {
+        let thought = |x| ManifoldPoint {
+            id: "thought".to_string(),
+            ..point_at(x, 0.0, 0.0)
+        };
+        let t0 = Utc::now();
+        let (conscious, unconscious) = (MemoryStore::Conscious, MemoryStore::Unconscious);
+        let mut smoother = PositionSmoother::new(1000);
+        let mut points = vec![thought(0.0)];
+        smoother.smooth(conscious, &mut points, t0);
+        assert_eq!(points[0].x, 0.0);
+
+        // One time constant later: 1 - 1/e of the way to the new projection
+        let mut moved = vec![thought(1.0)];
+        smoother.smooth(conscious, &mut moved, t0 + TimeDelta::seconds(1));
+        assert!((moved[0].x - (1.0 - (-1.0f32).exp())).abs() < 1e-5);
+
+        // Close enough: snaps onto it
+        let mut settled = vec![thought(1.0)];
+        smoother.smooth(conscious, &mut settled, t0 + TimeDelta::seconds(20));
+        assert_eq!(settled[0].x, 1.0);
+
+        // The same id on the other layer is a different point
+        let mut other_layer = vec![thought(-3.0)];
+        smoother.smooth(unconscious, &mut other_layer, t0 + TimeDelta::seconds(21));
+        assert_eq!(other_layer[0].x, -3.0);
+        let mut still = vec![thought(1.0)];
+        smoother.smooth(conscious, &mut still, t0 + TimeDelta::seconds(22));
+        assert_eq!(still[0].x, 1.0);
+
+        // Forgotten after a long absence: starts where projected
+        let mut returned = vec![thought(-1.0)];
+        smoother.smooth(
+            conscious,
+            &mut returned,
+            t0 + TimeDelta::seconds(20 + SMOOTHING_FORGET_SECS),
+        );
+        assert_eq!(returned[0].x, -1.0);
+
+        let mut off = PositionSmoother::new(0);
+        off.smooth(conscious, &mut points, t0);
+        let mut jumped = vec![thought(5.0)];
+        off.smooth(conscious, &mut jumped, t0);
+        assert_eq!(jumped[0].x, 5.0);
+    }
+
+    #[test]
+    fn test_projection_sized_to_vectors() {
+        let vectors = [vec![0.1; 384], vec![0.2; 384], vec![0.3; 768], Vec::new()];
+        assert_eq!(dominant_dim(vectors.iter()), Some(384));
+        assert_eq!(dominant_dim([Vec::new()].iter()), None);
+
+        let projection = ProjectionState::random(384);
+        assert_eq!(projection.dim(), 384);
+        let (x, y, z) = projection.project(&vectors[0]).unwrap();
+        assert!(x != 0.0 || y != 0.0 || z != 0.0);
+        assert!(projection.project(&vectors[2]).is_none());
+        let mut poisoned = vectors[0].clone();
+        poisoned[7] = f32::NAN;
+        assert!(projection.project(&poisoned).is_none());
+    }
+
+    fn point_at(x: f32, y: f32, z: f32) -> ManifoldPoint {
+        ManifoldPoint {
+            x,
+            y,
+            z,
+            salience: 0.5,
+            age_ms: 0,
+            id: format!("{x},{y},{z}"),
+            cluster: 0,
+            preview: None,
+            category: None,
+        }
+    }
+
+    #[test]
+    fn test_cluster_points_separates_blobs() {
+        let mut points: Vec<ManifoldPoint> = (0..6)
+            .map(|i| point_at(i as f32 * 0.01, 0.0, 0.0))
+            .chain((0..3).map(|i| point_at(5.0 + i as f32 * 0.01, 5.0, 0.0)))
+            .collect();
+
+        let centroids = cluster_points(&mut points, 2);
+
+        assert_eq!(centroids.len(), 2);
+        assert_eq!(centroids[0].size, 6);
+        assert_eq!(centroids[1].size, 3);
+        assert!(points[..6].iter().all(|p| p.cluster == 0));
+        assert!(points[6..].iter().all(|p| p.cluster == 1));
+    }
+
+    #[test]
+    fn test_cluster_points_caps_k() {
+        let mut points = vec![point_at(0.0, 0.0, 0.0), point_at(1.0, 1.0, 1.0)];
+        assert_eq!(cluster_points(&mut points, 8).len(), 2);
+        assert!(cluster_points(&mut [], 4).is_empty());
+    }
+
+    #[test]
+    fn test_preview_fields_and_truncation() {
+        assert_eq!(parse_preview_fields(None), vec!["content", "text"]);
+        assert_eq!(
+            parse_preview_fields(Some(" body , ,text")),
+            vec!["body", "text"]
+        );
+
+        assert_eq!(truncate_preview("  short thought "), "short thought");
+        let long = "é".repeat(PREVIEW_MAX_CHARS + 5);
+        let cut = truncate_preview(&long);
+        assert_eq!(cut.chars().count(), PREVIEW_MAX_CHARS + 1);
+        assert!(cut.ends_with('…'));
+
+        assert_eq!(parse_category_field(None).as_deref(), Some("memory_type"));
+        assert_eq!(
+            parse_category_field(Some(" kind ")).as_deref(),
+            Some("kind")
+        );
+        assert_eq!(parse_category_field(Some(" ")), None);
+    }
+
+    #[test]
+    fn test_nearest_to_crystal() {
+        let crystal = &get_law_crystals(&ProjectionState::random(768))[1];
+        let points = vec![
+            point_at(0.0, 0.0, 0.0),
+            point_at(1.4, -0.5, 0.1),
+            point_at(1.0, -0.5, 0.0),
+        ];
+        let nearest = nearest_to_crystal(&points, crystal, 2);
+        assert_eq!(nearest.len(), 2);
+        assert_eq!(nearest[0].point.id, points[1].id);
+        assert!((nearest[0].distance - 0.1).abs() < 1e-5);
+        assert_eq!(nearest[1].point.id, points[2].id);
+    }
+
+    #[test]
+    fn test_kinship_edges_cap_degree_and_threshold() {
+        // A tight star around the first vector, plus one unrelated vector
+        let vectors = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.99, 0.1, 0.0],
+            vec![0.98, 0.0, 0.2],
+            vec![0.97, -0.2, 0.0],
+            vec![0.96, 0.0, -0.25],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let edges = kinship_edges(&vectors, 2, 0.9);
+        let mut degree = [0; 6];
+        for &(a, b, s) in &edges {
+            assert!(a < b);
+            assert!(s >= 0.9);
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+        assert!(degree.iter().all(|&d| d <= 2));
+        assert_eq!(degree[5], 0);
+        // The strongest pair is always kept
+        assert!(edges.iter().any(|&(a, b, _)| (a, b) == (0, 1)));
+
+        assert!(kinship_edges(&vectors, 0, 0.9).is_empty());
+        assert!(kinship_edges(&vectors[..1], 3, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_manifold_etag_ignores_age() {
+        let manifold = |age_ms, x| ManifoldResponse {
+            points: vec![ManifoldPoint {
+                age_ms,
+                ..point_at(x, 0.0, 0.0)
+            }],
+            crystals: Vec::new(),
+            centroids: Vec::new(),
+            edges: Vec::new(),
+            projection_type: "random".to_string(),
+            explained_variance: None,
+            dimension: 768,
+            message: None,
+            last_refit: None,
+            layer: MemoryStore::Conscious,
+        };
+        let etag = manifold_etag(&manifold(0, 1.0));
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(etag, manifold_etag(&manifold(5_000, 1.0)));
+        assert_ne!(etag, manifold_etag(&manifold(0, 2.0)));
+    }
+
+    #[test]
+    fn test_reservoir_samples_uniformly() {
+        // Fewer items than capacity: everything is kept, in order
+        let mut small = Reservoir::new(10, SAMPLE_SEED);
+        (0..4).for_each(|i| small.offer(i));
+        assert_eq!(small.items, [0, 1, 2, 3]);
+
+        // Each of 100 items should land in a 10-item sample about 10% of the time
+        let mut hits = [0u32; 100];
+        for seed in 0..2000 {
+            let mut reservoir = Reservoir::new(10, seed);
+            (0..100).for_each(|i| reservoir.offer(i));
+            assert_eq!(reservoir.items.len(), 10);
+            for &i in &reservoir.items {
+                hits[i] += 1;
+            }
+        }
+        // Expected 200 per item; the head and tail get no special treatment
+        assert!(hits.iter().all(|&h| (120..=280).contains(&h)), "{hits:?}");
+    }
 }